@@ -1,7 +1,6 @@
 use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer};
-use serde_json;
 use std::collections::HashMap;
 
 pub struct Backend {
@@ -17,14 +16,14 @@ impl Backend {
         }
     }
 
-    async fn validate_agsi_document(&self, uri: &Url, text: &str) -> Vec<Diagnostic> {
+    async fn validate_agsi_document(&self, _uri: &Url, text: &str) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
         // Try to parse as JSON
         match serde_json::from_str::<serde_json::Value>(text) {
             Ok(json_value) => {
                 // Check for required AGSi fields
-                if !json_value.get("agsFile").is_some() {
+                if json_value.get("agsFile").is_none() {
                     diagnostics.push(Diagnostic {
                         range: Range {
                             start: Position { line: 0, character: 0 },
@@ -41,7 +40,7 @@ impl Backend {
                     });
                 }
 
-                if !json_value.get("agsiModel").is_some() {
+                if json_value.get("agsiModel").is_none() {
                     diagnostics.push(Diagnostic {
                         range: Range {
                             start: Position { line: 0, character: 0 },
@@ -65,7 +64,7 @@ impl Backend {
                             for (elem_idx, element) in elements.iter().enumerate() {
                                 if let Some(params) = element.get("agsiDataParameterValue").and_then(|v| v.as_array()) {
                                     for (param_idx, param) in params.iter().enumerate() {
-                                        if !param.get("codeID").is_some() {
+                                        if param.get("codeID").is_none() {
                                             diagnostics.push(Diagnostic {
                                                 range: Range {
                                                     start: Position { line: (model_idx + elem_idx + param_idx) as u32, character: 0 },
@@ -171,7 +170,7 @@ impl LanguageServer for Backend {
             .await;
     }
 
-    async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
+    async fn completion(&self, _params: CompletionParams) -> Result<Option<CompletionResponse>> {
         let agsi_completions = vec![
             CompletionItem {
                 label: "UnitWeight".to_string(),
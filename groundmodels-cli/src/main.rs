@@ -1,12 +1,18 @@
 use clap::{Parser, Subcommand};
-use groundmodels_core::{ConvertType, SoilParams, GroundModel, SoilType};
+use groundmodels_core::{ConvertType, SoilParams, GroundModel, SoilType, DrainageCondition};
+use groundmodels_core::agsi::AgsiModelElement;
+use groundmodels_core::agsi_io::{load_agsi, select_model};
 use groundmodels_core::soil_description::{
     parse_soil_description, validate_soil_description, generate_description,
     ValidationOptions as DescValidationOptions, SoilDescription,
     StrengthParameterType as DescStrengthType,
 };
+use groundmodels_core::construction::ConstructionSequence;
+use groundmodels_core::earth_pressure::{EarthPressureState, WallGeometry, WallSide};
+use groundmodels_core::settlement::{ConsolidationOptions, StressChange};
 use groundmodels_core::strip_log::{BuildStripLogOptions, StripLogRenderOptions};
-use serde_json;
+use groundmodels_core::units::Stress;
+use groundmodels_core::validation::{Severity, ValidationRules};
 use std::fs;
 use std::path::PathBuf;
 use std::collections::HashMap;
@@ -14,7 +20,6 @@ use jsonschema::Validator;
 use dialoguer::{Input, Select, MultiSelect, Confirm};
 use console::Style;
 use tower_lsp::{LspService, Server};
-use tower_lsp::lsp_types::*;
 use tokio::net::TcpListener;
 use tabled::{Table as TabledTable, Tabled};
 use docx_rs::*;
@@ -46,6 +51,24 @@ enum Commands {
         #[arg(short, long)]
         schema: Option<PathBuf>,
     },
+    /// Check a ground model for implausible parameters and layer geometry; exits non-zero on errors
+    Check {
+        /// Input AGSi JSON file
+        #[arg(short, long)]
+        input: PathBuf,
+        /// AGSi model to use, by modelID or modelName (defaults to the first model)
+        #[arg(long)]
+        model: Option<String>,
+        /// JSON or TOML rule set disabling rules, overriding severities or changing limits
+        #[arg(long)]
+        rules: Option<PathBuf>,
+        /// Also exit non-zero on warnings
+        #[arg(long, default_value_t = false)]
+        strict: bool,
+        /// Print the issues as JSON
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
     /// Interactive AGSi JSON file generation
     Generate {
         /// Output file path for generated AGSi JSON
@@ -59,6 +82,9 @@ enum Commands {
         /// Export tables to Microsoft Word (.docx) file
         #[arg(long)]
         word: Option<PathBuf>,
+        /// AGSi model to use, by modelID or modelName (defaults to the first model)
+        #[arg(long)]
+        model: Option<String>,
     },
     /// Convert AGSi JSON files to different formats
     Convert {
@@ -73,6 +99,10 @@ enum Commands {
         /// Conversion type
         #[arg(short, long, value_enum)]
         convert_type: CliConvertType,
+
+        /// AGSi model to use, by modelID or modelName (defaults to the first model)
+        #[arg(long)]
+        model: Option<String>,
     },
     /// Add predefined materials to AGSi files
     AddMaterials {
@@ -93,6 +123,9 @@ enum Commands {
         /// Input AGSi JSON file
         #[arg(short, long)]
         input: PathBuf,
+        /// AGSi model to use, by modelID or modelName (defaults to the first model)
+        #[arg(long)]
+        model: Option<String>,
     },
     /// Generate example AGSi data
     Example {
@@ -132,9 +165,12 @@ enum Commands {
     },
     /// Export strip log outputs from a GroundModel JSON
     StripLog {
-        /// Input GroundModel JSON file
+        /// Input GroundModel JSON file (or AGSi JSON file when --model is given)
         #[arg(short, long)]
         input: PathBuf,
+        /// Build the ground model from this AGSi model, by modelID or modelName
+        #[arg(long)]
+        model: Option<String>,
         /// Output SVG path
         #[arg(long)]
         svg: Option<PathBuf>,
@@ -150,9 +186,6 @@ enum Commands {
         /// Include stress calculations
         #[arg(long, default_value_t = false)]
         include_stresses: bool,
-        /// Stress integration dz (m)
-        #[arg(long, default_value_t = 0.05)]
-        dz: f64,
         /// Strip log title
         #[arg(long)]
         title: Option<String>,
@@ -160,6 +193,96 @@ enum Commands {
         #[arg(long, default_value = "m")]
         axis_unit: String,
     },
+    /// Export a lateral earth pressure profile for one side of a wall
+    EarthPressure {
+        /// Input GroundModel JSON file (or AGSi JSON file when --model is given)
+        #[arg(short, long)]
+        input: PathBuf,
+        /// Build the ground model from this AGSi model, by modelID or modelName
+        #[arg(long)]
+        model: Option<String>,
+        /// Level of the top of the wall
+        #[arg(long)]
+        wall_top: f64,
+        /// Level of the toe of the wall
+        #[arg(long)]
+        wall_toe: f64,
+        /// Formation level of the excavated side; the retained side is used when omitted
+        #[arg(long)]
+        formation_level: Option<f64>,
+        /// Earth pressure state
+        #[arg(long, value_enum, default_value = "active")]
+        state: CliEarthPressureState,
+        /// Slope of the retained surface, rising away from the wall (degrees)
+        #[arg(long, default_value_t = 0.0)]
+        backfill_slope: f64,
+        /// Inclination of the wall back from vertical (degrees)
+        #[arg(long, default_value_t = 0.0)]
+        wall_inclination: f64,
+        /// Wall friction as a ratio of phi_prime (δ/φ′)
+        #[arg(long, default_value_t = 0.0)]
+        friction_ratio: f64,
+        /// Use undrained (cu) pressures in cohesive units
+        #[arg(long, default_value_t = false)]
+        undrained: bool,
+        /// Additional surcharge on this side (kPa)
+        #[arg(long, default_value_t = 0.0)]
+        surcharge: f64,
+        /// Output CSV path
+        #[arg(long)]
+        csv: Option<PathBuf>,
+        /// Output SVG path
+        #[arg(long)]
+        svg: Option<PathBuf>,
+        /// Diagram title
+        #[arg(long)]
+        title: Option<String>,
+    },
+    /// Run a sequence of excavation, dewatering, fill and surcharge stages and report the stress history
+    Construction {
+        /// Input GroundModel JSON file (or AGSi JSON file when --model is given)
+        #[arg(short, long)]
+        input: PathBuf,
+        /// Build the ground model from this AGSi model, by modelID or modelName
+        #[arg(long)]
+        model: Option<String>,
+        /// JSON or TOML construction sequence
+        #[arg(long)]
+        sequence: PathBuf,
+        /// Levels at which to report stresses
+        #[arg(long, value_delimiter = ',', required = true)]
+        levels: Vec<f64>,
+        /// Output CSV path
+        #[arg(long)]
+        csv: Option<PathBuf>,
+    },
+    /// Calculate primary consolidation settlement from mv and cv
+    Settlement {
+        /// Input GroundModel JSON file (or AGSi JSON file when --model is given)
+        #[arg(short, long)]
+        input: PathBuf,
+        /// Build the ground model from this AGSi model, by modelID or modelName
+        #[arg(long)]
+        model: Option<String>,
+        /// Plan x coordinate below which the model's loads are applied
+        #[arg(long, default_value_t = 0.0)]
+        x: f64,
+        /// Plan y coordinate below which the model's loads are applied
+        #[arg(long, default_value_t = 0.0)]
+        y: f64,
+        /// CSV of level,stress change (kPa) to use instead of the model's loads
+        #[arg(long)]
+        stress_change: Option<PathBuf>,
+        /// Number of sublayers per unit
+        #[arg(long, default_value_t = 10)]
+        sublayers: usize,
+        /// Times for the time-settlement curve, in years (cv is in m²/year)
+        #[arg(long, value_delimiter = ',')]
+        times: Vec<f64>,
+        /// Output time-settlement curve CSV path
+        #[arg(long)]
+        csv: Option<PathBuf>,
+    },
 }
 
 #[derive(clap::ValueEnum, Clone)]
@@ -168,6 +291,23 @@ enum CliConvertType {
     GroundModel,
 }
 
+#[derive(clap::ValueEnum, Clone)]
+enum CliEarthPressureState {
+    Active,
+    Passive,
+    AtRest,
+}
+
+impl From<CliEarthPressureState> for EarthPressureState {
+    fn from(cli_state: CliEarthPressureState) -> Self {
+        match cli_state {
+            CliEarthPressureState::Active => EarthPressureState::Active,
+            CliEarthPressureState::Passive => EarthPressureState::Passive,
+            CliEarthPressureState::AtRest => EarthPressureState::AtRest,
+        }
+    }
+}
+
 impl From<CliConvertType> for ConvertType {
     fn from(cli_type: CliConvertType) -> Self {
         match cli_type {
@@ -190,22 +330,47 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             validate_agsi_file(&input, schema.as_ref())?;
         }
 
+        Commands::Check { input, model, rules, strict, json } => {
+            let rules = match rules {
+                Some(path) => ValidationRules::load(path)?,
+                None => ValidationRules::default(),
+            };
+            let ground_model = GroundModel::from_agsi_model(&load_agsi(&input)?, model.as_deref())?;
+            let issues = ground_model.validate_with(&rules);
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&issues)?);
+            } else {
+                for issue in &issues {
+                    println!("{}", issue);
+                }
+            }
+
+            let errors = issues.iter().filter(|issue| issue.severity == Severity::Error).count();
+            let warnings = issues.len() - errors;
+            if !json {
+                println!("{} error(s), {} warning(s)", errors, warnings);
+            }
+            if errors > 0 || (strict && warnings > 0) {
+                return Err("Check failed".into());
+            }
+        }
+
         Commands::Generate { output } => {
             generate_interactive_agsi(&output)?;
         }
 
-        Commands::Table { input, word } => {
-            handle_table_command(&input, word.as_ref())?;
+        Commands::Table { input, word, model } => {
+            handle_table_command(&input, word.as_ref(), model.as_deref())?;
         }
 
-        Commands::Convert { input, output, convert_type } => {
-            let input_content = fs::read_to_string(&input)?;
-            let agsi_data: serde_json::Value = serde_json::from_str(&input_content)?;
+        Commands::Convert { input, output, convert_type, model } => {
+            let agsi = load_agsi(&input)?;
+            let ground_model = GroundModel::from_agsi_model(&agsi, model.as_deref())?;
             
             let result = match convert_type.into() {
                 ConvertType::SoilParams => {
                     // Extract parameter data from AGSi and create SoilParams
-                    let ground_model = GroundModel::from_agsi_file(&agsi_data);
                     let soil_params = if let Some(first_params) = ground_model.soil_params.first() {
                         first_params.clone()
                     } else {
@@ -214,7 +379,6 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     serde_json::to_string_pretty(&soil_params)?
                 }
                 ConvertType::GroundModel => {
-                    let ground_model = GroundModel::from_agsi_file(&agsi_data);
                     serde_json::to_string_pretty(&ground_model)?
                 }
             };
@@ -227,31 +391,20 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             add_predefined_materials(&input, &output, &material)?;
         }
         
-        Commands::Analyze { input } => {
-            let input_content = fs::read_to_string(&input)?;
-            let agsi_data: serde_json::Value = serde_json::from_str(&input_content)?;
-            
-            let ground_model = GroundModel::from_agsi_file(&agsi_data);
+        Commands::Analyze { input, model } => {
+            let agsi = load_agsi(&input)?;
+            let ground_model = GroundModel::from_agsi_model(&agsi, model.as_deref())?;
             
             println!("Ground Model Analysis:");
             println!("=====================");
             println!("Number of soil parameters: {}", ground_model.soil_params.len());
             
-            for (i, params) in ground_model.soil_params.iter().enumerate() {
-                println!("\nSoil Parameter Set {}:", i + 1);
-                println!("  Unit Weight: {:.2} kN/m³", params.unit_weight);
-                println!("  Young's Modulus: {:.0} kPa", params.youngs_modulus);
-                println!("  Behaviour: {:?}", params.behaviour);
-                
-                if let Some(phi) = params.phi_prime {
-                    println!("  Friction Angle: {:.1}°", phi);
-                }
-                if let Some(c) = params.c_prime {
-                    println!("  Cohesion: {:.1} kPa", c);
-                }
-                if let Some(cu) = params.cu {
-                    println!("  Undrained Shear Strength: {:.1} kPa", cu);
-                }
+            print_soil_param_sets(&ground_model.soil_params);
+
+            for (case_id, case_params) in &ground_model.cases {
+                println!("\nCase: {}", case_id);
+                println!("{}", "-".repeat(6 + case_id.chars().count()));
+                print_soil_param_sets(case_params);
             }
         }
         
@@ -263,6 +416,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     "title": "Example Ground Model"
                 },
                 "agsSchema": {
+                    "name": "AGSi",
                     "version": "1.0.1"
                 },
                 "agsiModel": [{
@@ -396,7 +550,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             params.cu = Some(sp.value_range.typical_value);
                         }
                         DescStrengthType::Ucs => {
-                            params.ucs = Some(sp.value_range.typical_value);
+                            params.ucs = sp.typical_stress().map(|ucs| ucs.kpa());
                             params.behaviour = SoilType::Rock;
                         }
                         _ => {}
@@ -421,31 +575,29 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
         Commands::StripLog {
             input,
+            model,
             svg,
             csv,
             ags_geol,
             hole_id,
             include_stresses,
-            dz,
             title,
             axis_unit,
         } => {
-            let input_content = fs::read_to_string(&input)?;
-            let ground_model: GroundModel = serde_json::from_str(&input_content)?;
+            let ground_model = load_ground_model(&input, model.as_deref())?;
 
             let rows = ground_model.to_strip_log(BuildStripLogOptions {
                 include_stresses,
-                stress_dz: dz,
-            });
+            })?;
 
             if let Some(csv_path) = csv {
-                let content = ground_model.to_strip_log_csv(Some(rows.clone()));
+                let content = ground_model.to_strip_log_csv(Some(rows.clone()))?;
                 fs::write(&csv_path, content)?;
                 println!("CSV written to: {}", csv_path.display());
             }
 
             if let Some(ags_path) = ags_geol {
-                let content = ground_model.to_ags_geol_csv(&hole_id, Some(rows.clone()));
+                let content = ground_model.to_ags_geol_csv(&hole_id, Some(rows.clone()))?;
                 fs::write(&ags_path, content)?;
                 println!("AGS GEOL CSV written to: {}", ags_path.display());
             }
@@ -455,16 +607,219 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     title,
                     axis_unit_label: axis_unit,
                     ..Default::default()
-                });
+                })?;
                 fs::write(&svg_path, svg_content)?;
                 println!("SVG written to: {}", svg_path.display());
             }
         }
+        Commands::EarthPressure {
+            input,
+            model,
+            wall_top,
+            wall_toe,
+            formation_level,
+            state,
+            backfill_slope,
+            wall_inclination,
+            friction_ratio,
+            undrained,
+            surcharge,
+            csv,
+            svg,
+            title,
+        } => {
+            let ground_model = load_ground_model(&input, model.as_deref())?;
+
+            let side = match formation_level {
+                Some(formation_level) => WallSide::Excavated { formation_level },
+                None => WallSide::Retained,
+            };
+            let geometry = WallGeometry::new(backfill_slope.to_radians(), wall_inclination.to_radians(), friction_ratio);
+            let condition = if undrained { DrainageCondition::Undrained } else { DrainageCondition::Drained };
+            let profile = ground_model.earth_pressure_profile(
+                wall_top,
+                wall_toe,
+                side,
+                state.into(),
+                &geometry,
+                condition,
+                surcharge,
+            )?;
+
+            for point in &profile.points {
+                println!(
+                    "  {:>8.2}  {:<20} K = {:.3}  σh′ = {:>7.1} kPa  u = {:>6.1} kPa  σh = {:>7.1} kPa{}",
+                    point.level,
+                    point.reference,
+                    point.coefficient,
+                    point.earth_pressure,
+                    point.water_pressure,
+                    point.total,
+                    if point.tension_crack { "  (tension crack)" } else { "" }
+                );
+            }
+            println!("Resultant: {:.1} kN/m", profile.resultant());
+
+            if let Some(csv_path) = csv {
+                fs::write(&csv_path, profile.to_csv())?;
+                println!("CSV written to: {}", csv_path.display());
+            }
+
+            if let Some(svg_path) = svg {
+                fs::write(&svg_path, profile.render_svg(title.as_deref()))?;
+                println!("SVG written to: {}", svg_path.display());
+            }
+        }
+        Commands::Construction {
+            input,
+            model,
+            sequence,
+            levels,
+            csv,
+        } => {
+            let ground_model = load_ground_model(&input, model.as_deref())?;
+
+            let history = ConstructionSequence::load(&sequence)?.run(&ground_model, &levels)?;
+            for stage in &history.stages {
+                println!("{} (ground surface {:.2}):", stage.name, stage.model.get_top_level());
+                for (level, stress) in history.levels.iter().zip(&stage.stresses) {
+                    let Some(stress) = stress else {
+                        println!("  {:>8.2}  above ground", level);
+                        continue;
+                    };
+                    println!(
+                        "  {:>8.2}  σv = {:>7.1} kPa  u = {:>6.1} kPa  σv′ = {:>7.1} kPa  σv′max = {:>7.1} kPa  {}",
+                        level,
+                        stress.stress.total,
+                        stress.stress.pore_pressure,
+                        stress.stress.effective,
+                        stress.max_past_effective,
+                        stress.path
+                    );
+                }
+            }
+
+            if let Some(csv_path) = csv {
+                fs::write(&csv_path, history.to_csv())?;
+                println!("CSV written to: {}", csv_path.display());
+            }
+        }
+        Commands::Settlement {
+            input,
+            model,
+            x,
+            y,
+            stress_change,
+            sublayers,
+            times,
+            csv,
+        } => {
+            let ground_model = load_ground_model(&input, model.as_deref())?;
+
+            let stress_change = match stress_change {
+                Some(path) => StressChange::Profile(read_stress_change_csv(&path)?),
+                None => StressChange::ModelLoads { x, y },
+            };
+            let result = ground_model.consolidation_settlement(&ConsolidationOptions {
+                stress_change,
+                sublayers,
+            })?;
+
+            println!("Primary consolidation settlement:");
+            for layer in &result.layers {
+                println!(
+                    "  {} ({:.2} to {:.2}): Δσ′ {:.1} kPa, settlement {:.1} mm, {:?} drainage",
+                    layer.reference,
+                    layer.top_level,
+                    layer.base_level,
+                    layer.mean_stress_change,
+                    layer.settlement * 1000.0,
+                    layer.drainage
+                );
+            }
+            println!("Total: {:.1} mm", result.total() * 1000.0);
+
+            let curve = result.time_settlement_curve(&times);
+            for [time, settlement] in &curve {
+                println!(
+                    "  t = {}: {:.1} mm (U = {:.1}%)",
+                    time,
+                    settlement * 1000.0,
+                    result.degree_at(*time) * 100.0
+                );
+            }
+
+            if let Some(csv_path) = csv {
+                let mut content = String::from("time,settlement_m,degree_of_consolidation\n");
+                for [time, settlement] in &curve {
+                    content.push_str(&format!("{},{},{}\n", time, settlement, result.degree_at(*time)));
+                }
+                fs::write(&csv_path, content)?;
+                println!("CSV written to: {}", csv_path.display());
+            }
+        }
     }
 
     Ok(())
 }
 
+/// Reads an AGSi file and builds the ground model for `model`, or reads GroundModel JSON
+/// when no model is given.
+fn load_ground_model(input: &PathBuf, model: Option<&str>) -> Result<GroundModel, Box<dyn std::error::Error>> {
+    match model {
+        Some(model) => Ok(GroundModel::from_agsi_model(&load_agsi(input)?, Some(model))?),
+        None => Ok(serde_json::from_str(&fs::read_to_string(input)?)?),
+    }
+}
+
+fn print_soil_param_sets(soil_params: &[SoilParams]) {
+    for (i, params) in soil_params.iter().enumerate() {
+        println!("\nSoil Parameter Set {}:", i + 1);
+        if !params.reference.is_empty() {
+            println!("  Reference: {}", params.reference);
+        }
+        println!("  Unit Weight: {:.2} kN/m³", params.unit_weight);
+        println!("  Young's Modulus: {:.0} kPa", params.youngs_modulus);
+        println!("  Behaviour: {:?}", params.behaviour);
+        
+        if let Some(phi) = params.phi_prime {
+            println!("  Friction Angle: {:.1}°", phi.to_degrees());
+        }
+        if let Some(c) = params.c_prime {
+            println!("  Cohesion: {:.1} kPa", c);
+        }
+        if let Some(cu) = params.cu {
+            println!("  Undrained Shear Strength: {:.1} kPa", cu);
+        }
+    }
+}
+
+fn read_stress_change_csv(path: &PathBuf) -> Result<Vec<[f64; 2]>, Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(path)?;
+    let mut points = Vec::new();
+    for (index, line) in content.lines().map(str::trim).enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        let mut values = line.split(',').map(|value| value.trim().parse::<f64>());
+        match (values.next(), values.next()) {
+            (Some(Ok(level)), Some(Ok(increment))) => points.push([level, increment]),
+            // Only the first line may be a header
+            _ if index == 0 => continue,
+            _ => {
+                return Err(format!(
+                    "Invalid stress change row {} in {}: {}",
+                    index + 1,
+                    path.display(),
+                    line
+                )
+                .into())
+            }
+        }
+    }
+    Ok(points)
+}
+
 fn collect_descriptions(
     text: Option<&str>,
     input: Option<&PathBuf>,
@@ -501,7 +856,7 @@ async fn start_lsp_server(port: u16) -> Result<(), Box<dyn std::error::Error>> {
     loop {
         let (stream, _) = listener.accept().await?;
         let (read, write) = tokio::io::split(stream);
-        let (service, socket) = LspService::new(|client| lsp::Backend::new(client));
+        let (service, socket) = LspService::new(lsp::Backend::new);
         let server = Server::new(read, write, socket);
         tokio::spawn(server.serve(service));
     }
@@ -577,12 +932,12 @@ fn generate_interactive_agsi(output: &PathBuf) -> Result<(), Box<dyn std::error:
     // Prepare AGSi structure
     let mut agsi_observation_columns = Vec::new();
     let mut agsi_model_elements = Vec::new();
+    let mut last_bottom_elevation: Option<f64> = None;
+    let mut hole_top_elevation: Option<f64> = None;
 
     // If layers selected, prompt for layers
     if create_layers {
         let mut add_layer = true;
-        let mut last_bottom_elevation: Option<f64> = None;
-        let mut hole_top_elevation: Option<f64> = None;
 
         while add_layer {
             println!("\n{}", blue.apply_to("--- Add Layer ---"));
@@ -634,7 +989,7 @@ fn generate_interactive_agsi(output: &PathBuf) -> Result<(), Box<dyn std::error:
         while add_element {
             println!("\n{}", blue.apply_to("--- Add Model Element ---"));
 
-            let model_id: String = Input::new()
+            let _model_id: String = Input::new()
                 .with_prompt("Model ID (optional)")
                 .default("".to_string())
                 .interact_text()?;
@@ -656,9 +1011,7 @@ fn generate_interactive_agsi(output: &PathBuf) -> Result<(), Box<dyn std::error:
                 .default(0)
                 .interact()?;
 
-            let mut agsi_data_parameter_value = Vec::new();
-
-            if material_type_idx == 0 {
+            let agsi_data_parameter_value = if material_type_idx == 0 {
                 // Soil parameters
                 let gamma: f64 = Input::new()
                     .with_prompt("Unit Weight (kN/m³)")
@@ -695,20 +1048,19 @@ fn generate_interactive_agsi(output: &PathBuf) -> Result<(), Box<dyn std::error:
                     .default(0.3)
                     .interact_text()?;
 
-                // Convert degrees to radians for storage
-                let phi_prime = phi_prime_deg.to_radians();
+                // AGSi values use the default code units: degrees, kPa and m²/kN
                 let youngs_modulus = youngs_modulus_mpa * 1000.0; // Convert MPa to kPa
                 let mv = mv_per_mpa / 1000.0; // Convert 1/MPa to 1/kPa
 
-                agsi_data_parameter_value = vec![
+                vec![
                     serde_json::json!({"codeID": "UnitWeight", "valueNumeric": gamma}),
-                    serde_json::json!({"codeID": "AngleFriction", "valueNumeric": phi_prime}),
+                    serde_json::json!({"codeID": "AngleFriction", "valueNumeric": phi_prime_deg}),
                     serde_json::json!({"codeID": "Cohesion", "valueNumeric": c_prime}),
                     serde_json::json!({"codeID": "UndrainedShearStrength", "valueNumeric": cu}),
                     serde_json::json!({"codeID": "YoungsModulus", "valueNumeric": youngs_modulus}),
                     serde_json::json!({"codeID": "ModulusOfVolumeCompressibility", "valueNumeric": mv}),
                     serde_json::json!({"codeID": "PoissonsRatio", "valueNumeric": poissons_ratio}),
-                ];
+                ]
             } else {
                 // Rock parameters
                 let unit_weight: f64 = Input::new()
@@ -721,7 +1073,7 @@ fn generate_interactive_agsi(output: &PathBuf) -> Result<(), Box<dyn std::error:
                     .default(50.0)
                     .interact_text()?;
 
-                let ucs: f64 = Input::new()
+                let ucs_mpa: f64 = Input::new()
                     .with_prompt("Unconfined Compressive Strength (UCS, MPa)")
                     .default(50.0)
                     .interact_text()?;
@@ -736,20 +1088,25 @@ fn generate_interactive_agsi(output: &PathBuf) -> Result<(), Box<dyn std::error:
                     .default(0.0)
                     .interact_text()?;
 
-                agsi_data_parameter_value = vec![
+                let ucs = Stress::from_mpa(ucs_mpa).kpa();
+
+                vec![
                     serde_json::json!({"codeID": "UnitWeight", "valueNumeric": unit_weight}),
                     serde_json::json!({"codeID": "GeologicalStrengthIndex", "valueNumeric": gsi}),
                     serde_json::json!({"codeID": "UnconfinedCompressiveStrength", "valueNumeric": ucs}),
                     serde_json::json!({"codeID": "HoekBrownParamMi", "valueNumeric": mi}),
                     serde_json::json!({"codeID": "Disturbance", "valueNumeric": d}),
-                ];
-            }
+                ]
+            };
 
             let mut element = serde_json::json!({
                 "agsiDataParameterValue": agsi_data_parameter_value
             });
 
             if !geol_code.is_empty() {
+                element["agsiDataPropertyValue"] = serde_json::json!([
+                    {"codeID": "GeologyCode", "valueText": geol_code}
+                ]);
                 element["elementID"] = serde_json::Value::String(geol_code);
             }
             if !element_name.is_empty() {
@@ -812,14 +1169,12 @@ fn generate_interactive_agsi(output: &PathBuf) -> Result<(), Box<dyn std::error:
     });
 
     // Add observation structure if layers were created
-    if !agsi_observation_columns.is_empty() {
+    if let (Some(hole_top), Some(hole_bottom)) = (hole_top_elevation, last_bottom_elevation) {
         agsi_data["agsiModel"][0]["agsiObservationSet"] = serde_json::json!([{
             "agsiObservationExpHole": [{
                 "holeID": "GeneratedHole1",
-                "topCoordinate": [],
-                "verticalHoleDepth": serde_json::Value::Null,
-                "profileCoordinates": [],
-                "holeType": "",
+                "topCoordinate": [0.0, 0.0, hole_top],
+                "verticalHoleDepth": hole_top - hole_bottom,
                 "agsiObservationColumn": agsi_observation_columns
             }]
         }]);
@@ -887,7 +1242,7 @@ fn get_predefined_material(material: &str) -> Result<Vec<serde_json::Value>, Box
 
     materials.get(material)
         .ok_or_else(|| format!("Unknown material: {}", material).into())
-        .map(|params| params.clone())
+        .cloned()
 }
 
 #[derive(Tabled)]
@@ -908,6 +1263,8 @@ struct SoilLayerRow {
 struct SoilParamsRow {
     #[tabled(rename = "Reference")]
     reference: String,
+    #[tabled(rename = "Case")]
+    case: String,
     #[tabled(rename = "γ (kN/m³)")]
     unit_weight: String,
     #[tabled(rename = "φ′ (°)")]
@@ -928,6 +1285,8 @@ struct SoilParamsRow {
 struct RockParamsRow {
     #[tabled(rename = "Reference")]
     reference: String,
+    #[tabled(rename = "Case")]
+    case: String,
     #[tabled(rename = "GSI")]
     gsi: String,
     #[tabled(rename = "UCS (kPa)")]
@@ -950,19 +1309,18 @@ struct GroundwaterRow {
     remarks: String,
 }
 
-fn handle_table_command(input: &PathBuf, word_output: Option<&PathBuf>) -> Result<(), Box<dyn std::error::Error>> {
-    let input_content = fs::read_to_string(input)?;
-    let agsi_data: serde_json::Value = serde_json::from_str(&input_content)?;
+fn handle_table_command(input: &PathBuf, word_output: Option<&PathBuf>, model: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let agsi = load_agsi(input)?;
+    let selected_model = select_model(&agsi, model)?;
 
-    // Extract elements from AGSi structure
-    let mut elements = Vec::new();
-    if let Some(agsi_model) = agsi_data["agsiModel"].as_array() {
-        for model in agsi_model {
-            if let Some(model_elements) = model["agsiModelElement"].as_array() {
-                elements.extend(model_elements.iter().cloned());
-            }
-        }
-    }
+    // Extract elements from AGSi structure, across all models unless one was chosen
+    let elements: Vec<&AgsiModelElement> = if model.is_some() {
+        selected_model.agsi_model_element.iter().collect()
+    } else {
+        agsi.agsi_model.iter()
+            .flat_map(|model| model.agsi_model_element.iter())
+            .collect()
+    };
 
     if elements.is_empty() {
         println!("Could not find any model elements in the AGSi file.");
@@ -970,14 +1328,12 @@ fn handle_table_command(input: &PathBuf, word_output: Option<&PathBuf>) -> Resul
     }
 
     // Convert elements to soil parameters
-    let mut soil_params_vec = Vec::new();
-    for element in &elements {
-        if let Some(params_array) = element["agsiDataParameterValue"].as_array() {
-            let ground_model = GroundModel::from_agsi_file(&agsi_data);
-            soil_params_vec.extend(ground_model.soil_params);
-            break; // Use the ground model conversion
-        }
-    }
+    // Base parameter sets first, followed by each design case
+    let ground_model = GroundModel::from_agsi_model(&agsi, model)?;
+    let mut soil_params_vec: Vec<SoilParams> = ground_model.soil_params.into_iter()
+        .filter(|sp| sp.case_id.is_none())
+        .collect();
+    soil_params_vec.extend(ground_model.cases.into_values().flatten());
 
     // Separate soils and rocks
     let soils: Vec<_> = soil_params_vec.iter().filter(|sp| sp.behaviour != SoilType::Rock).collect();
@@ -985,21 +1341,17 @@ fn handle_table_command(input: &PathBuf, word_output: Option<&PathBuf>) -> Resul
 
     // Extract soil layers from observation columns if available
     let mut layer_rows = Vec::new();
-    if let Some(agsi_model) = agsi_data["agsiModel"].as_array() {
-        if let Some(obs_set) = agsi_model.get(0).and_then(|m| m["agsiObservationSet"].as_array()) {
-            if let Some(exp_hole) = obs_set.get(0).and_then(|o| o["agsiObservationExpHole"].as_array()) {
-                if let Some(columns) = exp_hole.get(0).and_then(|e| e["agsiObservationColumn"].as_array()) {
-                    for column in columns {
-                        layer_rows.push(SoilLayerRow {
-                            top_elevation: column.get("topElevation").and_then(|v| v.as_f64()).map(|f| f.to_string()).unwrap_or_default(),
-                            bottom_elevation: column.get("bottomElevation").and_then(|v| v.as_f64()).map(|f| f.to_string()).unwrap_or_default(),
-                            top_depth: column.get("topDepth").and_then(|v| v.as_f64()).map(|f| f.to_string()).unwrap_or_default(),
-                            bottom_depth: column.get("bottomDepth").and_then(|v| v.as_f64()).map(|f| f.to_string()).unwrap_or_default(),
-                            geology_code: column.get("geologyCode").and_then(|v| v.as_str()).unwrap_or("").to_string(),
-                        });
-                    }
-                }
-            }
+    if let Some(exp_hole) = selected_model.agsi_observation_set.first()
+        .and_then(|o| o.agsi_observation_exp_hole.first())
+    {
+        for column in &exp_hole.agsi_observation_column {
+            layer_rows.push(SoilLayerRow {
+                top_elevation: column.top_elevation().map(|f| f.to_string()).unwrap_or_default(),
+                bottom_elevation: column.bottom_elevation().map(|f| f.to_string()).unwrap_or_default(),
+                top_depth: column.top_depth().map(|f| f.to_string()).unwrap_or_default(),
+                bottom_depth: column.bottom_depth().map(|f| f.to_string()).unwrap_or_default(),
+                geology_code: column.geology_code().unwrap_or("").to_string(),
+            });
         }
     }
 
@@ -1015,6 +1367,7 @@ fn handle_table_command(input: &PathBuf, word_output: Option<&PathBuf>) -> Resul
         let soil_rows: Vec<SoilParamsRow> = soils.iter().map(|sp| {
             SoilParamsRow {
                 reference: sp.reference.clone(),
+                case: sp.case_id.clone().unwrap_or_default(),
                 unit_weight: sp.unit_weight.to_string(),
                 friction_angle: sp.phi_prime.map(|phi| format!("{:.1}", phi.to_degrees())).unwrap_or_default(),
                 cohesion: sp.c_prime.map(|c| c.to_string()).unwrap_or_default(),
@@ -1035,6 +1388,7 @@ fn handle_table_command(input: &PathBuf, word_output: Option<&PathBuf>) -> Resul
         let rock_rows: Vec<RockParamsRow> = rocks.iter().map(|sp| {
             RockParamsRow {
                 reference: sp.reference.clone(),
+                case: sp.case_id.clone().unwrap_or_default(),
                 gsi: sp.gsi.map(|g| g.to_string()).unwrap_or_default(),
                 ucs: sp.ucs.map(|u| u.to_string()).unwrap_or_default(),
                 mi: sp.mi.map(|m| m.to_string()).unwrap_or_default(),
@@ -1048,33 +1402,23 @@ fn handle_table_command(input: &PathBuf, word_output: Option<&PathBuf>) -> Resul
     }
 
     // Extract groundwater elements
-    let groundwater_elements: Vec<_> = elements.iter()
+    let groundwater_elements: Vec<&AgsiModelElement> = elements.iter()
+        .copied()
         .filter(|el| {
-            el.get("elementType").and_then(|v| v.as_str()) == Some("Groundwater") &&
-            el.get("geometryObject").and_then(|v| v.as_str()) == Some("agsiGeometryPlane") &&
-            el.get("agsiGeometry").and_then(|g| g.get("elevation")).is_some()
+            el.element_type.as_deref() == Some("Groundwater") &&
+            el.geometry_object.as_deref() == Some("agsiGeometryPlane") &&
+            el.agsi_geometry.as_ref().and_then(|g| g.subtype_3.as_ref()).is_some()
         })
         .collect();
 
     if !groundwater_elements.is_empty() {
         let gw_rows: Vec<GroundwaterRow> = groundwater_elements.iter().map(|gw| {
+            let plane = gw.agsi_geometry.as_ref().and_then(|g| g.subtype_3.as_ref());
             GroundwaterRow {
-                reference: gw.get("elementID").and_then(|v| v.as_str()).unwrap_or("").to_string(),
-                elevation: gw.get("agsiGeometry")
-                    .and_then(|g| g.get("elevation"))
-                    .and_then(|v| v.as_f64())
-                    .map(|f| f.to_string())
-                    .unwrap_or_default(),
-                description: gw.get("agsiGeometry")
-                    .and_then(|g| g.get("description"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .to_string(),
-                remarks: gw.get("agsiGeometry")
-                    .and_then(|g| g.get("remarks"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .to_string(),
+                reference: gw.element_id.clone().unwrap_or_default(),
+                elevation: plane.map(|p| p.elevation.to_string()).unwrap_or_default(),
+                description: plane.and_then(|p| p.description.clone()).unwrap_or_default(),
+                remarks: plane.and_then(|p| p.remarks.clone()).unwrap_or_default(),
             }
         }).collect();
 
@@ -1096,7 +1440,7 @@ fn export_to_word(
     layer_rows: &[SoilLayerRow],
     soils: &[&SoilParams],
     rocks: &[&SoilParams],
-    groundwater_elements: &[&serde_json::Value],
+    groundwater_elements: &[&AgsiModelElement],
     output_path: &PathBuf,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let mut doc = Docx::new();
@@ -1136,6 +1480,7 @@ fn export_to_word(
         let mut table_rows = vec![
             TableRow::new(vec![
                 TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text("Reference"))),
+                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text("Case"))),
                 TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text("γ (kN/m³)"))),
                 TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text("φ′ (°)"))),
                 TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text("c′ (kPa)"))),
@@ -1149,12 +1494,13 @@ fn export_to_word(
         for sp in soils {
             table_rows.push(TableRow::new(vec![
                 TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(&sp.reference))),
-                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(&sp.unit_weight.to_string()))),
-                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(&sp.phi_prime.map(|phi| format!("{:.1}", phi.to_degrees())).unwrap_or_default()))),
-                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(&sp.c_prime.map(|c| c.to_string()).unwrap_or_default()))),
-                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(&sp.cu.map(|cu| cu.to_string()).unwrap_or_default()))),
+                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(sp.case_id.as_deref().unwrap_or("")))),
+                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(sp.unit_weight.to_string()))),
+                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(sp.phi_prime.map(|phi| format!("{:.1}", phi.to_degrees())).unwrap_or_default()))),
+                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(sp.c_prime.map(|c| c.to_string()).unwrap_or_default()))),
+                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(sp.cu.map(|cu| cu.to_string()).unwrap_or_default()))),
                 TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(&if sp.mv > 0.0 { sp.mv.to_string() } else { String::new() }))),
-                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(&sp.youngs_modulus.to_string()))),
+                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(sp.youngs_modulus.to_string()))),
                 TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(&if sp.poissons_ratio > 0.0 { sp.poissons_ratio.to_string() } else { String::new() }))),
             ]));
         }
@@ -1170,6 +1516,7 @@ fn export_to_word(
         let mut table_rows = vec![
             TableRow::new(vec![
                 TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text("Reference"))),
+                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text("Case"))),
                 TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text("GSI"))),
                 TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text("UCS (kPa)"))),
                 TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text("mi (Hoek-Brown)"))),
@@ -1180,10 +1527,11 @@ fn export_to_word(
         for sp in rocks {
             table_rows.push(TableRow::new(vec![
                 TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(&sp.reference))),
-                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(&sp.gsi.map(|g| g.to_string()).unwrap_or_default()))),
-                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(&sp.ucs.map(|u| u.to_string()).unwrap_or_default()))),
-                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(&sp.mi.map(|m| m.to_string()).unwrap_or_default()))),
-                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(&sp.disturbance.to_string()))),
+                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(sp.case_id.as_deref().unwrap_or("")))),
+                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(sp.gsi.map(|g| g.to_string()).unwrap_or_default()))),
+                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(sp.ucs.map(|u| u.to_string()).unwrap_or_default()))),
+                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(sp.mi.map(|m| m.to_string()).unwrap_or_default()))),
+                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(sp.disturbance.to_string()))),
             ]));
         }
 
@@ -1205,11 +1553,12 @@ fn export_to_word(
         ];
 
         for gw in groundwater_elements {
+            let plane = gw.agsi_geometry.as_ref().and_then(|g| g.subtype_3.as_ref());
             table_rows.push(TableRow::new(vec![
-                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(gw.get("elementID").and_then(|v| v.as_str()).unwrap_or("")))),
-                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(&gw.get("agsiGeometry").and_then(|g| g.get("elevation")).and_then(|v| v.as_f64()).map(|f| f.to_string()).unwrap_or_default()))),
-                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(gw.get("agsiGeometry").and_then(|g| g.get("description")).and_then(|v| v.as_str()).unwrap_or("")))),
-                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(gw.get("agsiGeometry").and_then(|g| g.get("remarks")).and_then(|v| v.as_str()).unwrap_or("")))),
+                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(gw.element_id.as_deref().unwrap_or("")))),
+                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(plane.map(|p| p.elevation.to_string()).unwrap_or_default()))),
+                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(plane.and_then(|p| p.description.as_deref()).unwrap_or("")))),
+                TableCell::new().add_paragraph(Paragraph::new().add_run(Run::new().add_text(plane.and_then(|p| p.remarks.as_deref()).unwrap_or("")))),
             ]));
         }
 
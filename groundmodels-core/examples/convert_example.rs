@@ -5,6 +5,14 @@ fn main() {
     // Example AGSi JSON data
     let agsi_json = r#"
     {
+        "agsFile": {
+            "producedBy": "groundmodels",
+            "title": "Example ground model"
+        },
+        "agsSchema": {
+            "name": "AGSi",
+            "version": "1.0.1"
+        },
         "agsiModel": [
             {
                 "agsiModelElement": [
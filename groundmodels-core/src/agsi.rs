@@ -2,6 +2,8 @@
 #![allow(clippy::needless_lifetimes)]
 #![allow(clippy::match_single_binding)]
 #![allow(clippy::clone_on_copy)]
+#![allow(clippy::large_enum_variant)]
+#![allow(clippy::derivable_impls)]
 
 #[doc = r" Error types."]
 pub mod error {
@@ -3800,6 +3802,25 @@ pub enum AgsiGeometryLayer {
         #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
         remarks: ::std::option::Option<::std::string::String>,
     },
+    Variant2 {
+        #[serde(rename = "bottomElevation")]
+        bottom_elevation: f64,
+        #[doc = "Short description of geometry defined here."]
+        #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
+        description: ::std::option::Option<::std::string::String>,
+        #[doc = "Identifier for this geometry object. May be local to this file but all identifiers used within the Geometry group of objects shall be unique. Alternatively a UUID may be used (recommended for large datasets). Use of this attribute is optional and it is not referenced anywhere else in the schema, but it may be beneficial to include it to help with data control and integrity, and some applications may require or benefit from it."]
+        #[serde(
+            rename = "geometryID",
+            default,
+            skip_serializing_if = "::std::option::Option::is_none"
+        )]
+        geometry_id: ::std::option::Option<::std::string::String>,
+        #[doc = "Additional remarks, if required."]
+        #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
+        remarks: ::std::option::Option<::std::string::String>,
+        #[serde(rename = "topElevation")]
+        top_elevation: f64,
+    },
 }
 impl ::std::convert::From<&Self> for AgsiGeometryLayer {
     fn from(value: &AgsiGeometryLayer) -> Self {
@@ -3975,6 +3996,27 @@ pub enum AgsiGeometryVolFromSurfaces {
         #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
         remarks: ::std::option::Option<::std::string::String>,
     },
+    Variant2 {
+        #[doc = "Geometry for bottom surface, as embedded agsiGeometryFromFile or agsiGeometryPlane object. Definition of both top and bottom surfaces is recommended to minimise the risk of error. Refer to 6.2.3. Volumes from surfaces for further details."]
+        #[serde(rename = "agsiGeometryBottom")]
+        agsi_geometry_bottom: AgsiGeometryVolFromSurfacesVariant1AgsiGeometryBottom,
+        #[doc = "Short description of geometry defined here."]
+        #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
+        description: ::std::option::Option<::std::string::String>,
+        #[doc = "Identifier for this geometry object. May be local to this file but all identifiers used within the Geometry group of objects shall be unique. Alternatively a UUID may be used (recommended for large datasets). Use of this attribute is optional and it is not referenced anywhere else in the schema, but it may be beneficial to include it to help with data control and integrity, and some applications may require or benefit from it."]
+        #[serde(
+            rename = "geometryID",
+            default,
+            skip_serializing_if = "::std::option::Option::is_none"
+        )]
+        geometry_id: ::std::option::Option<::std::string::String>,
+        #[doc = "Additional remarks, if required."]
+        #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
+        remarks: ::std::option::Option<::std::string::String>,
+        #[doc = "Geometry for top surface, as embedded agsiGeometryFromFile or agsiGeometryPlane object. Definition of both top and bottom surfaces is recommended to minimise the risk of error. Refer to 6.2.3. Volumes from surfaces for further details."]
+        #[serde(rename = "agsiGeometryTop")]
+        agsi_geometry_top: AgsiGeometryVolFromSurfacesVariant0AgsiGeometryTop,
+    },
 }
 impl ::std::convert::From<&Self> for AgsiGeometryVolFromSurfaces {
     fn from(value: &AgsiGeometryVolFromSurfaces) -> Self {
@@ -5127,6 +5169,79 @@ pub enum AgsiObservationColumn {
         #[serde(rename = "topElevation")]
         top_elevation: f64,
     },
+    Variant2 {
+        #[doc = "Array of embedded agsiDataPropertyValue objects. May be used to provide other data for this range of depth/elevation."]
+        #[serde(
+            rename = "agsiDataPropertyValue",
+            default,
+            skip_serializing_if = "::std::vec::Vec::is_empty"
+        )]
+        agsi_data_property_value: ::std::vec::Vec<AgsiDataPropertyValue>,
+        #[serde(
+            rename = "bottomDepth",
+            default,
+            skip_serializing_if = "::std::option::Option::is_none"
+        )]
+        bottom_depth: ::std::option::Option<f64>,
+        #[serde(
+            rename = "bottomElevation",
+            default,
+            skip_serializing_if = "::std::option::Option::is_none"
+        )]
+        bottom_elevation: ::std::option::Option<f64>,
+        #[doc = "Identifier for this particular column observation. May be local to this file or a UUID as required/specified. This is optional and not referenced anywhere else in the schema, but it may be beneficial to include this to help with data control and integrity, and some software/applications may require it."]
+        #[serde(
+            rename = "columnID",
+            default,
+            skip_serializing_if = "::std::option::Option::is_none"
+        )]
+        column_id: ::std::option::Option<::std::string::String>,
+        #[doc = "Geological description."]
+        #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
+        description: ::std::option::Option<::std::string::String>,
+        #[doc = "BGS Lexicon code (applicable in UK only)."]
+        #[serde(
+            rename = "geologyBGS",
+            default,
+            skip_serializing_if = "::std::option::Option::is_none"
+        )]
+        geology_bgs: ::std::option::Option<::std::string::String>,
+        #[doc = "Geology code. Typically a project specific code defined defined using agsProjectCode."]
+        #[serde(
+            rename = "geologyCode",
+            default,
+            skip_serializing_if = "::std::option::Option::is_none"
+        )]
+        geology_code: ::std::option::Option<::std::string::String>,
+        #[doc = "2nd geology code, if applicable. Typically a project specific code defined using agsProjectCode."]
+        #[serde(
+            rename = "geologyCode2",
+            default,
+            skip_serializing_if = "::std::option::Option::is_none"
+        )]
+        geology_code2: ::std::option::Option<::std::string::String>,
+        #[doc = "Geological formation or stratum name."]
+        #[serde(
+            rename = "geologyFormation",
+            default,
+            skip_serializing_if = "::std::option::Option::is_none"
+        )]
+        geology_formation: ::std::option::Option<::std::string::String>,
+        #[doc = "Legend code. Recommend using code from AGS format ABBR code list."]
+        #[serde(
+            rename = "legendCode",
+            default,
+            skip_serializing_if = "::std::option::Option::is_none"
+        )]
+        legend_code: ::std::option::Option<::std::string::String>,
+        #[doc = "Additional remarks, if required."]
+        #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
+        remarks: ::std::option::Option<::std::string::String>,
+        #[serde(rename = "topDepth")]
+        top_depth: f64,
+        #[serde(rename = "topElevation")]
+        top_elevation: f64,
+    },
 }
 impl ::std::convert::From<&Self> for AgsiObservationColumn {
     fn from(value: &AgsiObservationColumn) -> Self {
@@ -5247,293 +5362,71 @@ impl ::std::convert::From<&Self> for AgsiObservationColumn {
 #[doc = r" ```"]
 #[doc = r" </details>"]
 #[derive(:: serde :: Deserialize, :: serde :: Serialize, Clone, Debug)]
-#[serde(untagged)]
-pub enum AgsiObservationExpHole {
-    Variant0(AgsiObservationExpHoleVariant0),
-    Variant1(AgsiObservationExpHoleVariant1),
-}
-impl ::std::convert::From<&Self> for AgsiObservationExpHole {
-    fn from(value: &AgsiObservationExpHole) -> Self {
-        value.clone()
-    }
-}
-impl ::std::convert::From<AgsiObservationExpHoleVariant0> for AgsiObservationExpHole {
-    fn from(value: AgsiObservationExpHoleVariant0) -> Self {
-        Self::Variant0(value)
-    }
-}
-impl ::std::convert::From<AgsiObservationExpHoleVariant1> for AgsiObservationExpHole {
-    fn from(value: AgsiObservationExpHoleVariant1) -> Self {
-        Self::Variant1(value)
-    }
-}
-#[doc = "`AgsiObservationExpHoleVariant0`"]
-#[doc = r""]
-#[doc = r" <details><summary>JSON schema</summary>"]
-#[doc = r""]
-#[doc = r" ```json"]
-#[doc = "{"]
-#[doc = "  \"allOf\": ["]
-#[doc = "    {"]
-#[doc = "      \"type\": \"object\","]
-#[doc = "      \"properties\": {"]
-#[doc = "        \"agsiDataPropertyFromFile\": {"]
-#[doc = "          \"description\": \"An embedded agsiDataPropertyFromFile object, which may be used to reference an external supporting data file.\","]
-#[doc = "          \"type\": \"object\","]
-#[doc = "          \"$ref\": \"#/$defs/agsiDataPropertyFromFile\""]
-#[doc = "        },"]
-#[doc = "        \"agsiDataPropertyValue\": {"]
-#[doc = "          \"description\": \"Array of embedded agsiDataPropertyValue objects. May be used for additional hole metadata or for profiles of test results for this hole, e.g. SPT vs depth/elevation.\","]
-#[doc = "          \"type\": \"array\","]
-#[doc = "          \"items\": {"]
-#[doc = "            \"$ref\": \"#/$defs/agsiDataPropertyValue\""]
-#[doc = "          }"]
-#[doc = "        },"]
-#[doc = "        \"agsiObservationColumn\": {"]
-#[doc = "          \"description\": \"Array of embedded agsiObservationColumn objects which are typically used to represent geology within the hole, but can also be used for other data. \","]
-#[doc = "          \"type\": \"array\","]
-#[doc = "          \"items\": {"]
-#[doc = "            \"$ref\": \"#/$defs/agsiObservationColumn\""]
-#[doc = "          }"]
-#[doc = "        },"]
-#[doc = "        \"date\": {"]
-#[doc = "          \"description\": \"Date of exploration. Recommend using start date for holes that take more than one day.\","]
-#[doc = "          \"type\": \"string\","]
-#[doc = "          \"format\": \"date\","]
-#[doc = "          \"example\": \"2018-05-23\""]
-#[doc = "        },"]
-#[doc = "        \"holeID\": {"]
-#[doc = "          \"description\": \"Identifier that is unique across the project for exploratory holes. Not necessarily the same as the original hole ID (see holeName). If used, identifiers for holeID should be unique within the AGSi file. \","]
-#[doc = "          \"type\": \"string\","]
-#[doc = "          \"example\": \"A/BH01\""]
-#[doc = "        },"]
-#[doc = "        \"holeName\": {"]
-#[doc = "          \"description\": \"Current name or ID of the exploratory hole for general use.\","]
-#[doc = "          \"type\": \"string\","]
-#[doc = "          \"example\": \"BH01\""]
-#[doc = "        },"]
-#[doc = "        \"holeType\": {"]
-#[doc = "          \"description\": \"Type of exploratory hole. Recommend using code from AGS format ABBR code list, e.g. CP+RC, with project specific codes defined using agsProjectCode. Alternatively, short description may be provided, e.g. cable percussion borehole with rotary follow on.\","]
-#[doc = "          \"type\": \"string\","]
-#[doc = "          \"example\": \"CP+RC\""]
-#[doc = "        },"]
-#[doc = "        \"holeUUID\": {"]
-#[doc = "          \"description\": \"Universal/global unique identifier (UUID) for the hole. This is optional and is not referenced anywhere else in the schema, but it may be beneficial to include this to help with data control and integrity. Other attributes should be used for IDs specific to the producer and/or client (see below).\","]
-#[doc = "          \"type\": \"string\","]
-#[doc = "          \"example\": \"523ad9ed-4f75-4a55-b251-c566a8b998bd\""]
-#[doc = "        },"]
-#[doc = "        \"profileCoordinates\": {"]
-#[doc = "          \"description\": \"Coordinates of the line of the exploratory hole (3D, including elevation), i.e. top, bottom and intermediate changes in direction if required. Input as ordered list of coordinate tuples starting at the top. Used for holes that are not vertical, or not straight. May be used for straight vertical holes as alternative to topCoordinate and verticalHoleDepth.\","]
-#[doc = "          \"type\": \"array\","]
-#[doc = "          \"items\": {"]
-#[doc = "            \"$ref\": \"#/$defs/coordinateTuple\""]
-#[doc = "          },"]
-#[doc = "          \"example\": ["]
-#[doc = "            ["]
-#[doc = "              1275.5,"]
-#[doc = "              2195.0,"]
-#[doc = "              15.25"]
-#[doc = "            ],"]
-#[doc = "            ["]
-#[doc = "              1275.5,"]
-#[doc = "              2195.0,"]
-#[doc = "              -9.75"]
-#[doc = "            ]"]
-#[doc = "          ]"]
-#[doc = "        },"]
-#[doc = "        \"remarks\": {"]
-#[doc = "          \"description\": \"Additional remarks, if required.\","]
-#[doc = "          \"type\": \"string\","]
-#[doc = "          \"example\": \"Original name on logs: BH1\""]
-#[doc = "        },"]
-#[doc = "        \"topCoordinate\": {"]
-#[doc = "          \"description\": \"Coordinates of the top of the exploratory hole (3D, including elevation) as a coordinate tuple.\","]
-#[doc = "          \"type\": \"array\","]
-#[doc = "          \"$ref\": \"#/$defs/coordinateTuple\","]
-#[doc = "          \"example\": ["]
-#[doc = "            1275.5,"]
-#[doc = "            2195.0,"]
-#[doc = "            15.25"]
-#[doc = "          ]"]
-#[doc = "        },"]
-#[doc = "        \"verticalHoleDepth\": {"]
-#[doc = "          \"description\": \"Final depth of exploratory hole for vertical holes only. For non-vertical or non-straight holes use profileCoordinates instead.\","]
-#[doc = "          \"type\": \"number\","]
-#[doc = "          \"example\": 25"]
-#[doc = "        }"]
-#[doc = "      },"]
-#[doc = "      \"additionalProperties\": false"]
-#[doc = "    },"]
-#[doc = "    {"]
-#[doc = "      \"required\": ["]
-#[doc = "        \"holeID\","]
-#[doc = "        \"topCoordinate\","]
-#[doc = "        \"verticalHoleDepth\""]
-#[doc = "      ]"]
-#[doc = "    },"]
-#[doc = "    {"]
-#[doc = "      \"not\": {"]
-#[doc = "        \"required\": ["]
-#[doc = "          \"holeID\","]
-#[doc = "          \"profileCoordinates\""]
-#[doc = "        ]"]
-#[doc = "      }"]
-#[doc = "    }"]
-#[doc = "  ]"]
-#[doc = "}"]
-#[doc = r" ```"]
-#[doc = r" </details>"]
-#[derive(
-    :: serde :: Deserialize,
-    :: serde :: Serialize,
-    Clone,
-    Copy,
-    Debug,
-    Eq,
-    Hash,
-    Ord,
-    PartialEq,
-    PartialOrd,
-)]
 #[serde(deny_unknown_fields)]
-pub enum AgsiObservationExpHoleVariant0 {}
-impl ::std::convert::From<&Self> for AgsiObservationExpHoleVariant0 {
-    fn from(value: &AgsiObservationExpHoleVariant0) -> Self {
-        value.clone()
-    }
+pub struct AgsiObservationExpHole {
+    #[serde(
+        rename = "agsiDataPropertyFromFile",
+        default,
+        skip_serializing_if = "::std::option::Option::is_none"
+    )]
+    pub agsi_data_property_from_file: ::std::option::Option<AgsiDataPropertyFromFile>,
+    #[serde(
+        rename = "agsiDataPropertyValue",
+        default,
+        skip_serializing_if = "::std::vec::Vec::is_empty"
+    )]
+    pub agsi_data_property_value: ::std::vec::Vec<AgsiDataPropertyValue>,
+    #[serde(
+        rename = "agsiObservationColumn",
+        default,
+        skip_serializing_if = "::std::vec::Vec::is_empty"
+    )]
+    pub agsi_observation_column: ::std::vec::Vec<AgsiObservationColumn>,
+    #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
+    pub date: ::std::option::Option<::chrono::naive::NaiveDate>,
+    #[serde(rename = "holeID")]
+    pub hole_id: ::std::string::String,
+    #[serde(
+        rename = "holeName",
+        default,
+        skip_serializing_if = "::std::option::Option::is_none"
+    )]
+    pub hole_name: ::std::option::Option<::std::string::String>,
+    #[serde(
+        rename = "holeType",
+        default,
+        skip_serializing_if = "::std::option::Option::is_none"
+    )]
+    pub hole_type: ::std::option::Option<::std::string::String>,
+    #[serde(
+        rename = "holeUUID",
+        default,
+        skip_serializing_if = "::std::option::Option::is_none"
+    )]
+    pub hole_uuid: ::std::option::Option<::std::string::String>,
+    #[serde(
+        rename = "profileCoordinates",
+        default,
+        skip_serializing_if = "::std::vec::Vec::is_empty"
+    )]
+    pub profile_coordinates: ::std::vec::Vec<CoordinateTuple>,
+    #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
+    pub remarks: ::std::option::Option<::std::string::String>,
+    #[serde(
+        rename = "topCoordinate",
+        default,
+        skip_serializing_if = "::std::option::Option::is_none"
+    )]
+    pub top_coordinate: ::std::option::Option<CoordinateTuple>,
+    #[serde(
+        rename = "verticalHoleDepth",
+        default,
+        skip_serializing_if = "::std::option::Option::is_none"
+    )]
+    pub vertical_hole_depth: ::std::option::Option<f64>,
 }
-#[doc = "`AgsiObservationExpHoleVariant1`"]
-#[doc = r""]
-#[doc = r" <details><summary>JSON schema</summary>"]
-#[doc = r""]
-#[doc = r" ```json"]
-#[doc = "{"]
-#[doc = "  \"allOf\": ["]
-#[doc = "    {"]
-#[doc = "      \"type\": \"object\","]
-#[doc = "      \"properties\": {"]
-#[doc = "        \"agsiDataPropertyFromFile\": {"]
-#[doc = "          \"description\": \"An embedded agsiDataPropertyFromFile object, which may be used to reference an external supporting data file.\","]
-#[doc = "          \"type\": \"object\","]
-#[doc = "          \"$ref\": \"#/$defs/agsiDataPropertyFromFile\""]
-#[doc = "        },"]
-#[doc = "        \"agsiDataPropertyValue\": {"]
-#[doc = "          \"description\": \"Array of embedded agsiDataPropertyValue objects. May be used for additional hole metadata or for profiles of test results for this hole, e.g. SPT vs depth/elevation.\","]
-#[doc = "          \"type\": \"array\","]
-#[doc = "          \"items\": {"]
-#[doc = "            \"$ref\": \"#/$defs/agsiDataPropertyValue\""]
-#[doc = "          }"]
-#[doc = "        },"]
-#[doc = "        \"agsiObservationColumn\": {"]
-#[doc = "          \"description\": \"Array of embedded agsiObservationColumn objects which are typically used to represent geology within the hole, but can also be used for other data. \","]
-#[doc = "          \"type\": \"array\","]
-#[doc = "          \"items\": {"]
-#[doc = "            \"$ref\": \"#/$defs/agsiObservationColumn\""]
-#[doc = "          }"]
-#[doc = "        },"]
-#[doc = "        \"date\": {"]
-#[doc = "          \"description\": \"Date of exploration. Recommend using start date for holes that take more than one day.\","]
-#[doc = "          \"type\": \"string\","]
-#[doc = "          \"format\": \"date\","]
-#[doc = "          \"example\": \"2018-05-23\""]
-#[doc = "        },"]
-#[doc = "        \"holeID\": {"]
-#[doc = "          \"description\": \"Identifier that is unique across the project for exploratory holes. Not necessarily the same as the original hole ID (see holeName). If used, identifiers for holeID should be unique within the AGSi file. \","]
-#[doc = "          \"type\": \"string\","]
-#[doc = "          \"example\": \"A/BH01\""]
-#[doc = "        },"]
-#[doc = "        \"holeName\": {"]
-#[doc = "          \"description\": \"Current name or ID of the exploratory hole for general use.\","]
-#[doc = "          \"type\": \"string\","]
-#[doc = "          \"example\": \"BH01\""]
-#[doc = "        },"]
-#[doc = "        \"holeType\": {"]
-#[doc = "          \"description\": \"Type of exploratory hole. Recommend using code from AGS format ABBR code list, e.g. CP+RC, with project specific codes defined using agsProjectCode. Alternatively, short description may be provided, e.g. cable percussion borehole with rotary follow on.\","]
-#[doc = "          \"type\": \"string\","]
-#[doc = "          \"example\": \"CP+RC\""]
-#[doc = "        },"]
-#[doc = "        \"holeUUID\": {"]
-#[doc = "          \"description\": \"Universal/global unique identifier (UUID) for the hole. This is optional and is not referenced anywhere else in the schema, but it may be beneficial to include this to help with data control and integrity. Other attributes should be used for IDs specific to the producer and/or client (see below).\","]
-#[doc = "          \"type\": \"string\","]
-#[doc = "          \"example\": \"523ad9ed-4f75-4a55-b251-c566a8b998bd\""]
-#[doc = "        },"]
-#[doc = "        \"profileCoordinates\": {"]
-#[doc = "          \"description\": \"Coordinates of the line of the exploratory hole (3D, including elevation), i.e. top, bottom and intermediate changes in direction if required. Input as ordered list of coordinate tuples starting at the top. Used for holes that are not vertical, or not straight. May be used for straight vertical holes as alternative to topCoordinate and verticalHoleDepth.\","]
-#[doc = "          \"type\": \"array\","]
-#[doc = "          \"items\": {"]
-#[doc = "            \"$ref\": \"#/$defs/coordinateTuple\""]
-#[doc = "          },"]
-#[doc = "          \"example\": ["]
-#[doc = "            ["]
-#[doc = "              1275.5,"]
-#[doc = "              2195.0,"]
-#[doc = "              15.25"]
-#[doc = "            ],"]
-#[doc = "            ["]
-#[doc = "              1275.5,"]
-#[doc = "              2195.0,"]
-#[doc = "              -9.75"]
-#[doc = "            ]"]
-#[doc = "          ]"]
-#[doc = "        },"]
-#[doc = "        \"remarks\": {"]
-#[doc = "          \"description\": \"Additional remarks, if required.\","]
-#[doc = "          \"type\": \"string\","]
-#[doc = "          \"example\": \"Original name on logs: BH1\""]
-#[doc = "        },"]
-#[doc = "        \"topCoordinate\": {"]
-#[doc = "          \"description\": \"Coordinates of the top of the exploratory hole (3D, including elevation) as a coordinate tuple.\","]
-#[doc = "          \"type\": \"array\","]
-#[doc = "          \"$ref\": \"#/$defs/coordinateTuple\","]
-#[doc = "          \"example\": ["]
-#[doc = "            1275.5,"]
-#[doc = "            2195.0,"]
-#[doc = "            15.25"]
-#[doc = "          ]"]
-#[doc = "        },"]
-#[doc = "        \"verticalHoleDepth\": {"]
-#[doc = "          \"description\": \"Final depth of exploratory hole for vertical holes only. For non-vertical or non-straight holes use profileCoordinates instead.\","]
-#[doc = "          \"type\": \"number\","]
-#[doc = "          \"example\": 25"]
-#[doc = "        }"]
-#[doc = "      },"]
-#[doc = "      \"additionalProperties\": false"]
-#[doc = "    },"]
-#[doc = "    {"]
-#[doc = "      \"required\": ["]
-#[doc = "        \"holeID\","]
-#[doc = "        \"profileCoordinates\""]
-#[doc = "      ]"]
-#[doc = "    },"]
-#[doc = "    {"]
-#[doc = "      \"not\": {"]
-#[doc = "        \"required\": ["]
-#[doc = "          \"holeID\","]
-#[doc = "          \"topCoordinate\","]
-#[doc = "          \"verticalHoleDepth\""]
-#[doc = "        ]"]
-#[doc = "      }"]
-#[doc = "    }"]
-#[doc = "  ]"]
-#[doc = "}"]
-#[doc = r" ```"]
-#[doc = r" </details>"]
-#[derive(
-    :: serde :: Deserialize,
-    :: serde :: Serialize,
-    Clone,
-    Copy,
-    Debug,
-    Eq,
-    Hash,
-    Ord,
-    PartialEq,
-    PartialOrd,
-)]
-#[serde(deny_unknown_fields)]
-pub enum AgsiObservationExpHoleVariant1 {}
-impl ::std::convert::From<&Self> for AgsiObservationExpHoleVariant1 {
-    fn from(value: &AgsiObservationExpHoleVariant1) -> Self {
+impl ::std::convert::From<&AgsiObservationExpHole> for AgsiObservationExpHole {
+    fn from(value: &AgsiObservationExpHole) -> Self {
         value.clone()
     }
 }
@@ -0,0 +1,1796 @@
+use crate::agsi::{
+    AgSiV101, AgsFile, AgsProject, AgsProjectCode, AgsProjectCodeSet, AgsSchema,
+    AgsiDataParameterValue, AgsiDataPropertyValue, AgsiGeometryLayer, AgsiGeometryPlane,
+    AgsiGeometryVolFromSurfaces, AgsiGeometryVolFromSurfacesVariant0AgsiGeometryTop,
+    AgsiGeometryVolFromSurfacesVariant1AgsiGeometryBottom, AgsiModel, AgsiModelElement,
+    AgsiModelElementAgsiGeometry, AgsiObservationColumn,
+};
+use crate::groundwater::{
+    PiezometricHead, PiezometricZone, PorePressureRegime, DEFAULT_UNIT_WEIGHT_WATER,
+};
+use crate::units::{Quantity, UnitTable};
+use crate::{GroundModel, ProfileVariable, SoilLayer, SoilParams};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Errors raised while reading an AGSi file into the typed schema.
+#[derive(Debug)]
+pub enum AgsiError {
+    /// The file could not be read.
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// The document is not valid JSON or does not match the AGSi v1.0.1 schema.
+    /// `path` is the JSON path of the offending value, e.g. `agsiModel[0].agsiModelElement[2]`.
+    Parse { path: String, message: String },
+    /// The document contains no `agsiModel` objects.
+    MissingModel,
+    /// No `agsiModel` has the requested `modelID` or `modelName`.
+    UnknownModel {
+        model: String,
+        available: Vec<String>,
+    },
+}
+
+impl fmt::Display for AgsiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AgsiError::Io { path, source } => {
+                write!(f, "Failed to read AGSi file {}: {}", path.display(), source)
+            }
+            AgsiError::Parse { path, message } if path.is_empty() || path == "." => {
+                write!(f, "Invalid AGSi: {}", message)
+            }
+            AgsiError::Parse { path, message } => {
+                write!(f, "Invalid AGSi at {}: {}", path, message)
+            }
+            AgsiError::MissingModel => write!(f, "AGSi file does not contain an agsiModel"),
+            AgsiError::UnknownModel { model, available } if available.is_empty() => {
+                write!(f, "No agsiModel matches '{}'", model)
+            }
+            AgsiError::UnknownModel { model, available } => write!(
+                f,
+                "No agsiModel matches '{}' (available: {})",
+                model,
+                available.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AgsiError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AgsiError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_path_to_error::Error<serde_json::Error>> for AgsiError {
+    fn from(err: serde_path_to_error::Error<serde_json::Error>) -> Self {
+        AgsiError::Parse {
+            path: err.path().to_string(),
+            message: err.into_inner().to_string(),
+        }
+    }
+}
+
+/// Reads and deserializes an AGSi v1.0.1 JSON file.
+pub fn load_agsi<P: AsRef<Path>>(path: P) -> Result<AgSiV101, AgsiError> {
+    let path = path.as_ref();
+    let text = fs::read_to_string(path).map_err(|source| AgsiError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    parse_agsi(&text)
+}
+
+/// Deserializes an AGSi v1.0.1 document from a JSON string.
+pub fn parse_agsi(text: &str) -> Result<AgSiV101, AgsiError> {
+    let mut deserializer = serde_json::Deserializer::from_str(text);
+    let agsi = serde_path_to_error::deserialize(&mut deserializer)?;
+    deserializer.end().map_err(|e| AgsiError::Parse {
+        path: String::new(),
+        message: e.to_string(),
+    })?;
+    Ok(agsi)
+}
+
+/// Deserializes an AGSi v1.0.1 document that has already been parsed to a JSON value.
+pub fn agsi_from_value(value: &serde_json::Value) -> Result<AgSiV101, AgsiError> {
+    Ok(serde_path_to_error::deserialize(value)?)
+}
+
+/// Identifying fields of one `agsiModel` entry in a file.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AgsiModelSummary {
+    pub index: usize,
+    pub model_id: Option<String>,
+    pub model_name: Option<String>,
+    pub model_type: Option<String>,
+}
+
+impl AgsiModelSummary {
+    /// The identifier used to select this model: `modelID`, then `modelName`, then the index.
+    pub fn label(&self) -> String {
+        self.model_id
+            .clone()
+            .or_else(|| self.model_name.clone())
+            .unwrap_or_else(|| self.index.to_string())
+    }
+}
+
+/// Lists the models contained in an AGSi file in document order.
+pub fn list_models(agsi: &AgSiV101) -> Vec<AgsiModelSummary> {
+    agsi.agsi_model
+        .iter()
+        .enumerate()
+        .map(|(index, model)| AgsiModelSummary {
+            index,
+            model_id: model.model_id.clone(),
+            model_name: model.model_name.clone(),
+            model_type: model.model_type.clone(),
+        })
+        .collect()
+}
+
+/// Picks a model by `modelID`, falling back to `modelName` and then to a numeric index.
+/// With no selection the first model is returned.
+pub fn select_model<'a>(
+    agsi: &'a AgSiV101,
+    model: Option<&str>,
+) -> Result<&'a AgsiModel, AgsiError> {
+    let Some(key) = model else {
+        return agsi.agsi_model.first().ok_or(AgsiError::MissingModel);
+    };
+
+    agsi.agsi_model
+        .iter()
+        .find(|m| m.model_id.as_deref() == Some(key))
+        .or_else(|| {
+            agsi.agsi_model
+                .iter()
+                .find(|m| m.model_name.as_deref() == Some(key))
+        })
+        .or_else(|| {
+            key.parse::<usize>()
+                .ok()
+                .and_then(|index| agsi.agsi_model.get(index))
+        })
+        .ok_or_else(|| AgsiError::UnknownModel {
+            model: key.to_string(),
+            available: list_models(agsi).iter().map(|m| m.label()).collect(),
+        })
+}
+
+impl TryFrom<&AgSiV101> for GroundModel {
+    type Error = AgsiError;
+
+    /// Builds a ground model from the first `agsiModel` in the file.
+    fn try_from(agsi: &AgSiV101) -> Result<Self, Self::Error> {
+        GroundModel::from_agsi_model(agsi, None)
+    }
+}
+
+impl TryFrom<&AgsiModel> for GroundModel {
+    type Error = AgsiError;
+
+    /// Builds a ground model assuming parameter values are in the AGSi default units.
+    fn try_from(model: &AgsiModel) -> Result<Self, Self::Error> {
+        GroundModel::from_agsi_model_with_units(model, &UnitTable::default())
+    }
+}
+
+impl GroundModel {
+    /// Errors carry JSON paths relative to the `agsiModel` object.
+    fn from_agsi_model_with_units(model: &AgsiModel, units: &UnitTable) -> Result<Self, AgsiError> {
+        let mut soil_params = Vec::new();
+        let mut cases: BTreeMap<String, Vec<SoilParams>> = BTreeMap::new();
+        let mut layer_geometries = Vec::new();
+        let mut groundwater_elements = Vec::new();
+
+        for (index, element) in model.agsi_model_element.iter().enumerate() {
+            if is_groundwater(element) {
+                groundwater_elements.push(element);
+                continue;
+            }
+
+            let reference = element
+                .element_name
+                .as_deref()
+                .or(element.element_id.as_deref())
+                .unwrap_or("unknown")
+                .to_string();
+
+            if let Some(geometry) = element
+                .agsi_geometry
+                .as_ref()
+                .and_then(AgsiLayerGeometry::from_element_geometry)
+            {
+                layer_geometries.push(AgsiLayerGeometry {
+                    reference: reference.clone(),
+                    path: format!("agsiModelElement[{}].agsiGeometry", index),
+                    description: element.description.clone().unwrap_or_default(),
+                    geol_code: geology_code(element),
+                    ..geometry
+                });
+            }
+
+            let (base_values, case_values): (Vec<_>, Vec<_>) = element
+                .agsi_data_parameter_value
+                .iter()
+                .cloned()
+                .partition(|value| value.case_id.is_none());
+
+            let mut case_ids: Vec<String> = Vec::new();
+            for case_id in case_values.iter().filter_map(|value| value.case_id.clone()) {
+                if !case_ids.contains(&case_id) {
+                    case_ids.push(case_id);
+                }
+            }
+
+            // Each case inherits the values that are not tied to a case
+            let mut element_cases = Vec::new();
+            for case_id in case_ids {
+                let mut values = base_values.clone();
+                values.extend(
+                    case_values
+                        .iter()
+                        .filter(|value| value.case_id.as_deref() == Some(case_id.as_str()))
+                        .cloned(),
+                );
+                let mut case_param =
+                    SoilParams::from_agsi_data_parameters_with_units(&values, units);
+                case_param.reference = reference.clone();
+                case_param.case_id = Some(case_id);
+                element_cases.push(case_param);
+            }
+
+            // Units that only define cased values fall back to their first case
+            let base_param = if !base_values.is_empty() {
+                let mut soil_param =
+                    SoilParams::from_agsi_data_parameters_with_units(&base_values, units);
+                soil_param.reference = reference;
+                Some(soil_param)
+            } else {
+                element_cases.first().cloned()
+            };
+            soil_params.extend(base_param);
+
+            for case_param in element_cases {
+                let case_id = case_param.case_id.clone().unwrap_or_default();
+                cases.entry(case_id).or_default().push(case_param);
+            }
+        }
+
+        let mut ground_model = GroundModel::new(
+            AgsiLayerGeometry::into_soil_layers(layer_geometries)?,
+            soil_params,
+        );
+        ground_model.cases = cases;
+        let (regime, water_table) =
+            groundwater_regime(&groundwater_elements, ground_model.get_top_level(), units);
+        if let Some(elevation) = water_table {
+            ground_model.groundwater = elevation;
+        }
+        ground_model.pore_pressure = regime;
+        ground_model.reference = model
+            .model_id
+            .clone()
+            .or_else(|| model.model_name.clone())
+            .unwrap_or_default();
+        Ok(ground_model)
+    }
+}
+
+impl GroundModel {
+    pub fn from_agsi_file(agsi_json: &serde_json::Value) -> Result<Self, AgsiError> {
+        GroundModel::try_from(&agsi_from_value(agsi_json)?)
+    }
+
+    /// Builds a ground model from the model chosen by [`select_model`]. Parameter values are
+    /// converted from the units declared in the file's `agsProjectCodeSet`.
+    pub fn from_agsi_model(agsi: &AgSiV101, model: Option<&str>) -> Result<Self, AgsiError> {
+        let units = UnitTable::from_agsi(agsi)?;
+        let model = select_model(agsi, model)?;
+        GroundModel::from_agsi_model_with_units(model, &units).map_err(|err| match err {
+            AgsiError::Parse { path, message } => {
+                let index = agsi
+                    .agsi_model
+                    .iter()
+                    .position(|candidate| std::ptr::eq(candidate, model))
+                    .unwrap_or_default();
+                AgsiError::Parse {
+                    path: format!("agsiModel[{}].{}", index, path),
+                    message,
+                }
+            }
+            other => other,
+        })
+    }
+}
+
+/// File and model metadata written by [`GroundModel::to_agsi`].
+#[derive(Debug, Clone)]
+pub struct AgsiExportOptions {
+    pub title: String,
+    pub produced_by: String,
+    /// `modelID` of the exported model; defaults to the ground model reference.
+    pub model_id: Option<String>,
+    pub model_name: Option<String>,
+    pub model_type: Option<String>,
+}
+
+impl Default for AgsiExportOptions {
+    fn default() -> Self {
+        AgsiExportOptions {
+            title: "Ground model".to_string(),
+            produced_by: "groundmodels".to_string(),
+            model_id: None,
+            model_name: None,
+            model_type: None,
+        }
+    }
+}
+
+impl GroundModel {
+    /// Writes the model as an AGSi v1.0.1 document with one `agsiModelElement` per layer,
+    /// layer geometry as `agsiGeometryLayer` and the groundwater level as a plane. The
+    /// `agsProjectCodeSet` declares the unit of each exported parameter code.
+    pub fn to_agsi(&self, opts: AgsiExportOptions) -> AgSiV101 {
+        let mut elements = Vec::new();
+        let mut exported = Vec::new();
+
+        let mut layers: Vec<&SoilLayer> = self.soil_layers.iter().collect();
+        layers.sort_by(|a, b| {
+            b.top_level
+                .partial_cmp(&a.top_level)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        for layer in layers {
+            let reference = if layer.unit_reference.is_empty() {
+                layer.reference.clone()
+            } else {
+                layer.unit_reference.clone()
+            };
+            let geometry = match layer.base_level {
+                Some(bottom_elevation) => AgsiGeometryLayer::Variant2 {
+                    bottom_elevation,
+                    description: None,
+                    geometry_id: None,
+                    remarks: None,
+                    top_elevation: layer.top_level,
+                },
+                None => AgsiGeometryLayer::Variant0 {
+                    description: None,
+                    geometry_id: None,
+                    remarks: None,
+                    top_elevation: layer.top_level,
+                },
+            };
+
+            // Repeated units carry their parameters on the first element only
+            let parameters = if exported.contains(&reference) {
+                Vec::new()
+            } else {
+                exported.push(reference.clone());
+                self.agsi_parameter_values(&reference)
+            };
+
+            elements.push(AgsiModelElement {
+                agsi_data_parameter_value: parameters,
+                agsi_data_property_value: non_empty(&layer.geol_code)
+                    .map(geology_code_value)
+                    .into_iter()
+                    .collect(),
+                agsi_geometry: Some(AgsiModelElementAgsiGeometry {
+                    subtype_4: Some(geometry),
+                    ..Default::default()
+                }),
+                description: non_empty(&layer.typical_description),
+                element_name: non_empty(&reference),
+                geometry_object: Some("agsiGeometryLayer".to_string()),
+                ..Default::default()
+            });
+        }
+
+        // Parameter sets that are not linked to a layer
+        for params in &self.soil_params {
+            if exported.contains(&params.reference) {
+                continue;
+            }
+            exported.push(params.reference.clone());
+            elements.push(AgsiModelElement {
+                agsi_data_parameter_value: self.agsi_parameter_values(&params.reference),
+                element_name: non_empty(&params.reference),
+                ..Default::default()
+            });
+        }
+
+        elements.extend(self.groundwater_elements());
+
+        let title = non_empty(&opts.title).unwrap_or_else(|| "Ground model".to_string());
+        let produced_by =
+            non_empty(&opts.produced_by).unwrap_or_else(|| "groundmodels".to_string());
+        let code_set = self.parameter_code_set(&elements);
+
+        AgSiV101 {
+            ags_file: AgsFile::builder()
+                .title(title.as_str())
+                .produced_by(produced_by.as_str())
+                .try_into()
+                .expect("AGSi file metadata is non-empty"),
+            ags_project: Some(
+                AgsProject::builder()
+                    .project_name(title.as_str())
+                    .ags_project_code_set(vec![code_set])
+                    .try_into()
+                    .expect("AGSi project name is non-empty"),
+            ),
+            ags_schema: AgsSchema::builder()
+                .name("AGSi")
+                .version("1.0.1")
+                .try_into()
+                .expect("AGSi schema metadata is non-empty"),
+            agsi_model: vec![AgsiModel {
+                agsi_model_element: elements,
+                model_id: opts.model_id.or_else(|| non_empty(&self.reference)),
+                model_name: opts.model_name,
+                model_type: opts.model_type,
+                ..Default::default()
+            }],
+        }
+    }
+
+    /// `Groundwater` elements for the pore pressure regime: a plane for each water table and
+    /// a layer for each bounded zone, with `PiezometricHead` or `PorePressure` values where
+    /// the head is not the top of the zone. Without a regime, one plane at `groundwater`.
+    fn groundwater_elements(&self) -> Vec<AgsiModelElement> {
+        let Some(regime) = &self.pore_pressure else {
+            return vec![groundwater_element(
+                "Groundwater".to_string(),
+                Some(groundwater_plane(self.groundwater)),
+                Vec::new(),
+            )];
+        };
+
+        regime
+            .zones
+            .iter()
+            .enumerate()
+            .map(|(i, zone)| {
+                let name = if i == 0 {
+                    "Groundwater".to_string()
+                } else {
+                    format!("Groundwater {}", i + 1)
+                };
+
+                let mut values = Vec::new();
+                if i == 0 && regime.unit_weight_water != DEFAULT_UNIT_WEIGHT_WATER {
+                    values.push(water_value(
+                        "UnitWeightWater",
+                        Some(regime.unit_weight_water),
+                    ));
+                }
+
+                let bounds = match (zone.top_level, zone.base_level) {
+                    (Some(top_elevation), Some(bottom_elevation)) => {
+                        Some(AgsiGeometryLayer::Variant2 {
+                            bottom_elevation,
+                            description: None,
+                            geometry_id: None,
+                            remarks: None,
+                            top_elevation,
+                        })
+                    }
+                    (Some(top_elevation), None) => Some(AgsiGeometryLayer::Variant0 {
+                        description: None,
+                        geometry_id: None,
+                        remarks: None,
+                        top_elevation,
+                    }),
+                    (None, Some(bottom_elevation)) => Some(AgsiGeometryLayer::Variant1 {
+                        bottom_elevation,
+                        description: None,
+                        geometry_id: None,
+                        remarks: None,
+                    }),
+                    (None, None) => None,
+                };
+
+                let geometry = match (&zone.head, bounds) {
+                    (PiezometricHead::Level(head), None) => Some(groundwater_plane(*head)),
+                    (head, Some(layer)) => {
+                        if let PiezometricHead::Level(level) = head {
+                            if Some(*level) != zone.top_level {
+                                values.push(water_value("PiezometricHead", Some(*level)));
+                            }
+                        }
+                        Some(AgsiModelElementAgsiGeometry {
+                            subtype_4: Some(layer),
+                            ..Default::default()
+                        })
+                    }
+                    (PiezometricHead::Profile(_), None) => None,
+                };
+                if let PiezometricHead::Profile(points) = &zone.head {
+                    values.push(AgsiDataParameterValue {
+                        value_profile: Some(points.clone().into()),
+                        value_profile_ind_var_code_id: Some("Elevation".to_string()),
+                        ..water_value("PorePressure", None)
+                    });
+                }
+
+                groundwater_element(name, geometry, values)
+            })
+            .collect()
+    }
+
+    /// Code set for the `codeID` of every parameter value in `elements`, with the AGSi default
+    /// unit for codes this library models and the unit advanced parameters were read in.
+    fn parameter_code_set(&self, elements: &[AgsiModelElement]) -> AgsProjectCodeSet {
+        let advanced_unit = |code_id: &str| {
+            self.soil_params
+                .iter()
+                .chain(self.cases.values().flatten())
+                .flat_map(|params| params.advanced_parameters.iter().flatten())
+                .find(|advanced| advanced.name == code_id)
+                .and_then(|advanced| advanced.units.clone())
+        };
+
+        let mut codes: Vec<AgsProjectCode> = Vec::new();
+        for value in elements
+            .iter()
+            .flat_map(|element| element.agsi_data_parameter_value.iter())
+        {
+            let code_id = value.code_id.as_str();
+            if codes.iter().any(|code| code.code_id.as_str() == code_id) {
+                continue;
+            }
+            let units = match Quantity::of_code(code_id).agsi_default_unit() {
+                Some(unit) => Some(unit.to_string()),
+                None => advanced_unit(code_id),
+            };
+            codes.push(AgsProjectCode {
+                code_id: code_id.parse().expect("code ID is non-empty"),
+                description: code_id.parse().expect("code ID is non-empty"),
+                is_standard: None,
+                remarks: None,
+                units,
+            });
+        }
+
+        AgsProjectCodeSet::builder()
+            .used_by_object("agsiDataParameterValue")
+            .used_by_attribute("codeID")
+            .ags_project_code(codes)
+            .try_into()
+            .expect("code set attributes are non-empty")
+    }
+
+    /// Parameter values for one unit: the base set without a `caseID`, followed by the
+    /// values each design case changes.
+    fn agsi_parameter_values(&self, reference: &str) -> Vec<AgsiDataParameterValue> {
+        let base = self
+            .soil_params
+            .iter()
+            .find(|params| params.reference == reference);
+
+        let mut values = Vec::new();
+        let base_values = match base {
+            Some(params) if params.case_id.is_none() => {
+                let base_values = soil_params_to_agsi(params, None);
+                values.extend(base_values.iter().cloned());
+                base_values
+            }
+            _ => Vec::new(),
+        };
+
+        for (case_id, case_params) in &self.cases {
+            if let Some(params) = case_params.iter().find(|p| p.reference == reference) {
+                values.extend(
+                    soil_params_to_agsi(params, Some(case_id))
+                        .into_iter()
+                        .filter(|value| {
+                            !base_values.iter().any(|base_value| {
+                                base_value.code_id == value.code_id
+                                    && base_value.value_numeric == value.value_numeric
+                                    && base_value.value_profile.as_deref()
+                                        == value.value_profile.as_deref()
+                            })
+                        }),
+                );
+            }
+        }
+
+        // Units that only have cased values were imported with their first case as the base
+        if values.is_empty() {
+            if let Some(params) = base {
+                values = soil_params_to_agsi(params, params.case_id.as_deref());
+            }
+        }
+
+        values
+    }
+}
+
+/// Maps `SoilParams` fields to AGSi parameter values using the code IDs read by
+/// [`SoilParams::from_agsi_data_parameters`], in the AGSi default units. Unset or
+/// zero-valued fields are skipped.
+fn soil_params_to_agsi(params: &SoilParams, case_id: Option<&str>) -> Vec<AgsiDataParameterValue> {
+    let units = UnitTable::default();
+    let set = |value: f64| if value != 0.0 { Some(value) } else { None };
+    let mut fields: Vec<(String, Option<f64>)> = vec![
+        ("UnitWeight".to_string(), set(params.unit_weight)),
+        (
+            "UnitWeightSaturated".to_string(),
+            params.saturated_unit_weight,
+        ),
+        ("AngleFriction".to_string(), params.phi_prime),
+        ("Cohesion".to_string(), params.c_prime),
+        ("UndrainedShearStrength".to_string(), params.cu),
+        ("YoungsModulus".to_string(), set(params.youngs_modulus)),
+        ("ModulusOfVolumeCompressibility".to_string(), set(params.mv)),
+        ("PoissonsRatio".to_string(), set(params.poissons_ratio)),
+        (
+            "CoefficientOfConsolidation".to_string(),
+            set(params.coefficient_of_consolidation),
+        ),
+        (
+            "ModulusOfVolumeCompressibilityRecompression".to_string(),
+            params.mv_recompression,
+        ),
+        ("OverconsolidationRatio".to_string(), params.ocr),
+        ("PreOverburdenPressure".to_string(), params.pop),
+        (
+            "PreconsolidationPressure".to_string(),
+            params.preconsolidation_pressure,
+        ),
+        ("GeologicalStrengthIndex".to_string(), params.gsi),
+        ("UnconfinedCompressiveStrength".to_string(), params.ucs),
+        ("HoekBrownParamMi".to_string(), params.mi),
+        ("Disturbance".to_string(), set(params.disturbance)),
+    ];
+    for advanced in params.advanced_parameters.iter().flatten() {
+        fields.push((advanced.name.clone(), Some(advanced.value)));
+    }
+
+    fields
+        .into_iter()
+        .filter_map(|(code, value)| {
+            let profile = params
+                .profiles
+                .iter()
+                .find(|profile| canonical_code(&profile.code_id) == code);
+            if value.is_none() && profile.is_none() {
+                return None;
+            }
+            let value = value.map(|value| units.to_agsi(&code, value));
+            let points = profile.map(|p| {
+                p.points
+                    .iter()
+                    .map(|&[position, value]| [position, units.to_agsi(&code, value)])
+                    .collect::<Vec<_>>()
+            });
+
+            Some(AgsiDataParameterValue {
+                code_id: code.parse().ok()?,
+                case_id: case_id.map(str::to_string),
+                data_id: None,
+                remarks: None,
+                value_numeric: if profile.is_some() { None } else { value },
+                value_profile: points.map(Into::into),
+                value_profile_ind_var_code_id: profile.map(|p| {
+                    match p.variable {
+                        ProfileVariable::Depth => "Depth",
+                        ProfileVariable::Elevation => "Elevation",
+                    }
+                    .to_string()
+                }),
+                value_text: None,
+            })
+        })
+        .collect()
+}
+
+fn canonical_code(code_id: &str) -> &str {
+    match code_id {
+        "EffectiveFrictionAngle" => "AngleFriction",
+        "EffectiveCohesion" => "Cohesion",
+        other => other,
+    }
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+/// AGSi model elements have no geology code attribute, so it is carried as a text property
+/// value with this code.
+const GEOLOGY_CODE: &str = "GeologyCode";
+
+fn geology_code(element: &AgsiModelElement) -> String {
+    element
+        .agsi_data_property_value
+        .iter()
+        .find(|value| value.code_id.as_str() == GEOLOGY_CODE && value.case_id.is_none())
+        .and_then(|value| value.value_text.clone())
+        .unwrap_or_default()
+}
+
+fn geology_code_value(geol_code: String) -> AgsiDataPropertyValue {
+    AgsiDataPropertyValue {
+        case_id: None,
+        code_id: GEOLOGY_CODE.parse().expect("code ID is non-empty"),
+        data_id: None,
+        remarks: None,
+        value_numeric: None,
+        value_profile: None,
+        value_profile_ind_var_code_id: None,
+        value_text: Some(geol_code),
+    }
+}
+
+fn is_groundwater(element: &AgsiModelElement) -> bool {
+    element.element_type.as_deref() == Some("Groundwater")
+}
+
+/// Reads the pore pressure regime from `Groundwater` elements. A plane element is a water
+/// table. An element with layer or volume geometry is a zone of perched or confined water
+/// whose head is its top, unless it gives a `PiezometricHead` elevation or a `PorePressure`
+/// profile. `UnitWeightWater` on any element sets the unit weight of water. Values are
+/// converted with `units`.
+///
+/// Returns the regime, or `None` if the elements describe a single hydrostatic water table,
+/// and the level of the last water table plane (or the highest head if there is no plane).
+fn groundwater_regime(
+    elements: &[&AgsiModelElement],
+    datum: f64,
+    units: &UnitTable,
+) -> (Option<PorePressureRegime>, Option<f64>) {
+    let mut zones = Vec::new();
+    let mut unit_weight_water = None;
+    let mut water_table = None;
+
+    for element in elements {
+        let geometry = element.agsi_geometry.as_ref();
+        let plane = geometry
+            .and_then(|geometry| geometry.subtype_3.as_ref())
+            .map(|plane| plane.elevation);
+        let bounds = geometry.and_then(AgsiLayerGeometry::from_element_geometry);
+        if plane.is_some() {
+            water_table = plane;
+        }
+
+        let (top_level, base_level) = match &bounds {
+            Some(bounds) => (bounds.top_level, bounds.base_level),
+            None => (None, None),
+        };
+        let mut head = plane.or(top_level).map(PiezometricHead::Level);
+
+        for value in element
+            .agsi_data_parameter_value
+            .iter()
+            .filter(|value| value.case_id.is_none())
+        {
+            let code_id = value.code_id.as_str();
+            match code_id {
+                "PiezometricHead" => {
+                    if let Some(level) = value.value_numeric {
+                        head = Some(PiezometricHead::Level(units.to_internal(code_id, level)));
+                    }
+                }
+                "PorePressure" => {
+                    let Some(profile) = value.value_profile.as_deref() else {
+                        continue;
+                    };
+                    let by_elevation = value
+                        .value_profile_ind_var_code_id
+                        .as_deref()
+                        .is_some_and(|var| var.to_lowercase().contains("elevation"));
+                    let points = profile
+                        .iter()
+                        .map(|[position, pwp]| {
+                            let level = if by_elevation {
+                                *position
+                            } else {
+                                datum - position
+                            };
+                            [level, units.to_internal(code_id, *pwp)]
+                        })
+                        .collect();
+                    head = Some(PiezometricHead::Profile(points));
+                }
+                "UnitWeightWater" => {
+                    unit_weight_water = value
+                        .value_numeric
+                        .map(|value| units.to_internal(code_id, value))
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(head) = head {
+            zones.push(PiezometricZone::new(top_level, base_level, head));
+        }
+    }
+
+    // Bounded zones take precedence over water tables that extend through the whole model
+    zones.sort_by_key(|zone| zone.top_level.is_none() && zone.base_level.is_none());
+
+    let water_table = water_table.or_else(|| {
+        zones
+            .iter()
+            .filter_map(|zone| match zone.head {
+                PiezometricHead::Level(level) => Some(level),
+                PiezometricHead::Profile(_) => None,
+            })
+            .reduce(f64::max)
+    });
+
+    let simple = unit_weight_water.is_none()
+        && match zones.as_slice() {
+            [] => true,
+            [zone] => {
+                zone.top_level.is_none()
+                    && zone.base_level.is_none()
+                    && matches!(zone.head, PiezometricHead::Level(_))
+            }
+            _ => false,
+        };
+    if simple {
+        return (None, water_table);
+    }
+
+    let regime = PorePressureRegime {
+        unit_weight_water: unit_weight_water.unwrap_or(DEFAULT_UNIT_WEIGHT_WATER),
+        zones,
+    };
+    (Some(regime), water_table)
+}
+
+fn groundwater_plane(elevation: f64) -> AgsiModelElementAgsiGeometry {
+    AgsiModelElementAgsiGeometry {
+        subtype_3: Some(AgsiGeometryPlane {
+            description: Some("Groundwater level".to_string()),
+            elevation,
+            geometry_id: None,
+            remarks: None,
+        }),
+        ..Default::default()
+    }
+}
+
+fn groundwater_element(
+    name: String,
+    geometry: Option<AgsiModelElementAgsiGeometry>,
+    values: Vec<AgsiDataParameterValue>,
+) -> AgsiModelElement {
+    let geometry_object = geometry.as_ref().map(|geometry| {
+        if geometry.subtype_3.is_some() {
+            "agsiGeometryPlane".to_string()
+        } else {
+            "agsiGeometryLayer".to_string()
+        }
+    });
+    AgsiModelElement {
+        agsi_data_parameter_value: values,
+        agsi_geometry: geometry,
+        element_id: Some(name.clone()),
+        element_name: Some(name),
+        element_type: Some("Groundwater".to_string()),
+        geometry_object,
+        ..Default::default()
+    }
+}
+
+fn water_value(code_id: &str, value_numeric: Option<f64>) -> AgsiDataParameterValue {
+    AgsiDataParameterValue {
+        code_id: code_id.parse().expect("code ID is non-empty"),
+        case_id: None,
+        data_id: None,
+        remarks: None,
+        value_numeric,
+        value_profile: None,
+        value_profile_ind_var_code_id: None,
+        value_text: None,
+    }
+}
+
+impl AgsiObservationColumn {
+    pub fn top_depth(&self) -> Option<f64> {
+        match self {
+            AgsiObservationColumn::Variant0 { top_depth, .. }
+            | AgsiObservationColumn::Variant2 { top_depth, .. } => Some(*top_depth),
+            AgsiObservationColumn::Variant1 { .. } => None,
+        }
+    }
+
+    pub fn top_elevation(&self) -> Option<f64> {
+        match self {
+            AgsiObservationColumn::Variant1 { top_elevation, .. }
+            | AgsiObservationColumn::Variant2 { top_elevation, .. } => Some(*top_elevation),
+            AgsiObservationColumn::Variant0 { .. } => None,
+        }
+    }
+
+    pub fn bottom_depth(&self) -> Option<f64> {
+        match self {
+            AgsiObservationColumn::Variant0 { bottom_depth, .. }
+            | AgsiObservationColumn::Variant1 { bottom_depth, .. }
+            | AgsiObservationColumn::Variant2 { bottom_depth, .. } => *bottom_depth,
+        }
+    }
+
+    pub fn bottom_elevation(&self) -> Option<f64> {
+        match self {
+            AgsiObservationColumn::Variant0 {
+                bottom_elevation, ..
+            }
+            | AgsiObservationColumn::Variant1 {
+                bottom_elevation, ..
+            }
+            | AgsiObservationColumn::Variant2 {
+                bottom_elevation, ..
+            } => *bottom_elevation,
+        }
+    }
+
+    pub fn geology_code(&self) -> Option<&str> {
+        match self {
+            AgsiObservationColumn::Variant0 { geology_code, .. }
+            | AgsiObservationColumn::Variant1 { geology_code, .. }
+            | AgsiObservationColumn::Variant2 { geology_code, .. } => geology_code.as_deref(),
+        }
+    }
+}
+
+/// Vertical extent of an AGSi model element before it is linked into the stratigraphy.
+struct AgsiLayerGeometry {
+    reference: String,
+    /// JSON path of the element geometry, used in error messages.
+    path: String,
+    top_level: Option<f64>,
+    base_level: Option<f64>,
+    description: String,
+    geol_code: String,
+}
+
+impl AgsiLayerGeometry {
+    fn from_element_geometry(geometry: &AgsiModelElementAgsiGeometry) -> Option<Self> {
+        let (top_level, base_level) = if let Some(layer) = &geometry.subtype_4 {
+            match layer {
+                AgsiGeometryLayer::Variant0 { top_elevation, .. } => (Some(*top_elevation), None),
+                AgsiGeometryLayer::Variant1 {
+                    bottom_elevation, ..
+                } => (None, Some(*bottom_elevation)),
+                AgsiGeometryLayer::Variant2 {
+                    top_elevation,
+                    bottom_elevation,
+                    ..
+                } => (Some(*top_elevation), Some(*bottom_elevation)),
+            }
+        } else if let Some(volume) = &geometry.subtype_0 {
+            // Only plane surfaces can be evaluated without the referenced geometry files
+            let top_plane = |top: &AgsiGeometryVolFromSurfacesVariant0AgsiGeometryTop| match top {
+                AgsiGeometryVolFromSurfacesVariant0AgsiGeometryTop::Plane(plane) => {
+                    Some(plane.elevation)
+                }
+                AgsiGeometryVolFromSurfacesVariant0AgsiGeometryTop::FromFile(_) => None,
+            };
+            let bottom_plane =
+                |bottom: &AgsiGeometryVolFromSurfacesVariant1AgsiGeometryBottom| match bottom {
+                    AgsiGeometryVolFromSurfacesVariant1AgsiGeometryBottom::Plane(plane) => {
+                        Some(plane.elevation)
+                    }
+                    AgsiGeometryVolFromSurfacesVariant1AgsiGeometryBottom::FromFile(_) => None,
+                };
+
+            match volume {
+                AgsiGeometryVolFromSurfaces::Variant0 {
+                    agsi_geometry_top, ..
+                } => (top_plane(agsi_geometry_top), None),
+                AgsiGeometryVolFromSurfaces::Variant1 {
+                    agsi_geometry_bottom,
+                    ..
+                } => (None, bottom_plane(agsi_geometry_bottom)),
+                AgsiGeometryVolFromSurfaces::Variant2 {
+                    agsi_geometry_top,
+                    agsi_geometry_bottom,
+                    ..
+                } => (
+                    top_plane(agsi_geometry_top),
+                    bottom_plane(agsi_geometry_bottom),
+                ),
+            }
+        } else {
+            return None;
+        };
+
+        if top_level.is_none() && base_level.is_none() {
+            return None;
+        }
+
+        Some(AgsiLayerGeometry {
+            reference: String::new(),
+            path: String::new(),
+            top_level,
+            base_level,
+            description: String::new(),
+            geol_code: String::new(),
+        })
+    }
+
+    /// Orders the geometries from the top down and fills in a missing top level from the
+    /// base of the layer above. A missing base level is left unset and the layer below is
+    /// recorded as the base unit instead. A layer whose top cannot be resolved this way,
+    /// because it is the uppermost layer or the layer above has no base level either, is
+    /// an error.
+    fn into_soil_layers(
+        mut geometries: Vec<AgsiLayerGeometry>,
+    ) -> Result<Vec<SoilLayer>, AgsiError> {
+        let sort_level = |g: &AgsiLayerGeometry| g.top_level.or(g.base_level).unwrap_or(f64::MIN);
+        geometries.sort_by(|a, b| {
+            sort_level(b)
+                .partial_cmp(&sort_level(a))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let mut layers = Vec::new();
+        let mut previous_base: Option<f64> = None;
+        for (i, geometry) in geometries.iter().enumerate() {
+            let top_level = geometry.top_level.or(previous_base).ok_or_else(|| {
+                let message = match i.checked_sub(1).map(|above| &geometries[above]) {
+                    Some(above) => format!(
+                        "'{}' has no top elevation and '{}' above it has no bottom elevation",
+                        geometry.reference, above.reference
+                    ),
+                    None => format!(
+                        "'{}' is the uppermost layer but has no top elevation",
+                        geometry.reference
+                    ),
+                };
+                AgsiError::Parse {
+                    path: geometry.path.clone(),
+                    message,
+                }
+            })?;
+            let base_unit_reference = if geometry.base_level.is_none() {
+                geometries.get(i + 1).map(|next| next.reference.clone())
+            } else {
+                None
+            };
+            layers.push(SoilLayer::with_all_fields(
+                geometry.reference.clone(),
+                top_level,
+                geometry.base_level,
+                base_unit_reference,
+                geometry.description.clone(),
+                geometry.geol_code.clone(),
+            ));
+            previous_base = geometry.base_level;
+        }
+
+        Ok(layers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GroundModelError, SoilType};
+
+    fn agsi_document(models: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "agsFile": {"producedBy": "groundmodels", "title": "Test model"},
+            "agsSchema": {"name": "AGSi", "version": "1.0.1"},
+            "agsiModel": models
+        })
+    }
+
+    #[test]
+    fn test_from_agsi_file_builds_layers() {
+        let agsi = agsi_document(serde_json::json!([{
+            "agsiModelElement": [
+                {
+                    "elementID": "SAND",
+                    "elementName": "Terrace Sand",
+                    "agsiGeometry": {
+                        "agsiGeometryTop": {"elevation": 5.0},
+                        "agsiGeometryBottom": {"elevation": -2.0}
+                    },
+                    "agsiDataParameterValue": [
+                        {"codeID": "UnitWeight", "valueNumeric": 19.0}
+                    ]
+                },
+                {
+                    "elementID": "E1",
+                    "elementName": "Made Ground",
+                    "description": "Gravelly sand with brick",
+                    "agsiGeometry": {"topElevation": 8.0, "bottomElevation": 5.0},
+                    "agsiDataPropertyValue": [
+                        {"codeID": "GeologyCode", "valueText": "MG"}
+                    ],
+                    "agsiDataParameterValue": [
+                        {"codeID": "UnitWeight", "valueNumeric": 18.0}
+                    ]
+                },
+                {
+                    "elementID": "CLAY",
+                    "agsiGeometry": {"bottomElevation": -20.0},
+                    "agsiDataParameterValue": [
+                        {"codeID": "UndrainedShearStrength", "valueNumeric": 75.0}
+                    ]
+                }
+            ]
+        }]));
+
+        let ground_model = GroundModel::from_agsi_file(&agsi).unwrap();
+
+        assert_eq!(ground_model.soil_layers.len(), 3);
+        let made_ground = &ground_model.soil_layers[0];
+        assert_eq!(made_ground.unit_reference, "Made Ground");
+        assert_eq!(made_ground.geol_code, "MG");
+        assert_eq!(made_ground.typical_description, "Gravelly sand with brick");
+        assert_eq!(made_ground.top_level, 8.0);
+        assert_eq!(made_ground.base_level, Some(5.0));
+
+        // Bottom-only layer takes its top from the layer above
+        let clay = &ground_model.soil_layers[2];
+        assert_eq!(clay.unit_reference, "CLAY");
+        // The element ID is not a geology code
+        assert_eq!(clay.geol_code, "");
+        assert_eq!(clay.top_level, -2.0);
+        assert_eq!(clay.base_level, Some(-20.0));
+
+        assert_eq!(
+            ground_model.get_params_at_level(6.0).unwrap().unit_weight,
+            18.0
+        );
+        assert_eq!(
+            ground_model.get_params_at_level(0.0).unwrap().unit_weight,
+            19.0
+        );
+        assert_eq!(
+            ground_model.get_params_at_level(-10.0).unwrap().behaviour,
+            SoilType::Cohesive
+        );
+    }
+
+    #[test]
+    fn test_from_agsi_file_top_only_layer_links_base_unit() {
+        let agsi = agsi_document(serde_json::json!([{
+            "agsiModelElement": [
+                {
+                    "elementName": "Alluvium",
+                    "agsiGeometry": {"topElevation": 3.0}
+                },
+                {
+                    "elementName": "Chalk",
+                    "agsiGeometry": {"topElevation": -4.0, "bottomElevation": -30.0}
+                }
+            ]
+        }]));
+
+        let ground_model = GroundModel::from_agsi_file(&agsi).unwrap();
+
+        assert_eq!(ground_model.soil_layers.len(), 2);
+        assert_eq!(ground_model.soil_layers[0].base_level, None);
+        assert_eq!(
+            ground_model.soil_layers[0].base_unit_reference,
+            Some("Chalk".to_string())
+        );
+        assert!(ground_model.soil_params.is_empty());
+    }
+
+    #[test]
+    fn test_from_agsi_file_rejects_base_only_layer_below_top_only_layer() {
+        let agsi = agsi_document(serde_json::json!([
+            {"modelID": "Empty"},
+            {
+                "agsiModelElement": [
+                    {
+                        "elementName": "Alluvium",
+                        "agsiGeometry": {"topElevation": 3.0}
+                    },
+                    {
+                        "elementName": "Gravel",
+                        "agsiGeometry": {"bottomElevation": -6.0}
+                    }
+                ]
+            }
+        ]));
+        let agsi = agsi_from_value(&agsi).unwrap();
+
+        match GroundModel::from_agsi_model(&agsi, Some("1")) {
+            Err(AgsiError::Parse { path, message }) => {
+                assert_eq!(path, "agsiModel[1].agsiModelElement[1].agsiGeometry");
+                assert!(message.contains("'Gravel'"));
+                assert!(message.contains("'Alluvium'"));
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_agsi_reports_json_path() {
+        let agsi = agsi_document(serde_json::json!([{
+            "agsiModelElement": [
+                {"elementName": "Sand"},
+                {
+                    "elementName": "Clay",
+                    "agsiDataParameterValue": [
+                        {"codeID": "UnitWeight", "valueNumeric": "heavy"}
+                    ]
+                }
+            ]
+        }]));
+
+        match parse_agsi(&agsi.to_string()) {
+            Err(AgsiError::Parse { path, .. }) => assert_eq!(
+                path,
+                "agsiModel[0].agsiModelElement[1].agsiDataParameterValue[0].valueNumeric"
+            ),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_agsi_requires_file_metadata() {
+        let err = parse_agsi(r#"{"agsiModel": []}"#).unwrap_err();
+        assert!(matches!(err, AgsiError::Parse { .. }));
+        assert!(err.to_string().contains("agsFile"));
+    }
+
+    #[test]
+    fn test_try_from_without_model_fails() {
+        let agsi = parse_agsi(&agsi_document(serde_json::json!([])).to_string()).unwrap();
+        assert!(matches!(
+            GroundModel::try_from(&agsi),
+            Err(AgsiError::MissingModel)
+        ));
+    }
+
+    fn multi_model_document() -> AgSiV101 {
+        let agsi = agsi_document(serde_json::json!([
+            {
+                "modelID": "GEOL",
+                "modelName": "Geological model",
+                "modelType": "Geological model",
+                "agsiModelElement": [{
+                    "elementName": "Alluvium",
+                    "agsiDataParameterValue": [{"codeID": "UnitWeight", "valueNumeric": 17.0}]
+                }]
+            },
+            {
+                "modelID": "DESIGN",
+                "modelName": "Geotechnical design model",
+                "agsiModelElement": [{
+                    "elementName": "Alluvium",
+                    "agsiDataParameterValue": [{"codeID": "UnitWeight", "valueNumeric": 18.5}]
+                }]
+            },
+            {
+                "agsiModelElement": []
+            }
+        ]));
+        agsi_from_value(&agsi).unwrap()
+    }
+
+    #[test]
+    fn test_list_models() {
+        let models = list_models(&multi_model_document());
+
+        assert_eq!(models.len(), 3);
+        assert_eq!(models[0].model_id.as_deref(), Some("GEOL"));
+        assert_eq!(models[0].model_type.as_deref(), Some("Geological model"));
+        assert_eq!(models[1].label(), "DESIGN");
+        assert_eq!(models[2].label(), "2");
+    }
+
+    #[test]
+    fn test_select_model_by_id_name_and_index() {
+        let agsi = multi_model_document();
+
+        let by_id = GroundModel::from_agsi_model(&agsi, Some("DESIGN")).unwrap();
+        assert_eq!(by_id.reference, "DESIGN");
+        assert_eq!(by_id.soil_params[0].unit_weight, 18.5);
+
+        let by_name = GroundModel::from_agsi_model(&agsi, Some("Geological model")).unwrap();
+        assert_eq!(by_name.soil_params[0].unit_weight, 17.0);
+
+        let by_index = select_model(&agsi, Some("2")).unwrap();
+        assert!(by_index.agsi_model_element.is_empty());
+
+        let first = GroundModel::try_from(&agsi).unwrap();
+        assert_eq!(first.reference, "GEOL");
+    }
+
+    #[test]
+    fn test_select_unknown_model_lists_available() {
+        let err = select_model(&multi_model_document(), Some("SECTION-A")).unwrap_err();
+
+        match &err {
+            AgsiError::UnknownModel { model, available } => {
+                assert_eq!(model, "SECTION-A");
+                assert_eq!(available, &vec!["GEOL", "DESIGN", "2"]);
+            }
+            other => panic!("expected an unknown model error, got {:?}", other),
+        }
+        assert!(err.to_string().contains("GEOL, DESIGN, 2"));
+    }
+
+    #[test]
+    fn test_parameters_grouped_by_case() {
+        let agsi = agsi_document(serde_json::json!([{
+            "agsiModelElement": [
+                {
+                    "elementName": "London Clay",
+                    "agsiDataParameterValue": [
+                        {"codeID": "UnitWeight", "valueNumeric": 20.0},
+                        {"codeID": "UndrainedShearStrength", "caseID": "Characteristic", "valueNumeric": 80.0},
+                        {"codeID": "UndrainedShearStrength", "caseID": "Lower bound", "valueNumeric": 60.0}
+                    ]
+                },
+                {
+                    "elementName": "Terrace Gravel",
+                    "agsiDataParameterValue": [
+                        {"codeID": "UnitWeight", "caseID": "Characteristic", "valueNumeric": 19.0},
+                        {"codeID": "EffectiveFrictionAngle", "caseID": "Characteristic", "valueNumeric": 34.0}
+                    ]
+                }
+            ]
+        }]));
+
+        let ground_model = GroundModel::from_agsi_file(&agsi).unwrap();
+
+        assert_eq!(
+            ground_model.case_ids(),
+            vec!["Characteristic", "Lower bound"]
+        );
+        assert_eq!(ground_model.soil_params.len(), 2);
+        let clay = &ground_model.soil_params[0];
+        assert_eq!(clay.case_id, None);
+        assert_eq!(clay.unit_weight, 20.0);
+        assert_eq!(clay.cu, None);
+        assert_eq!(
+            ground_model.soil_params[1].case_id.as_deref(),
+            Some("Characteristic")
+        );
+
+        let lower_bound = &ground_model.cases["Lower bound"];
+        assert_eq!(lower_bound.len(), 1);
+        assert_eq!(lower_bound[0].unit_weight, 20.0);
+        assert_eq!(lower_bound[0].cu, Some(60.0));
+
+        let characteristic = ground_model.for_case("Characteristic").unwrap();
+        assert_eq!(characteristic.soil_params[0].cu, Some(80.0));
+        assert_eq!(
+            characteristic.soil_params[1].phi_prime,
+            Some(34.0_f64.to_radians())
+        );
+
+        // Units without values for the case keep their base parameters
+        let lower_bound_model = ground_model.for_case("Lower bound").unwrap();
+        assert_eq!(lower_bound_model.soil_params[1].unit_weight, 19.0);
+
+        assert!(matches!(
+            ground_model.for_case("DA1-C2"),
+            Err(GroundModelError::UnknownCase { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parameters_converted_from_declared_units() {
+        let mut agsi = agsi_document(serde_json::json!([{
+            "agsiModelElement": [{
+                "elementName": "Mudstone",
+                "agsiDataParameterValue": [
+                    {"codeID": "AngleFriction", "valueNumeric": 38.0},
+                    {"codeID": "UnconfinedCompressiveStrength", "valueNumeric": 12.5},
+                    {
+                        "codeID": "YoungsModulus",
+                        "valueProfile": [[0.0, 0.2], [10.0, 0.5]],
+                        "valueProfileIndVarCodeID": "Depth"
+                    }
+                ]
+            }]
+        }]));
+        agsi["agsProject"] = serde_json::json!({
+            "projectName": "Units",
+            "agsProjectCodeSet": [{
+                "usedByObject": "agsiDataParameterValue",
+                "usedByAttribute": "codeID",
+                "agsProjectCode": [
+                    {"codeID": "UnconfinedCompressiveStrength", "description": "UCS", "units": "MPa"},
+                    {"codeID": "YoungsModulus", "description": "E", "units": "GPa"}
+                ]
+            }]
+        });
+
+        let ground_model = GroundModel::from_agsi_file(&agsi).unwrap();
+        let mudstone = &ground_model.soil_params[0];
+        assert!((mudstone.phi_prime.unwrap() - 38.0_f64.to_radians()).abs() < 1e-12);
+        assert_eq!(mudstone.ucs, Some(12500.0));
+        assert_eq!(mudstone.youngs_modulus, 200000.0);
+        assert_eq!(
+            mudstone.profiles[0].points,
+            vec![[0.0, 200000.0], [10.0, 500000.0]]
+        );
+
+        agsi["agsProject"]["agsProjectCodeSet"][0]["agsProjectCode"][1]["units"] =
+            serde_json::json!("blows");
+        assert!(matches!(
+            GroundModel::from_agsi_file(&agsi),
+            Err(AgsiError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_export_declares_the_units_it_writes() {
+        let mut agsi = agsi_document(serde_json::json!([{
+            "agsiModelElement": [{
+                "elementName": "Mudstone",
+                "agsiDataParameterValue": [
+                    {"codeID": "UnconfinedCompressiveStrength", "valueNumeric": 12.5},
+                    {"codeID": "PoissonsRatio", "valueNumeric": 25.0},
+                    {"codeID": "Permeability", "valueNumeric": 1.0e-9}
+                ]
+            }]
+        }]));
+        agsi["agsProject"] = serde_json::json!({
+            "projectName": "Units",
+            "agsProjectCodeSet": [{
+                "usedByObject": "agsiDataParameterValue",
+                "usedByAttribute": "codeID",
+                "agsProjectCode": [
+                    {"codeID": "UnconfinedCompressiveStrength", "description": "UCS", "units": "MPa"},
+                    {"codeID": "PoissonsRatio", "description": "nu", "units": "%"},
+                    {"codeID": "Permeability", "description": "k", "units": "m/s"}
+                ]
+            }]
+        });
+
+        let exported = GroundModel::from_agsi_file(&agsi)
+            .unwrap()
+            .to_agsi(AgsiExportOptions::default());
+        let code_set = &exported.ags_project.as_ref().unwrap().ags_project_code_set[0];
+        let unit = |code_id: &str| {
+            code_set
+                .ags_project_code
+                .iter()
+                .find(|code| code.code_id.as_str() == code_id)
+                .and_then(|code| code.units.clone())
+        };
+        assert_eq!(
+            unit("UnconfinedCompressiveStrength").as_deref(),
+            Some("kPa")
+        );
+        assert_eq!(unit("PoissonsRatio").as_deref(), Some("-"));
+        assert_eq!(unit("Permeability").as_deref(), Some("m/s"));
+
+        let text = serde_json::to_string(&exported).unwrap();
+        let reloaded = GroundModel::try_from(&parse_agsi(&text).unwrap()).unwrap();
+        let mudstone = &reloaded.soil_params[0];
+        assert_eq!(mudstone.ucs, Some(12500.0));
+        assert_eq!(mudstone.poissons_ratio, 0.25);
+        let permeability = &mudstone.advanced_parameters.as_ref().unwrap()[0];
+        assert_eq!(permeability.value, 1.0e-9);
+        assert_eq!(permeability.units.as_deref(), Some("m/s"));
+    }
+
+    #[test]
+    fn test_groundwater_values_converted_from_declared_units() {
+        let mut agsi = agsi_document(serde_json::json!([{
+            "agsiModelElement": [
+                {
+                    "elementName": "Clay",
+                    "agsiGeometry": {"topElevation": 10.0, "bottomElevation": -20.0},
+                    "agsiDataParameterValue": [{"codeID": "CoefficientOfConsolidation", "valueNumeric": 0.01}]
+                },
+                {
+                    "elementName": "Perched water",
+                    "elementType": "Groundwater",
+                    "agsiGeometry": {"topElevation": 9.0, "bottomElevation": 7.0},
+                    "agsiDataParameterValue": [
+                        {"codeID": "UnitWeightWater", "valueNumeric": 9810.0},
+                        {"codeID": "PiezometricHead", "valueNumeric": 9500.0}
+                    ]
+                },
+                {
+                    "elementName": "Aquifer",
+                    "elementType": "Groundwater",
+                    "agsiGeometry": {"topElevation": -10.0},
+                    "agsiDataParameterValue": [{
+                        "codeID": "PorePressure",
+                        "valueProfile": [[-10.0, 0.1], [-20.0, 0.2]],
+                        "valueProfileIndVarCodeID": "Elevation"
+                    }]
+                }
+            ]
+        }]));
+        agsi["agsProject"] = serde_json::json!({
+            "projectName": "Units",
+            "agsProjectCodeSet": [{
+                "usedByObject": "agsiDataParameterValue",
+                "usedByAttribute": "codeID",
+                "agsProjectCode": [
+                    {"codeID": "CoefficientOfConsolidation", "description": "cv", "units": "m2/day"},
+                    {"codeID": "UnitWeightWater", "description": "Water", "units": "N/m3"},
+                    {"codeID": "PiezometricHead", "description": "Head", "units": "mm"},
+                    {"codeID": "PorePressure", "description": "u", "units": "MPa"}
+                ]
+            }]
+        });
+
+        let ground_model = GroundModel::from_agsi_file(&agsi).unwrap();
+        let cv = ground_model.soil_params[0].coefficient_of_consolidation;
+        assert!((cv - 0.01 * 365.25).abs() < 1e-12);
+        let regime = ground_model.pore_pressure.as_ref().unwrap();
+        assert_eq!(regime.unit_weight_water, 9.81);
+        assert!((ground_model.get_pwp_at_level(8.0) - 1.5 * 9.81).abs() < 1e-9);
+        assert!((ground_model.get_pwp_at_level(-15.0) - 150.0).abs() < 1e-9);
+    }
+
+    fn export_model() -> GroundModel {
+        let mut clay = SoilParams::new(
+            "London Clay".to_string(),
+            0.0001,
+            40000.0,
+            0.2,
+            2.5,
+            SoilType::Cohesive,
+            20.0,
+        );
+        clay.cu = Some(75.0);
+        clay.pop = Some(150.0);
+        clay.mv_recompression = Some(0.00003);
+        clay.profiles.push(crate::ParameterProfile::new(
+            "UndrainedShearStrength".to_string(),
+            ProfileVariable::Depth,
+            vec![[2.0, 75.0], [20.0, 165.0]],
+        ));
+        clay.advanced_parameters = Some(vec![crate::AdvancedParameter {
+            name: "PlasticityIndex".to_string(),
+            value: 45.0,
+            units: Some("%".to_string()),
+        }]);
+
+        let gravel = SoilParams {
+            reference: "Terrace Gravel".to_string(),
+            unit_weight: 19.0,
+            phi_prime: Some(34.0_f64.to_radians()),
+            ..Default::default()
+        };
+
+        let mut lower_bound = gravel.clone();
+        lower_bound.phi_prime = Some(32.0_f64.to_radians());
+        lower_bound.case_id = Some("Lower bound".to_string());
+
+        let mut model = GroundModel::new(
+            vec![
+                SoilLayer::with_all_fields(
+                    "Terrace Gravel".to_string(),
+                    10.0,
+                    Some(8.0),
+                    None,
+                    "Sandy gravel".to_string(),
+                    "TG".to_string(),
+                ),
+                SoilLayer::with_all_fields(
+                    "London Clay".to_string(),
+                    8.0,
+                    Some(-20.0),
+                    None,
+                    "Stiff clay".to_string(),
+                    "LC".to_string(),
+                ),
+            ],
+            vec![gravel, clay],
+        );
+        model.groundwater = 7.5;
+        model.reference = "DESIGN".to_string();
+        model
+            .cases
+            .insert("Lower bound".to_string(), vec![lower_bound]);
+        model
+    }
+
+    #[test]
+    fn test_to_agsi_round_trip() {
+        let model = export_model();
+        let agsi = model.to_agsi(AgsiExportOptions::default());
+        let text = serde_json::to_string(&agsi).unwrap();
+        let phi_values: Vec<f64> = agsi.agsi_model[0]
+            .agsi_model_element
+            .iter()
+            .flat_map(|element| element.agsi_data_parameter_value.iter())
+            .filter(|value| value.code_id.as_str() == "AngleFriction")
+            .filter_map(|value| value.value_numeric)
+            .collect();
+        assert_eq!(phi_values.len(), 2);
+        assert!(phi_values
+            .iter()
+            .all(|phi| (phi - 34.0).abs() < 1e-9 || (phi - 32.0).abs() < 1e-9));
+
+        let reloaded = GroundModel::try_from(&parse_agsi(&text).unwrap()).unwrap();
+
+        assert_eq!(reloaded.reference, "DESIGN");
+        assert_eq!(reloaded.groundwater, 7.5);
+        assert_eq!(reloaded.soil_layers.len(), 2);
+        assert_eq!(reloaded.soil_layers[1].unit_reference, "London Clay");
+        assert_eq!(reloaded.soil_layers[1].geol_code, "LC");
+        assert_eq!(reloaded.soil_layers[1].typical_description, "Stiff clay");
+        assert_eq!(reloaded.soil_layers[1].base_level, Some(-20.0));
+
+        let clay = reloaded.get_soil_params("London Clay").unwrap();
+        assert_eq!(clay.unit_weight, 20.0);
+        assert_eq!(clay.mv, 0.0001);
+        assert_eq!(clay.youngs_modulus, 40000.0);
+        assert_eq!(clay.poissons_ratio, 0.2);
+        assert_eq!(clay.coefficient_of_consolidation, 2.5);
+        assert_eq!(clay.pop, Some(150.0));
+        assert_eq!(clay.mv_recompression, Some(0.00003));
+        assert_eq!(clay.behaviour, SoilType::Cohesive);
+        assert_eq!(clay.profiles[0].points, vec![[2.0, 75.0], [20.0, 165.0]]);
+        let advanced = clay.advanced_parameters.as_ref().unwrap();
+        assert_eq!(advanced[0].name, "PlasticityIndex");
+        assert_eq!(advanced[0].value, 45.0);
+        assert_eq!(advanced[0].units.as_deref(), Some("%"));
+
+        assert_eq!(reloaded.case_ids(), vec!["Lower bound"]);
+        let lower_bound = reloaded.for_case("Lower bound").unwrap();
+        let gravel = lower_bound.get_soil_params("Terrace Gravel").unwrap();
+        assert!((gravel.phi_prime.unwrap() - 32.0_f64.to_radians()).abs() < 1e-12);
+        assert_eq!(gravel.unit_weight, 19.0);
+    }
+
+    fn schema_errors(agsi: &AgSiV101) -> Vec<String> {
+        let schema: serde_json::Value =
+            serde_json::from_str(include_str!("AGSi_JSONSchema_v1-0-1_2020-12.json")).unwrap();
+        let validator = jsonschema::Validator::new(&schema).unwrap();
+        let instance = serde_json::to_value(agsi).unwrap();
+
+        validator
+            .iter_errors(&instance)
+            .map(|e| format!("{} at {}", e, e.instance_path))
+            .collect()
+    }
+
+    #[test]
+    fn test_to_agsi_validates_against_schema() {
+        let agsi = export_model().to_agsi(AgsiExportOptions {
+            model_name: Some("Geotechnical design model".to_string()),
+            ..Default::default()
+        });
+
+        let errors = schema_errors(&agsi);
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_groundwater_elements_build_regime() {
+        let agsi = agsi_document(serde_json::json!([{
+            "agsiModelElement": [
+                {
+                    "elementName": "Made Ground",
+                    "agsiGeometry": {"topElevation": 10.0, "bottomElevation": 5.0},
+                    "agsiDataParameterValue": [{"codeID": "UnitWeight", "valueNumeric": 18.0}]
+                },
+                {
+                    "elementName": "Perched water",
+                    "elementType": "Groundwater",
+                    "agsiGeometry": {"topElevation": 9.0, "bottomElevation": 7.0},
+                    "agsiDataParameterValue": [{"codeID": "UnitWeightWater", "valueNumeric": 9.81}]
+                },
+                {
+                    "elementName": "Water table",
+                    "elementType": "Groundwater",
+                    "agsiGeometry": {"elevation": 3.0}
+                },
+                {
+                    "elementName": "Chalk aquifer",
+                    "elementType": "Groundwater",
+                    "agsiGeometry": {"topElevation": -10.0},
+                    "agsiDataParameterValue": [{"codeID": "PiezometricHead", "valueNumeric": 6.0}]
+                }
+            ]
+        }]));
+
+        let ground_model = GroundModel::from_agsi_file(&agsi).unwrap();
+
+        // Groundwater elements are not ground units
+        assert_eq!(ground_model.soil_layers.len(), 1);
+        assert_eq!(ground_model.soil_params.len(), 1);
+        assert_eq!(ground_model.groundwater, 3.0);
+
+        let regime = ground_model.pore_pressure.as_ref().unwrap();
+        assert_eq!(regime.unit_weight_water, 9.81);
+        assert_eq!(regime.zones.len(), 3);
+        assert!((ground_model.get_pwp_at_level(8.0) - 9.81).abs() < 1e-9);
+        assert_eq!(ground_model.get_pwp_at_level(5.0), 0.0);
+        assert!((ground_model.get_pwp_at_level(-10.0) - 16.0 * 9.81).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_single_water_table_has_no_regime() {
+        let agsi = agsi_document(serde_json::json!([{
+            "agsiModelElement": [{
+                "elementType": "Groundwater",
+                "agsiGeometry": {"elevation": 4.0}
+            }]
+        }]));
+
+        let ground_model = GroundModel::from_agsi_file(&agsi).unwrap();
+
+        assert_eq!(ground_model.groundwater, 4.0);
+        assert!(ground_model.pore_pressure.is_none());
+    }
+
+    #[test]
+    fn test_to_agsi_round_trips_pore_pressure_regime() {
+        let mut model = export_model();
+        let regime = PorePressureRegime {
+            unit_weight_water: 9.81,
+            zones: vec![
+                PiezometricZone::new(Some(10.0), Some(8.0), PiezometricHead::Level(10.0)),
+                PiezometricZone::new(
+                    Some(0.0),
+                    Some(-20.0),
+                    PiezometricHead::Profile(vec![[-20.0, 150.0], [0.0, 0.0]]),
+                ),
+                PiezometricZone::new(Some(-20.0), None, PiezometricHead::Level(4.0)),
+                PiezometricZone::new(None, None, PiezometricHead::Level(7.5)),
+            ],
+        };
+        model.pore_pressure = Some(regime.clone());
+
+        let agsi = model.to_agsi(AgsiExportOptions::default());
+        let errors = schema_errors(&agsi);
+        assert!(errors.is_empty(), "{:?}", errors);
+
+        let text = serde_json::to_string(&agsi).unwrap();
+        let reloaded = GroundModel::try_from(&parse_agsi(&text).unwrap()).unwrap();
+        assert_eq!(reloaded.groundwater, 7.5);
+        assert_eq!(reloaded.pore_pressure, Some(regime));
+    }
+
+    #[test]
+    fn test_observation_column_with_depths_and_elevations() {
+        let agsi = agsi_document(serde_json::json!([{
+            "agsiObservationSet": [{
+                "agsiObservationExpHole": [{
+                    "holeID": "BH1",
+                    "topCoordinate": [0.0, 0.0, 10.0],
+                    "verticalHoleDepth": 5.0,
+                    "agsiObservationColumn": [{
+                        "topDepth": 0.0,
+                        "bottomDepth": 5.0,
+                        "topElevation": 10.0,
+                        "bottomElevation": 5.0,
+                        "geologyCode": "MG"
+                    }]
+                }]
+            }]
+        }]));
+
+        let agsi = agsi_from_value(&agsi).unwrap();
+        let hole = &agsi.agsi_model[0].agsi_observation_set[0].agsi_observation_exp_hole[0];
+        let column = &hole.agsi_observation_column[0];
+        assert_eq!(hole.hole_id, "BH1");
+        assert_eq!(column.top_depth(), Some(0.0));
+        assert_eq!(column.top_elevation(), Some(10.0));
+        assert_eq!(column.bottom_elevation(), Some(5.0));
+        assert_eq!(column.geology_code(), Some("MG"));
+    }
+}
@@ -0,0 +1,625 @@
+use crate::{DrainageCondition, GroundModel, GroundModelError, PartialFactors, SoilParams};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+/// Distance below a level used to pick the unit beneath a layer boundary (m).
+const LEVEL_TOLERANCE: f64 = 1e-6;
+
+/// Friction angle (radians) below which drained resistance uses the cohesive (φ = 0) factors.
+const MIN_FRICTION_ANGLE: f64 = 1e-6;
+
+/// A pad or strip footing. Dimensions are in metres and angles in radians.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShallowFoundation {
+    pub width: f64,
+    /// Length of a pad, or `None` for a strip. Strip results are per metre run.
+    pub length: Option<f64>,
+    /// Level of the underside of the foundation.
+    pub level: f64,
+    /// Inclination of the foundation base to the horizontal.
+    #[serde(default)]
+    pub base_inclination: f64,
+}
+
+impl ShallowFoundation {
+    pub fn strip(width: f64, level: f64) -> Self {
+        ShallowFoundation {
+            width,
+            length: None,
+            level,
+            base_inclination: 0.0,
+        }
+    }
+
+    pub fn pad(width: f64, length: f64, level: f64) -> Self {
+        ShallowFoundation {
+            width,
+            length: Some(length),
+            level,
+            base_inclination: 0.0,
+        }
+    }
+
+    pub fn with_base_inclination(mut self, base_inclination: f64) -> Self {
+        self.base_inclination = base_inclination;
+        self
+    }
+}
+
+/// Design actions on a [`ShallowFoundation`] (kN, or kN/m for strips).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FoundationAction {
+    pub vertical: f64,
+    #[serde(default)]
+    pub horizontal: f64,
+    /// Angle between the horizontal load and the length of the foundation (radians). `π/2`
+    /// when the load acts across the width, as it always does on a strip.
+    #[serde(default = "across_width")]
+    pub horizontal_angle: f64,
+    /// Eccentricity of the vertical load across the width (m).
+    #[serde(default)]
+    pub eccentricity_width: f64,
+    /// Eccentricity of the vertical load along the length (m).
+    #[serde(default)]
+    pub eccentricity_length: f64,
+}
+
+fn across_width() -> f64 {
+    FRAC_PI_2
+}
+
+impl FoundationAction {
+    pub fn vertical(vertical: f64) -> Self {
+        FoundationAction {
+            vertical,
+            horizontal: 0.0,
+            horizontal_angle: FRAC_PI_2,
+            eccentricity_width: 0.0,
+            eccentricity_length: 0.0,
+        }
+    }
+
+    pub fn with_horizontal(mut self, horizontal: f64, angle: f64) -> Self {
+        self.horizontal = horizontal;
+        self.horizontal_angle = angle;
+        self
+    }
+
+    pub fn with_eccentricity(mut self, width: f64, length: f64) -> Self {
+        self.eccentricity_width = width;
+        self.eccentricity_length = length;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BearingOptions {
+    /// Material factors applied with `SoilParams::apply_partial_factors`, e.g. DA1-C2.
+    /// Parameters that are already factored are used as they are.
+    pub partial_factors: Option<PartialFactors>,
+    /// Resistance factor γR;v dividing the bearing resistance.
+    pub resistance_factor: f64,
+    /// Include Brinch Hansen depth factors, which EC7 Annex D omits.
+    pub depth_factors: bool,
+    /// Depth below the foundation, in multiples of the effective width, within which deeper
+    /// layers are checked by spreading the load at 2V:1H.
+    pub influence_depth: f64,
+}
+
+impl Default for BearingOptions {
+    fn default() -> Self {
+        BearingOptions {
+            partial_factors: None,
+            resistance_factor: 1.0,
+            depth_factors: false,
+            influence_depth: 2.0,
+        }
+    }
+}
+
+/// Bearing capacity factors and the base inclination (b), shape (s), inclination (i) and
+/// depth (d) factors for the cohesion (c), overburden (q) and self-weight (γ) terms.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BearingFactors {
+    pub nc: f64,
+    pub nq: f64,
+    pub n_gamma: f64,
+    pub bc: f64,
+    pub bq: f64,
+    pub b_gamma: f64,
+    pub sc: f64,
+    pub sq: f64,
+    pub s_gamma: f64,
+    pub ic: f64,
+    pub iq: f64,
+    pub i_gamma: f64,
+    pub dc: f64,
+    pub dq: f64,
+    pub d_gamma: f64,
+}
+
+/// Bearing resistance checked at one level: the founding level, or the top of a deeper layer
+/// loaded through a spread foundation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BearingCheck {
+    /// Unit providing the resistance.
+    pub reference: String,
+    pub level: f64,
+    /// Effective width B′ at this level (m).
+    pub effective_width: f64,
+    /// Effective length L′ at this level, `None` for strips (m).
+    pub effective_length: Option<f64>,
+    /// Overburden at this level: effective for drained checks, total for undrained (kPa).
+    pub overburden: f64,
+    /// Effective unit weight in the self-weight term (kN/m³).
+    pub unit_weight: f64,
+    pub factors: BearingFactors,
+    /// Ultimate bearing pressure at this level (kPa).
+    pub ultimate_pressure: f64,
+    /// Bearing resistance of the foundation, less the weight of any spread soil (kN or kN/m).
+    pub resistance: f64,
+    /// `resistance / resistance_factor`.
+    pub design_resistance: f64,
+}
+
+/// Bearing resistance of a shallow foundation on a ground model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BearingCapacity {
+    pub condition: DrainageCondition,
+    /// The founding level first, then any deeper layers within the influence zone.
+    pub checks: Vec<BearingCheck>,
+}
+
+impl BearingCapacity {
+    /// The check with the lowest design resistance.
+    pub fn governing(&self) -> &BearingCheck {
+        self.checks
+            .iter()
+            .min_by(|a, b| a.design_resistance.total_cmp(&b.design_resistance))
+            .expect("bearing capacity always checks the founding level")
+    }
+
+    pub fn design_resistance(&self) -> f64 {
+        self.governing().design_resistance
+    }
+
+    /// Design vertical action over design resistance.
+    pub fn utilisation(&self, action: &FoundationAction) -> f64 {
+        action.vertical / self.design_resistance()
+    }
+}
+
+/// Plan dimensions of a (possibly spread) foundation, with B′ ≤ L′ and the horizontal load
+/// angle measured from L′.
+struct EffectiveFoundation {
+    width: f64,
+    length: Option<f64>,
+    horizontal_angle: f64,
+}
+
+impl EffectiveFoundation {
+    fn area(&self) -> f64 {
+        self.width * self.length.unwrap_or(1.0)
+    }
+
+    /// B′/L′, zero for strips.
+    fn aspect(&self) -> f64 {
+        self.length.map_or(0.0, |length| self.width / length)
+    }
+
+    /// Exponent m of the inclination factors.
+    fn inclination_exponent(&self) -> f64 {
+        let ratio = self.aspect();
+        let m_width = (2.0 + ratio) / (1.0 + ratio);
+        let m_length = (2.0 * ratio + 1.0) / (ratio + 1.0);
+        m_length * self.horizontal_angle.cos().powi(2)
+            + m_width * self.horizontal_angle.sin().powi(2)
+    }
+
+    fn spread(&self, depth: f64) -> Self {
+        EffectiveFoundation {
+            width: self.width + depth,
+            length: self.length.map(|length| length + depth),
+            horizontal_angle: self.horizontal_angle,
+        }
+    }
+}
+
+/// Loads and geometry at the base of a (possibly spread) foundation.
+struct BaseLoading {
+    vertical: f64,
+    horizontal: f64,
+    /// Base inclination α (radians).
+    alpha: f64,
+    /// Depth factor parameter k, or `None` to leave out depth factors.
+    depth_ratio: Option<f64>,
+}
+
+impl GroundModel {
+    /// Bearing resistance of a shallow foundation to EC7 Annex D. The founding level is
+    /// checked with the parameters of the unit below it, and the top of every deeper layer
+    /// within `options.influence_depth` is checked as a foundation spread at 2V:1H carrying
+    /// the weight of the spread soil. The lowest design resistance governs.
+    pub fn bearing_resistance(
+        &self,
+        foundation: &ShallowFoundation,
+        action: &FoundationAction,
+        condition: DrainageCondition,
+        options: &BearingOptions,
+    ) -> Result<BearingCapacity, GroundModelError> {
+        let model = match &options.partial_factors {
+            Some(pf) => self.apply_partial_factors(pf),
+            None => self.clone(),
+        };
+
+        let base = effective_foundation(foundation, action)?;
+        let ground_level = model.get_top_level();
+        let overburden = |level: f64| {
+            let point = model.stress_profile(&[level])[0];
+            match condition {
+                DrainageCondition::Drained => point.effective,
+                DrainageCondition::Undrained => point.total,
+            }
+        };
+
+        let mut levels = vec![foundation.level];
+        let zone_base = foundation.level - options.influence_depth * base.width;
+        let mut deeper: Vec<f64> = model
+            .soil_layers
+            .iter()
+            .map(|layer| layer.top_level)
+            .filter(|&top| top < foundation.level - LEVEL_TOLERANCE && top > zone_base)
+            .collect();
+        deeper.sort_by(|a, b| b.total_cmp(a));
+        deeper.dedup();
+        levels.extend(deeper);
+
+        let mut checks = Vec::new();
+        for level in levels {
+            let depth_below_base = foundation.level - level;
+            let spread = base.spread(depth_below_base);
+            let params = model.get_params_at_level(level - LEVEL_TOLERANCE)?;
+
+            // Weight of the spread soil between the foundation and this level
+            let block_weight = (overburden(level) - overburden(foundation.level)) * spread.area();
+            let vertical = action.vertical + block_weight;
+            let alpha = if depth_below_base > 0.0 {
+                0.0
+            } else {
+                foundation.base_inclination
+            };
+
+            let q = overburden(level);
+            let gamma = {
+                let below = model.stress_profile(&[level, level - spread.width]);
+                (below[1].effective - below[0].effective) / spread.width
+            };
+            let embedment = (ground_level - level).max(0.0);
+            let depth_ratio = options.depth_factors.then(|| {
+                let ratio = embedment / spread.width;
+                if ratio <= 1.0 {
+                    ratio
+                } else {
+                    ratio.atan()
+                }
+            });
+
+            let loading = BaseLoading {
+                vertical,
+                horizontal: action.horizontal,
+                alpha,
+                depth_ratio,
+            };
+            let (factors, ultimate_pressure) = match condition {
+                DrainageCondition::Undrained => {
+                    let cu = params.required(params.cu, "cu")?;
+                    let factors = cohesive_factors(&params, cu, &spread, &loading)?;
+                    let pressure =
+                        factors.nc * cu * factors.bc * factors.sc * factors.ic * factors.dc + q;
+                    (factors, pressure)
+                }
+                DrainageCondition::Drained => {
+                    let phi = params.required(params.phi_prime, "phi_prime")?;
+                    let c = params.c_prime.unwrap_or(0.0);
+                    if phi < MIN_FRICTION_ANGLE {
+                        let factors = cohesive_factors(&params, c, &spread, &loading)?;
+                        let pressure =
+                            factors.nc * c * factors.bc * factors.sc * factors.ic * factors.dc + q;
+                        (factors, pressure)
+                    } else {
+                        let factors = drained_factors(&params, phi, c, &spread, &loading)?;
+                        let pressure = c
+                            * factors.nc
+                            * factors.bc
+                            * factors.sc
+                            * factors.ic
+                            * factors.dc
+                            + q * factors.nq * factors.bq * factors.sq * factors.iq * factors.dq
+                            + 0.5
+                                * gamma
+                                * spread.width
+                                * factors.n_gamma
+                                * factors.b_gamma
+                                * factors.s_gamma
+                                * factors.i_gamma
+                                * factors.d_gamma;
+                        (factors, pressure)
+                    }
+                }
+            };
+
+            let resistance = ultimate_pressure * spread.area() - block_weight;
+            checks.push(BearingCheck {
+                reference: params.reference.clone(),
+                level,
+                effective_width: spread.width,
+                effective_length: spread.length,
+                overburden: q,
+                unit_weight: gamma,
+                factors,
+                ultimate_pressure,
+                resistance,
+                design_resistance: resistance / options.resistance_factor,
+            });
+        }
+
+        Ok(BearingCapacity { condition, checks })
+    }
+}
+
+fn effective_foundation(
+    foundation: &ShallowFoundation,
+    action: &FoundationAction,
+) -> Result<EffectiveFoundation, GroundModelError> {
+    let width = foundation.width - 2.0 * action.eccentricity_width.abs();
+    let length = foundation
+        .length
+        .map(|length| length - 2.0 * action.eccentricity_length.abs());
+    if width <= 0.0 || length.is_some_and(|length| length <= 0.0) {
+        return Err(GroundModelError::InvalidGeometry {
+            reference: "foundation".to_string(),
+            message: "load eccentricity leaves no effective area".to_string(),
+        });
+    }
+
+    Ok(match length {
+        Some(length) if length < width => EffectiveFoundation {
+            width: length,
+            length: Some(width),
+            horizontal_angle: FRAC_PI_2 - action.horizontal_angle,
+        },
+        Some(length) => EffectiveFoundation {
+            width,
+            length: Some(length),
+            horizontal_angle: action.horizontal_angle,
+        },
+        None => EffectiveFoundation {
+            width,
+            length: None,
+            horizontal_angle: FRAC_PI_2,
+        },
+    })
+}
+
+/// Factors for undrained (`strength` = cu) or purely cohesive drained resistance, EC7 D.3.
+fn cohesive_factors(
+    params: &SoilParams,
+    strength: f64,
+    foundation: &EffectiveFoundation,
+    loading: &BaseLoading,
+) -> Result<BearingFactors, GroundModelError> {
+    let sliding = loading.horizontal.abs() / (foundation.area() * strength);
+    if sliding > 1.0 {
+        return Err(GroundModelError::Calculation {
+            reference: params.reference.clone(),
+            message: "horizontal load exceeds the base shear resistance".to_string(),
+        });
+    }
+
+    Ok(BearingFactors {
+        nc: PI + 2.0,
+        nq: 1.0,
+        n_gamma: 0.0,
+        bc: 1.0 - 2.0 * loading.alpha / (PI + 2.0),
+        bq: 1.0,
+        b_gamma: 1.0,
+        sc: 1.0 + 0.2 * foundation.aspect(),
+        sq: 1.0,
+        s_gamma: 1.0,
+        ic: 0.5 * (1.0 + (1.0 - sliding).sqrt()),
+        iq: 1.0,
+        i_gamma: 1.0,
+        dc: loading.depth_ratio.map_or(1.0, |k| 1.0 + 0.4 * k),
+        dq: 1.0,
+        d_gamma: 1.0,
+    })
+}
+
+/// Factors for drained resistance with φ′ > 0, EC7 D.4.
+fn drained_factors(
+    params: &SoilParams,
+    phi: f64,
+    c: f64,
+    foundation: &EffectiveFoundation,
+    loading: &BaseLoading,
+) -> Result<BearingFactors, GroundModelError> {
+    let tan_phi = phi.tan();
+    let nq = (PI * tan_phi).exp() * (FRAC_PI_4 + phi / 2.0).tan().powi(2);
+    let nc = (nq - 1.0) / tan_phi;
+    let n_gamma = 2.0 * (nq - 1.0) * tan_phi;
+
+    let bq = (1.0 - loading.alpha * tan_phi).powi(2);
+    let bc = bq - (1.0 - bq) / (nc * tan_phi);
+
+    let ratio = foundation.aspect();
+    let sq = 1.0 + ratio * phi.sin();
+    let s_gamma = 1.0 - 0.3 * ratio;
+    let sc = (sq * nq - 1.0) / (nq - 1.0);
+
+    let m = foundation.inclination_exponent();
+    let load_ratio =
+        1.0 - loading.horizontal.abs() / (loading.vertical + foundation.area() * c / tan_phi);
+    if load_ratio <= 0.0 {
+        return Err(GroundModelError::Calculation {
+            reference: params.reference.clone(),
+            message: "horizontal load exceeds the base shear resistance".to_string(),
+        });
+    }
+    let iq = load_ratio.powf(m);
+    let i_gamma = load_ratio.powf(m + 1.0);
+    let ic = iq - (1.0 - iq) / (nc * tan_phi);
+
+    let dq = loading
+        .depth_ratio
+        .map_or(1.0, |k| 1.0 + 2.0 * tan_phi * (1.0 - phi.sin()).powi(2) * k);
+    let dc = dq - (1.0 - dq) / (nc * tan_phi);
+
+    Ok(BearingFactors {
+        nc,
+        nq,
+        n_gamma,
+        bc,
+        bq,
+        b_gamma: bq,
+        sc,
+        sq,
+        s_gamma,
+        ic,
+        iq,
+        i_gamma,
+        dc,
+        dq,
+        d_gamma: 1.0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::soil;
+    use crate::{SoilLayer, SoilType};
+
+    #[test]
+    fn undrained_strip_on_surface() {
+        let mut clay = soil("Clay", SoilType::Cohesive, 18.0);
+        clay.cu = Some(50.0);
+        let model = GroundModel::quick_init(clay, 0.0, -50.0);
+
+        let result = model
+            .bearing_resistance(
+                &ShallowFoundation::strip(2.0, 0.0),
+                &FoundationAction::vertical(200.0),
+                DrainageCondition::Undrained,
+                &BearingOptions::default(),
+            )
+            .unwrap();
+
+        assert_eq!(result.checks.len(), 1);
+        let check = result.governing();
+        assert!((check.ultimate_pressure - (PI + 2.0) * 50.0).abs() < 1e-9);
+        assert!((result.design_resistance() - (PI + 2.0) * 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn drained_pad_matches_annex_d() {
+        let mut sand = soil("Sand", SoilType::Granular, 18.0);
+        sand.phi_prime = Some(30.0_f64.to_radians());
+        let model = GroundModel::quick_init(sand, 0.0, -50.0);
+
+        let result = model
+            .bearing_resistance(
+                &ShallowFoundation::pad(2.0, 4.0, -1.0),
+                &FoundationAction::vertical(1000.0),
+                DrainageCondition::Drained,
+                &BearingOptions::default(),
+            )
+            .unwrap();
+
+        let factors = result.governing().factors;
+        assert!((factors.nq - 18.401).abs() < 1e-3);
+        assert!((factors.n_gamma - 20.093).abs() < 1e-3);
+        assert!((factors.sq - 1.25).abs() < 1e-12);
+        assert!((factors.s_gamma - 0.85).abs() < 1e-12);
+
+        let expected = 18.0 * factors.nq * 1.25 + 0.5 * 18.0 * 2.0 * factors.n_gamma * 0.85;
+        assert!((result.governing().ultimate_pressure - expected).abs() < 1e-9);
+        assert!((result.design_resistance() - expected * 8.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn partial_factors_and_inclined_load_reduce_resistance() {
+        let mut sand = soil("Sand", SoilType::Granular, 18.0);
+        sand.phi_prime = Some(35.0_f64.to_radians());
+        let model = GroundModel::quick_init(sand, 0.0, -50.0);
+        let foundation = ShallowFoundation::strip(2.0, -1.0);
+        let action = FoundationAction::vertical(500.0);
+
+        let characteristic = model
+            .bearing_resistance(
+                &foundation,
+                &action,
+                DrainageCondition::Drained,
+                &BearingOptions::default(),
+            )
+            .unwrap()
+            .design_resistance();
+        let factored = model
+            .bearing_resistance(
+                &foundation,
+                &action,
+                DrainageCondition::Drained,
+                &BearingOptions {
+                    partial_factors: Some(PartialFactors::new(1.25, 1.25, 1.0, 1.4)),
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .design_resistance();
+        let inclined = model
+            .bearing_resistance(
+                &foundation,
+                &action.clone().with_horizontal(100.0, FRAC_PI_2),
+                DrainageCondition::Drained,
+                &BearingOptions::default(),
+            )
+            .unwrap();
+
+        assert!(factored < characteristic);
+        // Strip: m = 2 for a load across the width
+        let iq = inclined.governing().factors.iq;
+        assert!((iq - (1.0 - 100.0 / 500.0_f64).powi(2)).abs() < 1e-12);
+        assert!(inclined.design_resistance() < characteristic);
+    }
+
+    #[test]
+    fn weak_layer_within_influence_zone_governs() {
+        let mut sand = soil("Sand", SoilType::Granular, 18.0);
+        sand.phi_prime = Some(38.0_f64.to_radians());
+        let mut clay = soil("Clay", SoilType::Cohesive, 18.0);
+        clay.phi_prime = Some(20.0_f64.to_radians());
+        clay.c_prime = Some(2.0);
+        let model = GroundModel::new(
+            vec![
+                SoilLayer::with_unit_reference(0.0, -2.0, String::new(), "Sand".to_string()),
+                SoilLayer::with_unit_reference(-2.0, -30.0, String::new(), "Clay".to_string()),
+            ],
+            vec![sand, clay],
+        );
+
+        let result = model
+            .bearing_resistance(
+                &ShallowFoundation::pad(2.0, 2.0, -1.0),
+                &FoundationAction::vertical(800.0),
+                DrainageCondition::Drained,
+                &BearingOptions::default(),
+            )
+            .unwrap();
+
+        assert_eq!(result.checks.len(), 2);
+        let check = result.governing();
+        assert_eq!(check.reference, "Clay");
+        assert_eq!(check.level, -2.0);
+        assert_eq!(check.effective_width, 3.0);
+        assert!(check.design_resistance < result.checks[0].design_resistance);
+    }
+}
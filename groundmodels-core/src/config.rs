@@ -0,0 +1,43 @@
+//! Reading settings such as design approaches, validation rule sets and construction
+//! sequences. Each is JSON, or TOML when read from a file with a `.toml` extension.
+
+use crate::GroundModelError;
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::path::Path;
+
+/// A [`GroundModelError::InvalidConfig`] for settings of `kind` read from `name`.
+pub(crate) fn invalid(kind: &'static str, name: &str, message: String) -> GroundModelError {
+    GroundModelError::InvalidConfig {
+        kind,
+        name: name.to_string(),
+        message,
+    }
+}
+
+pub(crate) fn from_json<T: DeserializeOwned>(
+    kind: &'static str,
+    text: &str,
+) -> Result<T, GroundModelError> {
+    serde_json::from_str(text).map_err(|err| invalid(kind, "JSON", err.to_string()))
+}
+
+pub(crate) fn from_toml<T: DeserializeOwned>(
+    kind: &'static str,
+    text: &str,
+) -> Result<T, GroundModelError> {
+    toml::from_str(text).map_err(|err| invalid(kind, "TOML", err.to_string()))
+}
+
+/// Reads `path` as TOML when it has a `.toml` extension, or JSON otherwise.
+pub(crate) fn load<T: DeserializeOwned>(
+    kind: &'static str,
+    path: &Path,
+) -> Result<T, GroundModelError> {
+    let name = path.display().to_string();
+    let text = fs::read_to_string(path).map_err(|err| invalid(kind, &name, err.to_string()))?;
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("toml") => from_toml(kind, &text),
+        _ => from_json(kind, &text),
+    }
+}
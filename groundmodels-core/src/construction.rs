@@ -0,0 +1,448 @@
+//! Staged excavation, dewatering, filling and surcharging.
+//!
+//! A [`ConstructionSequence`] applies its stages to a ground model in order and records the
+//! model and the vertical stresses after each stage. The largest effective stress reached at
+//! each level is carried from stage to stage, so every stage shows whether the ground is on
+//! its virgin loading line or unloading and reloading below it. The history starts from each
+//! unit's preconsolidation pressure (see [`crate::stress_history`]), or from the current
+//! effective stress where the unit is normally consolidated.
+
+use crate::config;
+use crate::strip_log::csv_quote;
+use crate::{GroundModel, GroundModelError, SoilParams, StressPoint};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+const KIND: &str = "construction sequence";
+
+/// Changes in effective stress smaller than this (kPa) do not change the stress path.
+pub const STRESS_TOLERANCE: f64 = 1e-6;
+
+/// One change to the ground model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+pub enum ConstructionOperation {
+    /// Removes the ground above `level` and any surcharge ([`GroundModel::excavate`]).
+    Excavate { level: f64 },
+    /// Sets a hydrostatic water table at `level` ([`GroundModel::dewater`]).
+    Dewater { level: f64 },
+    /// Places fill up to `level` ([`GroundModel::place_fill`]).
+    Fill { level: f64, params: Box<SoilParams> },
+    /// Sets the uniform surcharge on the ground surface (kPa).
+    Surcharge { pressure: f64 },
+}
+
+impl ConstructionOperation {
+    pub fn apply(&self, model: &GroundModel) -> Result<GroundModel, GroundModelError> {
+        match self {
+            ConstructionOperation::Excavate { level } => Ok(model.excavate(*level)),
+            ConstructionOperation::Dewater { level } => Ok(model.dewater(*level)),
+            ConstructionOperation::Fill { level, params } => model.place_fill(*level, params),
+            ConstructionOperation::Surcharge { pressure } => {
+                let mut surcharged = model.clone();
+                surcharged.surcharge = *pressure;
+                surcharged.keep_stress_history(model);
+                Ok(surcharged)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstructionStage {
+    pub name: String,
+    #[serde(flatten)]
+    pub operation: ConstructionOperation,
+}
+
+impl ConstructionStage {
+    pub fn new(name: &str, operation: ConstructionOperation) -> Self {
+        ConstructionStage {
+            name: name.to_string(),
+            operation,
+        }
+    }
+}
+
+/// Where the effective stress at a level lies relative to its stress history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StressPath {
+    /// At the largest effective stress reached so far.
+    Loading,
+    /// Below the largest effective stress reached and lower than at the previous stage.
+    Unloading,
+    /// Below the largest effective stress reached and higher than at the previous stage.
+    Reloading,
+}
+
+impl fmt::Display for StressPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StressPath::Loading => write!(f, "loading"),
+            StressPath::Unloading => write!(f, "unloading"),
+            StressPath::Reloading => write!(f, "reloading"),
+        }
+    }
+}
+
+/// Vertical stresses at one level after a stage, with the stress history up to that stage.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StageStress {
+    #[serde(flatten)]
+    pub stress: StressPoint,
+    /// Largest vertical effective stress reached at this level so far (kPa).
+    pub max_past_effective: f64,
+    pub path: StressPath,
+}
+
+impl StageStress {
+    /// Ratio of the largest past effective stress to the current one, or `None` when the
+    /// current effective stress is not positive.
+    pub fn ocr(&self) -> Option<f64> {
+        (self.stress.effective > 0.0).then(|| self.max_past_effective / self.stress.effective)
+    }
+}
+
+/// The model and stresses after one stage. `stresses` follows the levels passed to
+/// [`ConstructionSequence::run`], with `None` where a level is above the ground surface.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StageResult {
+    pub name: String,
+    pub model: GroundModel,
+    pub stresses: Vec<Option<StageStress>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstructionHistory {
+    pub levels: Vec<f64>,
+    /// The initial model followed by one result per stage.
+    pub stages: Vec<StageResult>,
+}
+
+impl ConstructionHistory {
+    /// Stresses at `levels[index]` through the stages.
+    pub fn history(&self, index: usize) -> Vec<Option<&StageStress>> {
+        self.stages
+            .iter()
+            .map(|stage| stage.stresses.get(index).and_then(Option::as_ref))
+            .collect()
+    }
+
+    /// The model after the last stage.
+    pub fn final_model(&self) -> Option<&GroundModel> {
+        self.stages.last().map(|stage| &stage.model)
+    }
+
+    /// One row per stage and level, skipping levels above the ground surface.
+    pub fn to_csv(&self) -> String {
+        let mut lines = vec![
+            "stage,level,total_kpa,pore_pressure_kpa,effective_kpa,max_past_effective_kpa,path"
+                .to_string(),
+        ];
+        for stage in &self.stages {
+            for (level, stress) in self.levels.iter().zip(&stage.stresses) {
+                let Some(stress) = stress else {
+                    continue;
+                };
+                lines.push(
+                    [
+                        csv_quote(&stage.name),
+                        level.to_string(),
+                        stress.stress.total.to_string(),
+                        stress.stress.pore_pressure.to_string(),
+                        stress.stress.effective.to_string(),
+                        stress.max_past_effective.to_string(),
+                        stress.path.to_string(),
+                    ]
+                    .join(","),
+                );
+            }
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConstructionSequence {
+    pub stages: Vec<ConstructionStage>,
+}
+
+impl ConstructionSequence {
+    pub fn new(stages: Vec<ConstructionStage>) -> Self {
+        ConstructionSequence { stages }
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, GroundModelError> {
+        config::from_json(KIND, text)
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, GroundModelError> {
+        config::from_toml(KIND, text)
+    }
+
+    /// Reads a sequence from a `.toml` file, or JSON otherwise.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, GroundModelError> {
+        config::load(KIND, path.as_ref())
+    }
+
+    /// Applies the stages to `model` and evaluates the stresses at `levels` after each. A
+    /// level that is excavated and later filled again starts a new stress history.
+    pub fn run(
+        &self,
+        model: &GroundModel,
+        levels: &[f64],
+    ) -> Result<ConstructionHistory, GroundModelError> {
+        let mut previous: Vec<Option<StageStress>> = vec![None; levels.len()];
+        let mut stages = Vec::with_capacity(self.stages.len() + 1);
+        let mut model = model.clone();
+
+        for (index, stage) in std::iter::once(None)
+            .chain(self.stages.iter().map(Some))
+            .enumerate()
+        {
+            let name = match stage {
+                Some(stage) => {
+                    model = stage.operation.apply(&model)?;
+                    stage.name.clone()
+                }
+                None => "Initial".to_string(),
+            };
+            if index > 0 && model.soil_layers.is_empty() {
+                return Err(config::invalid(
+                    KIND,
+                    &name,
+                    "no ground is left".to_string(),
+                ));
+            }
+
+            let surface = model.get_top_level();
+            let stresses: Vec<Option<StageStress>> = model
+                .stress_profile(levels)
+                .into_iter()
+                .zip(&previous)
+                .map(|(stress, previous)| {
+                    (stress.level <= surface)
+                        .then(|| next_stage_stress(&model, stress, previous.as_ref()))
+                })
+                .collect();
+
+            previous.clone_from(&stresses);
+            stages.push(StageResult {
+                name,
+                model: model.clone(),
+                stresses,
+            });
+        }
+
+        Ok(ConstructionHistory {
+            levels: levels.to_vec(),
+            stages,
+        })
+    }
+}
+
+fn next_stage_stress(
+    model: &GroundModel,
+    stress: StressPoint,
+    previous: Option<&StageStress>,
+) -> StageStress {
+    let Some(previous) = previous else {
+        let max_past_effective = model
+            .get_params_at_level(stress.level)
+            .ok()
+            .and_then(|params| params.preconsolidation_at(stress.effective))
+            .unwrap_or(stress.effective);
+        let path = if max_past_effective > stress.effective + STRESS_TOLERANCE {
+            StressPath::Unloading
+        } else {
+            StressPath::Loading
+        };
+        return StageStress {
+            stress,
+            max_past_effective,
+            path,
+        };
+    };
+
+    let effective = stress.effective;
+    let path = if effective >= previous.max_past_effective - STRESS_TOLERANCE {
+        StressPath::Loading
+    } else if effective < previous.stress.effective - STRESS_TOLERANCE {
+        StressPath::Unloading
+    } else if effective > previous.stress.effective + STRESS_TOLERANCE {
+        StressPath::Reloading
+    } else {
+        previous.path
+    };
+    StageStress {
+        stress,
+        max_past_effective: previous.max_past_effective.max(effective),
+        path,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::soil;
+    use crate::{SoilLayer, SoilType};
+
+    fn clay_model() -> GroundModel {
+        GroundModel::new(
+            vec![SoilLayer::with_unit_reference(
+                10.0,
+                -20.0,
+                String::new(),
+                "Clay".to_string(),
+            )],
+            vec![soil("Clay", SoilType::Cohesive, 20.0)],
+        )
+    }
+
+    #[test]
+    fn stages_track_unloading_and_reloading() {
+        let sequence = ConstructionSequence::new(vec![
+            ConstructionStage::new("Excavate", ConstructionOperation::Excavate { level: 4.0 }),
+            ConstructionStage::new("Dewater", ConstructionOperation::Dewater { level: -3.0 }),
+            ConstructionStage::new(
+                "Fill",
+                ConstructionOperation::Fill {
+                    level: 6.0,
+                    params: Box::new(soil("Fill", SoilType::Cohesive, 18.0)),
+                },
+            ),
+            ConstructionStage::new(
+                "Surcharge, 100 kPa",
+                ConstructionOperation::Surcharge { pressure: 100.0 },
+            ),
+        ]);
+        let history = sequence.run(&clay_model(), &[8.0, 5.0, 2.0, -5.0]).unwrap();
+
+        assert_eq!(history.stages.len(), 5);
+        assert_eq!(history.stages[0].name, "Initial");
+        assert_eq!(history.final_model().unwrap().get_top_level(), 6.0);
+
+        let effective = |index: usize| -> Vec<Option<(f64, StressPath)>> {
+            history
+                .history(index)
+                .into_iter()
+                .map(|stress| stress.map(|s| (s.stress.effective, s.path)))
+                .collect()
+        };
+        use StressPath::*;
+        assert_eq!(
+            effective(0),
+            [Some((40.0, Loading)), None, None, None, None]
+        );
+        // Excavated then filled: the fill starts a new history
+        assert_eq!(
+            effective(1),
+            [
+                Some((100.0, Loading)),
+                None,
+                None,
+                Some((18.0, Loading)),
+                Some((118.0, Loading)),
+            ]
+        );
+        assert_eq!(
+            effective(2),
+            [
+                Some((160.0, Loading)),
+                Some((40.0, Unloading)),
+                Some((40.0, Unloading)),
+                Some((76.0, Reloading)),
+                Some((176.0, Loading)),
+            ]
+        );
+        assert_eq!(
+            effective(3),
+            [
+                Some((250.0, Loading)),
+                Some((130.0, Unloading)),
+                Some((160.0, Reloading)),
+                Some((196.0, Reloading)),
+                Some((296.0, Loading)),
+            ]
+        );
+
+        let reloaded = history.history(2)[3].unwrap();
+        assert_eq!(reloaded.max_past_effective, 160.0);
+        assert!((reloaded.ocr().unwrap() - 160.0 / 76.0).abs() < 1e-12);
+
+        let csv = history.to_csv();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert!(rows[0].ends_with(",path"));
+        // Levels above the ground surface are skipped
+        assert_eq!(rows.len(), 1 + 1 + 3 + 5 + 5);
+        assert_eq!(rows[1], "\"Initial\",8,40,0,40,40,loading");
+        assert_eq!(
+            rows.last().unwrap(),
+            &"\"Surcharge, 100 kPa\",-5,316,20,296,296,loading"
+        );
+    }
+
+    #[test]
+    fn history_starts_from_preconsolidation_pressure() {
+        let mut model = clay_model();
+        model.soil_params[0].pop = Some(100.0);
+        let sequence = ConstructionSequence::new(vec![ConstructionStage::new(
+            "Surcharge",
+            ConstructionOperation::Surcharge { pressure: 150.0 },
+        )]);
+        let history = sequence.run(&model, &[2.0]).unwrap();
+
+        let initial = history.stages[0].stresses[0].unwrap();
+        assert_eq!(initial.max_past_effective, 260.0);
+        assert_eq!(initial.path, StressPath::Unloading);
+        assert_eq!(initial.ocr(), Some(260.0 / 160.0));
+        let loaded = history.stages[1].stresses[0].unwrap();
+        assert_eq!(loaded.path, StressPath::Loading);
+        assert_eq!(loaded.max_past_effective, 310.0);
+    }
+
+    #[test]
+    fn sequences_load_from_toml() {
+        let toml = r#"
+            [[stages]]
+            name = "Dig to formation"
+            operation = "excavate"
+            level = 4.0
+
+            [[stages]]
+            name = "Pump"
+            operation = "dewater"
+            level = 2.0
+        "#;
+        let sequence = ConstructionSequence::from_toml(toml).unwrap();
+        assert_eq!(sequence.stages.len(), 2);
+        assert!(matches!(
+            sequence.stages[1].operation,
+            ConstructionOperation::Dewater { level } if level == 2.0
+        ));
+
+        let json = serde_json::to_string(&ConstructionSequence::new(vec![ConstructionStage::new(
+            "Fill",
+            ConstructionOperation::Fill {
+                level: 12.0,
+                params: Box::new(soil("Fill", SoilType::Cohesive, 18.0)),
+            },
+        )]))
+        .unwrap();
+        let sequence = ConstructionSequence::from_json(&json).unwrap();
+        let history = sequence.run(&clay_model(), &[11.0]).unwrap();
+        assert_eq!(history.stages[1].stresses[0].unwrap().stress.total, 18.0);
+
+        assert!(matches!(
+            ConstructionSequence::from_json(r#"{"stages": [{"name": "x", "operation": "blast"}]}"#),
+            Err(GroundModelError::InvalidConfig { .. })
+        ));
+        let excavate_all = ConstructionSequence::new(vec![ConstructionStage::new(
+            "Remove",
+            ConstructionOperation::Excavate { level: -30.0 },
+        )]);
+        assert!(excavate_all.run(&clay_model(), &[0.0]).is_err());
+    }
+}
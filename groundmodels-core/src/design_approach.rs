@@ -0,0 +1,451 @@
+use crate::bearing::BearingOptions;
+use crate::config;
+use crate::piles::{PileOptions, PileType};
+use crate::{GroundModel, GroundModelError, PartialFactors};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const KIND: &str = "design approach";
+
+/// Names accepted by [`DesignApproach::builtin`].
+pub const BUILTIN_DESIGN_APPROACHES: [&str; 7] = [
+    "DA1-C1",
+    "DA1-C2",
+    "DA2",
+    "DA3",
+    "UK-DA1-C1",
+    "UK-DA1-C2",
+    "UK-DA1-C2-SLS",
+];
+
+/// Partial factors on actions (EN 1997-1 Table A.3).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionFactors {
+    pub permanent_unfavourable: f64,
+    pub permanent_favourable: f64,
+    pub variable_unfavourable: f64,
+    pub variable_favourable: f64,
+}
+
+impl ActionFactors {
+    pub fn a1() -> Self {
+        ActionFactors {
+            permanent_unfavourable: 1.35,
+            permanent_favourable: 1.0,
+            variable_unfavourable: 1.5,
+            variable_favourable: 0.0,
+        }
+    }
+
+    pub fn a2() -> Self {
+        ActionFactors {
+            permanent_unfavourable: 1.0,
+            permanent_favourable: 1.0,
+            variable_unfavourable: 1.3,
+            variable_favourable: 0.0,
+        }
+    }
+
+    /// Design value of unfavourable permanent and variable actions.
+    pub fn design_value(&self, permanent: f64, variable: f64) -> f64 {
+        permanent * self.permanent_unfavourable + variable * self.variable_unfavourable
+    }
+}
+
+impl Default for ActionFactors {
+    fn default() -> Self {
+        ActionFactors {
+            permanent_unfavourable: 1.0,
+            permanent_favourable: 1.0,
+            variable_unfavourable: 1.0,
+            variable_favourable: 1.0,
+        }
+    }
+}
+
+/// Base, shaft and total resistance factors for one pile type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PileResistanceFactors {
+    pub base: f64,
+    pub shaft: f64,
+    pub total: f64,
+}
+
+impl PileResistanceFactors {
+    pub fn new(base: f64, shaft: f64, total: f64) -> Self {
+        PileResistanceFactors { base, shaft, total }
+    }
+}
+
+impl Default for PileResistanceFactors {
+    fn default() -> Self {
+        PileResistanceFactors::new(1.0, 1.0, 1.0)
+    }
+}
+
+/// Partial resistance factors (EN 1997-1 Tables A.5 to A.7 and A.13).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResistanceFactors {
+    pub bearing: f64,
+    pub sliding: f64,
+    pub earth_resistance: f64,
+    pub bored_pile: PileResistanceFactors,
+    pub driven_pile: PileResistanceFactors,
+    /// Model factor γRd on calculated pile resistances, set by the UK National Annex.
+    pub pile_model_factor: f64,
+}
+
+impl ResistanceFactors {
+    pub fn r1() -> Self {
+        ResistanceFactors {
+            bored_pile: PileResistanceFactors::new(1.25, 1.0, 1.15),
+            ..Self::default()
+        }
+    }
+
+    pub fn r2() -> Self {
+        ResistanceFactors {
+            bearing: 1.4,
+            sliding: 1.1,
+            earth_resistance: 1.4,
+            bored_pile: PileResistanceFactors::new(1.1, 1.1, 1.1),
+            driven_pile: PileResistanceFactors::new(1.1, 1.1, 1.1),
+            pile_model_factor: 1.0,
+        }
+    }
+
+    pub fn r3() -> Self {
+        Self::default()
+    }
+
+    /// R1 for spread foundations and walls with R4 for piles.
+    pub fn r4() -> Self {
+        ResistanceFactors {
+            bored_pile: PileResistanceFactors::new(1.6, 1.3, 1.5),
+            driven_pile: PileResistanceFactors::new(1.3, 1.3, 1.3),
+            ..Self::default()
+        }
+    }
+
+    pub fn pile(&self, pile_type: PileType) -> &PileResistanceFactors {
+        match pile_type {
+            PileType::Bored => &self.bored_pile,
+            PileType::Driven => &self.driven_pile,
+        }
+    }
+}
+
+impl Default for ResistanceFactors {
+    fn default() -> Self {
+        ResistanceFactors {
+            bearing: 1.0,
+            sliding: 1.0,
+            earth_resistance: 1.0,
+            bored_pile: PileResistanceFactors::default(),
+            driven_pile: PileResistanceFactors::default(),
+            pile_model_factor: 1.0,
+        }
+    }
+}
+
+/// A named combination of material (M), action (A) and resistance (R) factor sets.
+/// Sections left out of a JSON or TOML file are unfactored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesignApproach {
+    pub name: String,
+    #[serde(default)]
+    pub material: PartialFactors,
+    /// Material factors for calculating pile resistances. DA1-C2 uses M1 here and M2 only
+    /// for unfavourable actions on piles. Defaults to `material`.
+    #[serde(default)]
+    pub pile_material: Option<PartialFactors>,
+    #[serde(default)]
+    pub actions: ActionFactors,
+    #[serde(default)]
+    pub resistance: ResistanceFactors,
+}
+
+impl DesignApproach {
+    /// DA1 Combination 1: A1 + M1 + R1.
+    pub fn da1_c1() -> Self {
+        DesignApproach {
+            name: "DA1-C1".to_string(),
+            material: m1(),
+            pile_material: None,
+            actions: ActionFactors::a1(),
+            resistance: ResistanceFactors::r1(),
+        }
+    }
+
+    /// DA1 Combination 2: A2 + M2 + R1, with A2 + M1 + R4 for piles.
+    pub fn da1_c2() -> Self {
+        DesignApproach {
+            name: "DA1-C2".to_string(),
+            material: m2(),
+            pile_material: Some(m1()),
+            actions: ActionFactors::a2(),
+            resistance: ResistanceFactors::r4(),
+        }
+    }
+
+    /// DA2: A1 + M1 + R2.
+    pub fn da2() -> Self {
+        DesignApproach {
+            name: "DA2".to_string(),
+            material: m1(),
+            pile_material: None,
+            actions: ActionFactors::a1(),
+            resistance: ResistanceFactors::r2(),
+        }
+    }
+
+    /// DA3: A2 on geotechnical actions + M2 + R3.
+    pub fn da3() -> Self {
+        DesignApproach {
+            name: "DA3".to_string(),
+            material: m2(),
+            pile_material: None,
+            actions: ActionFactors::a2(),
+            resistance: ResistanceFactors::r3(),
+        }
+    }
+
+    /// UK National Annex DA1-C1. Piles take R1 = 1.0 and the model factor γRd = 1.4.
+    pub fn uk_da1_c1() -> Self {
+        DesignApproach {
+            name: "UK-DA1-C1".to_string(),
+            resistance: ResistanceFactors {
+                bored_pile: PileResistanceFactors::default(),
+                pile_model_factor: 1.4,
+                ..ResistanceFactors::r1()
+            },
+            ..Self::da1_c1()
+        }
+    }
+
+    /// UK National Annex DA1-C2 without explicit verification of the serviceability limit
+    /// state, and with γRd = 1.4 as no static load tests are assumed.
+    pub fn uk_da1_c2() -> Self {
+        DesignApproach {
+            name: "UK-DA1-C2".to_string(),
+            resistance: ResistanceFactors {
+                bored_pile: PileResistanceFactors::new(2.0, 1.6, 2.0),
+                driven_pile: PileResistanceFactors::new(1.7, 1.5, 1.7),
+                pile_model_factor: 1.4,
+                ..ResistanceFactors::r4()
+            },
+            ..Self::da1_c2()
+        }
+    }
+
+    /// UK National Annex DA1-C2 where the serviceability limit state is explicitly verified.
+    pub fn uk_da1_c2_sls() -> Self {
+        DesignApproach {
+            name: "UK-DA1-C2-SLS".to_string(),
+            resistance: ResistanceFactors {
+                bored_pile: PileResistanceFactors::new(1.7, 1.4, 1.7),
+                driven_pile: PileResistanceFactors::new(1.5, 1.3, 1.5),
+                pile_model_factor: 1.4,
+                ..ResistanceFactors::r4()
+            },
+            ..Self::da1_c2()
+        }
+    }
+
+    /// One of [`BUILTIN_DESIGN_APPROACHES`], ignoring case.
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "DA1-C1" => Some(Self::da1_c1()),
+            "DA1-C2" => Some(Self::da1_c2()),
+            "DA2" => Some(Self::da2()),
+            "DA3" => Some(Self::da3()),
+            "UK-DA1-C1" => Some(Self::uk_da1_c1()),
+            "UK-DA1-C2" => Some(Self::uk_da1_c2()),
+            "UK-DA1-C2-SLS" => Some(Self::uk_da1_c2_sls()),
+            _ => None,
+        }
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, GroundModelError> {
+        config::from_json(KIND, text)
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, GroundModelError> {
+        config::from_toml(KIND, text)
+    }
+
+    /// Reads a user-defined set from a `.toml` file, or JSON otherwise.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, GroundModelError> {
+        config::load(KIND, path.as_ref())
+    }
+
+    /// A built-in set by name, or a user-defined set from a file.
+    pub fn resolve(name_or_path: &str) -> Result<Self, GroundModelError> {
+        if let Some(approach) = Self::builtin(name_or_path) {
+            return Ok(approach);
+        }
+        if Path::new(name_or_path).is_file() {
+            return Self::load(name_or_path);
+        }
+        Err(config::invalid(
+            KIND,
+            name_or_path,
+            format!(
+                "not a file or one of {}",
+                BUILTIN_DESIGN_APPROACHES.join(", ")
+            ),
+        ))
+    }
+
+    pub fn pile_material(&self) -> &PartialFactors {
+        self.pile_material.as_ref().unwrap_or(&self.material)
+    }
+
+    /// Bearing options with this approach's material factors and γR;v.
+    pub fn bearing_options(&self) -> BearingOptions {
+        BearingOptions {
+            partial_factors: Some(self.material.clone()),
+            resistance_factor: self.resistance.bearing,
+            ..BearingOptions::default()
+        }
+    }
+
+    /// Pile options with this approach's pile material factors, γRd, γb and γs.
+    pub fn pile_options(&self, pile_type: PileType) -> PileOptions {
+        let factors = self.resistance.pile(pile_type);
+        PileOptions {
+            partial_factors: Some(self.pile_material().clone()),
+            model_factor: self.resistance.pile_model_factor,
+            shaft_factor: factors.shaft,
+            base_factor: factors.base,
+            ..PileOptions::default()
+        }
+    }
+}
+
+fn m1() -> PartialFactors {
+    PartialFactors::default()
+}
+
+fn m2() -> PartialFactors {
+    PartialFactors::new(1.25, 1.25, 1.0, 1.4).with_rock(1.4, 1.0)
+}
+
+impl GroundModel {
+    /// Returns a copy of the model with the approach's material factors applied to every
+    /// unit. Units that were already factored have their factors removed first, so every
+    /// unit carries the same set.
+    pub fn apply_design_approach(
+        &self,
+        approach: &DesignApproach,
+    ) -> Result<GroundModel, GroundModelError> {
+        let mut model = self.clone();
+        for params in model.soil_params.iter_mut() {
+            if params.factored {
+                *params = params.remove_partial_factors()?;
+            }
+            *params = params.apply_partial_factors(&approach.material);
+        }
+        Ok(model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SoilParams, SoilType};
+
+    #[test]
+    fn builtin_sets() {
+        let da1_c2 = DesignApproach::builtin("da1-c2").unwrap();
+        assert_eq!(da1_c2.material.gamma_phi, 1.25);
+        assert_eq!(da1_c2.material.gamma_cu, 1.4);
+        assert_eq!(da1_c2.pile_material().gamma_phi, 1.0);
+        assert_eq!(da1_c2.actions.variable_unfavourable, 1.3);
+        assert_eq!(da1_c2.resistance.bored_pile.base, 1.6);
+
+        let da2 = DesignApproach::da2();
+        assert_eq!(da2.actions.design_value(100.0, 50.0), 210.0);
+        assert_eq!(da2.bearing_options().resistance_factor, 1.4);
+
+        let uk = DesignApproach::builtin("UK-DA1-C2").unwrap();
+        let options = uk.pile_options(PileType::Bored);
+        assert_eq!(options.model_factor, 1.4);
+        assert_eq!(options.base_factor, 2.0);
+        assert_eq!(options.shaft_factor, 1.6);
+
+        for name in BUILTIN_DESIGN_APPROACHES {
+            assert_eq!(DesignApproach::builtin(name).unwrap().name, name);
+        }
+        assert!(DesignApproach::builtin("DA4").is_none());
+        assert!(matches!(
+            DesignApproach::resolve("DA4"),
+            Err(GroundModelError::InvalidConfig { .. })
+        ));
+    }
+
+    #[test]
+    fn user_defined_sets() {
+        let toml = r#"
+            name = "Project"
+
+            [material]
+            gamma_phi = 1.2
+            gamma_c = 1.2
+            gamma_gamma = 1.0
+            gamma_cu = 1.5
+
+            [resistance]
+            bearing = 1.5
+        "#;
+        let approach = DesignApproach::from_toml(toml).unwrap();
+        assert_eq!(approach.material.gamma_cu, 1.5);
+        assert_eq!(approach.resistance.bearing, 1.5);
+        assert_eq!(approach.resistance.sliding, 1.0);
+        assert_eq!(approach.actions, ActionFactors::default());
+
+        let json = serde_json::to_string(&DesignApproach::da3()).unwrap();
+        let approach = DesignApproach::from_json(&json).unwrap();
+        assert_eq!(approach.name, "DA3");
+        assert_eq!(approach.material.gamma_c, 1.25);
+
+        assert!(DesignApproach::from_json("{\"material\": {}}").is_err());
+    }
+
+    #[test]
+    fn apply_design_approach_refactors_every_unit() {
+        let mut clay = SoilParams::new(
+            "Clay".to_string(),
+            0.0,
+            20000.0,
+            0.3,
+            0.0,
+            SoilType::Cohesive,
+            19.0,
+        );
+        clay.cu = Some(70.0);
+        let mut sand = SoilParams::new(
+            "Sand".to_string(),
+            0.0,
+            40000.0,
+            0.3,
+            0.0,
+            SoilType::Granular,
+            20.0,
+        );
+        sand.phi_prime = Some(35.0_f64.to_radians());
+        let sand = sand.apply_partial_factors(&PartialFactors::new(1.5, 1.0, 1.0, 1.0));
+
+        let model = GroundModel::new(Vec::new(), vec![clay, sand]);
+        let factored = model
+            .apply_design_approach(&DesignApproach::da1_c2())
+            .unwrap();
+
+        assert_eq!(factored.soil_params[0].cu, Some(50.0));
+        let phi_d = factored.soil_params[1].phi_prime.unwrap();
+        let expected = (35.0_f64.to_radians().tan() / 1.25).atan();
+        assert!((phi_d - expected).abs() < 1e-10);
+        assert!(factored.soil_params.iter().all(|p| p.factored));
+    }
+}
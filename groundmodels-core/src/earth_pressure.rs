@@ -0,0 +1,681 @@
+use crate::strip_log::{csv_quote, escape_xml, format_number};
+use crate::{
+    DrainageCondition, GroundModel, GroundModelError, ProfileVariable, SoilParams, SoilType,
+};
+use serde::{Deserialize, Serialize};
+
+/// Which side of a wall the pressures act on.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WallSide {
+    /// The ground model as it stands, including its surcharge.
+    Retained,
+    /// The ground model excavated to `formation_level`.
+    Excavated { formation_level: f64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EarthPressureState {
+    Active,
+    Passive,
+    AtRest,
+}
+
+/// Horizontal pressures at one level (kPa). At a layer boundary there is one point for the
+/// unit above and one for the unit below.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EarthPressurePoint {
+    pub level: f64,
+    pub reference: String,
+    /// Total vertical stress.
+    pub vertical_stress: f64,
+    pub pore_pressure: f64,
+    /// Earth pressure coefficient; 1 for undrained units.
+    pub coefficient: f64,
+    /// Effective horizontal pressure, or the total horizontal pressure for undrained units.
+    pub earth_pressure: f64,
+    /// Water pressure on the wall. Zero for undrained units, whose earth pressure is total.
+    pub water_pressure: f64,
+    /// `earth_pressure + water_pressure`.
+    pub total: f64,
+    /// The calculated earth pressure was tensile and has been set to zero.
+    pub tension_crack: bool,
+}
+
+/// Horizontal pressures against a wall from the top down.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EarthPressureProfile {
+    pub side: WallSide,
+    pub state: EarthPressureState,
+    pub geometry: WallGeometry,
+    pub condition: DrainageCondition,
+    pub points: Vec<EarthPressurePoint>,
+}
+
+impl EarthPressureProfile {
+    /// Resultant horizontal force per metre run (kN/m), integrating `total` down the wall.
+    pub fn resultant(&self) -> f64 {
+        self.points
+            .windows(2)
+            .map(|pair| (pair[0].total + pair[1].total) / 2.0 * (pair[0].level - pair[1].level))
+            .sum()
+    }
+
+    pub fn to_csv(&self) -> String {
+        let headers = [
+            "LEVEL(m)",
+            "REFERENCE",
+            "SIGMA_V(kPa)",
+            "U(kPa)",
+            "K",
+            "EARTH_PRESSURE(kPa)",
+            "WATER_PRESSURE(kPa)",
+            "TOTAL(kPa)",
+            "TENSION_CRACK",
+        ];
+        let mut lines = vec![headers.join(",")];
+        for p in &self.points {
+            lines.push(
+                [
+                    p.level.to_string(),
+                    csv_quote(&p.reference),
+                    p.vertical_stress.to_string(),
+                    p.pore_pressure.to_string(),
+                    p.coefficient.to_string(),
+                    p.earth_pressure.to_string(),
+                    p.water_pressure.to_string(),
+                    p.total.to_string(),
+                    p.tension_crack.to_string(),
+                ]
+                .join(","),
+            );
+        }
+        lines.join("\n")
+    }
+
+    /// Pressure diagram with level down the left axis: total pressure as a filled outline,
+    /// with earth and water pressures as lines.
+    pub fn render_svg(&self, title: Option<&str>) -> String {
+        let (width, height) = (480.0, 640.0);
+        let (left, right, top_margin, bottom_margin) = (64.0, 24.0, 40.0, 40.0);
+        let plot_w = width - left - right;
+        let plot_h = height - top_margin - bottom_margin;
+
+        let top = self.points.first().map_or(0.0, |p| p.level);
+        let bottom = self.points.last().map_or(0.0, |p| p.level);
+        let depth = (top - bottom).max(0.0001);
+        let max_pressure = self
+            .points
+            .iter()
+            .map(|p| p.total.max(p.earth_pressure))
+            .fold(0.0_f64, f64::max)
+            .max(1.0);
+
+        let x_for = |pressure: f64| left + pressure / max_pressure * plot_w;
+        let y_for = |level: f64| top_margin + (top - level) / depth * plot_h;
+        let polyline = |value: fn(&EarthPressurePoint) -> f64| {
+            self.points
+                .iter()
+                .map(|p| format!("{:.2},{:.2}", x_for(value(p)), y_for(p.level)))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        let mut svg = Vec::new();
+        svg.push(format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">",
+            width, height, width, height
+        ));
+        svg.push("<style>.axis{font:12px Segoe UI, Arial, sans-serif;fill:#333;}.title{font:14px Segoe UI, Arial, sans-serif;font-weight:600;fill:#111;}</style>".to_string());
+        if let Some(title) = title {
+            svg.push(format!(
+                "<text class=\"title\" x=\"{}\" y=\"{}\">{}</text>",
+                left,
+                top_margin * 0.5,
+                escape_xml(title)
+            ));
+        }
+
+        svg.push(format!(
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#222\" />",
+            left,
+            top_margin,
+            left,
+            top_margin + plot_h
+        ));
+        let mut level = top.floor();
+        while level >= bottom {
+            let y = y_for(level);
+            svg.push(format!(
+                "<text class=\"axis\" x=\"{}\" y=\"{}\" text-anchor=\"end\" dominant-baseline=\"middle\">{}</text>",
+                left - 8.0,
+                y,
+                format_number(level)
+            ));
+            level -= 1.0;
+        }
+        svg.push(format!(
+            "<text class=\"axis\" x=\"{}\" y=\"{}\" text-anchor=\"end\">{} kPa</text>",
+            left + plot_w,
+            top_margin + plot_h + 24.0,
+            format_number(max_pressure)
+        ));
+
+        let outline = format!(
+            "{:.2},{:.2} {} {:.2},{:.2}",
+            x_for(0.0),
+            y_for(top),
+            polyline(|p| p.total),
+            x_for(0.0),
+            y_for(bottom)
+        );
+        svg.push(format!(
+            "<polygon points=\"{}\" fill=\"#F6D04D\" fill-opacity=\"0.5\" stroke=\"#222\" />",
+            outline
+        ));
+        svg.push(format!(
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"#B8906B\" stroke-width=\"1.5\" />",
+            polyline(|p| p.earth_pressure)
+        ));
+        svg.push(format!(
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"#1E90FF\" stroke-width=\"1.5\" stroke-dasharray=\"6,4\" />",
+            polyline(|p| p.water_pressure)
+        ));
+
+        svg.push("</svg>".to_string());
+        svg.join("")
+    }
+}
+
+/// Geometry of the wall back and the retained surface. Angles are in radians: `backfill_slope`
+/// rises away from the wall, and `wall_inclination` is the back face from vertical, positive
+/// when the retained soil overhangs the face. `friction_ratio` is δ/φ′.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct WallGeometry {
+    pub backfill_slope: f64,
+    pub wall_inclination: f64,
+    pub friction_ratio: f64,
+}
+
+impl WallGeometry {
+    pub fn new(backfill_slope: f64, wall_inclination: f64, friction_ratio: f64) -> Self {
+        Self {
+            backfill_slope,
+            wall_inclination,
+            friction_ratio,
+        }
+    }
+
+    /// A smooth vertical wall retaining a surface sloping at `backfill_slope`.
+    pub fn sloping(backfill_slope: f64) -> Self {
+        Self {
+            backfill_slope,
+            ..Self::default()
+        }
+    }
+}
+
+/// EN 1997-1 Annex C.2 coefficients for the pressure normal to the wall,
+/// `K_γ γ d + K_q q ∓ K_c c′`. `k_c` is a magnitude; it reduces active and adds to passive
+/// pressures.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EarthPressureCoefficients {
+    pub k_n: f64,
+    pub k_gamma: f64,
+    pub k_q: f64,
+    pub k_c: f64,
+}
+
+impl SoilParams {
+    /// Coulomb active coefficient. The resultant acts at δ to the normal of the wall.
+    pub fn coulomb_k_active(&self, geometry: &WallGeometry) -> Result<f64, GroundModelError> {
+        let phi = self.required(self.phi_prime, "phi_prime")?;
+        let beta = geometry.backfill_slope;
+        let theta = geometry.wall_inclination;
+        let delta = self.wall_friction(phi, geometry)?;
+        if beta > phi {
+            return Err(self.calculation_error("backfill slope is steeper than phi_prime"));
+        }
+
+        let root = ((phi + delta).sin() * (phi - beta).sin()
+            / ((theta + delta).cos() * (theta - beta).cos()))
+        .sqrt();
+        Ok((phi - theta).cos().powi(2)
+            / (theta.cos().powi(2) * (theta + delta).cos() * (1.0 + root).powi(2)))
+    }
+
+    /// Coulomb passive coefficient. The resultant acts at δ to the normal of the wall.
+    /// Coulomb overestimates passive resistance for δ greater than about φ′/3; prefer
+    /// [`SoilParams::annex_c_coefficients`] for rough walls.
+    pub fn coulomb_k_passive(&self, geometry: &WallGeometry) -> Result<f64, GroundModelError> {
+        let phi = self.required(self.phi_prime, "phi_prime")?;
+        let beta = geometry.backfill_slope;
+        let theta = geometry.wall_inclination;
+        let delta = self.wall_friction(phi, geometry)?;
+
+        let ratio = (phi + delta).sin() * (phi + beta).sin()
+            / ((theta - delta).cos() * (theta - beta).cos());
+        if !(0.0..1.0).contains(&ratio) {
+            return Err(self.calculation_error("no Coulomb passive wedge for this wall geometry"));
+        }
+        Ok((phi + theta).cos().powi(2)
+            / (theta.cos().powi(2) * (theta - delta).cos() * (1.0 - ratio.sqrt()).powi(2)))
+    }
+
+    /// Active or passive coefficients from the EN 1997-1 Annex C.2 numerical procedure, which
+    /// approximates the Caquot–Kérisel log-spiral solution. At-rest pressures use
+    /// [`SoilParams::k0_overconsolidated`] instead.
+    pub fn annex_c_coefficients(
+        &self,
+        state: EarthPressureState,
+        geometry: &WallGeometry,
+    ) -> Result<EarthPressureCoefficients, GroundModelError> {
+        let phi = self.required(self.phi_prime, "phi_prime")?;
+        if phi <= 0.0 {
+            return Err(self.calculation_error("phi_prime must be positive for Annex C"));
+        }
+        let beta = geometry.backfill_slope;
+        let theta = geometry.wall_inclination;
+        let delta = self.wall_friction(phi, geometry)?;
+        if beta.abs() > phi {
+            return Err(self.calculation_error("backfill slope is steeper than phi_prime"));
+        }
+
+        // The procedure is written for passive pressures; active pressures take φ′ and δ
+        // as negative.
+        let (phi, delta) = match state {
+            EarthPressureState::Passive => (phi, delta),
+            EarthPressureState::Active => (-phi, -delta),
+            EarthPressureState::AtRest => {
+                return Err(self.calculation_error("Annex C covers active and passive states"))
+            }
+        };
+
+        let m_t = ((-beta.sin() / phi.sin()).acos() - phi - beta) / 2.0;
+        let m_w = ((delta.sin() / phi.sin()).acos() - phi - delta) / 2.0;
+        let nu = m_t + beta - m_w - theta;
+        let k_n = (1.0 + phi.sin() * (2.0 * m_w + phi).sin())
+            / (1.0 - phi.sin() * (2.0 * m_t + phi).sin())
+            * (2.0 * nu * phi.tan()).exp();
+
+        Ok(EarthPressureCoefficients {
+            k_n,
+            k_gamma: k_n * beta.cos() * (beta - theta).cos(),
+            k_q: k_n * beta.cos().powi(2),
+            k_c: (k_n - 1.0) / phi.tan(),
+        })
+    }
+
+    fn wall_friction(&self, phi: f64, geometry: &WallGeometry) -> Result<f64, GroundModelError> {
+        if !(0.0..=1.0).contains(&geometry.friction_ratio) {
+            return Err(GroundModelError::InvalidParameter {
+                reference: self.reference.clone(),
+                name: "friction_ratio",
+                message: "must be between 0 and 1".to_string(),
+            });
+        }
+        Ok(geometry.friction_ratio * phi)
+    }
+}
+
+impl GroundModel {
+    /// Horizontal pressures on one side of a wall between `wall_top` and `wall_toe`. Drained
+    /// pressures are `K σ′v ∓ 2c′√K` plus water pressure, with Rankine coefficients for a
+    /// smooth vertical wall and level ground; for any other `geometry`, active and passive
+    /// pressures are normal to the wall, `K_γ (σ′v − q) + K_q q ∓ K_c c′`, with the Annex C
+    /// coefficients. Undrained pressures in units with `cu` are `σv ∓ 2cu`, and granular units
+    /// stay drained. Tensile earth pressures are set to zero as a tension crack. `surcharge` is
+    /// added to the vertical stress on this side.
+    #[allow(clippy::too_many_arguments)]
+    pub fn earth_pressure_profile(
+        &self,
+        wall_top: f64,
+        wall_toe: f64,
+        side: WallSide,
+        state: EarthPressureState,
+        geometry: &WallGeometry,
+        condition: DrainageCondition,
+        surcharge: f64,
+    ) -> Result<EarthPressureProfile, GroundModelError> {
+        let mut model = match side {
+            WallSide::Retained => self.clone(),
+            WallSide::Excavated { formation_level } => self.excavate(formation_level),
+        };
+        model.surcharge += surcharge;
+        let datum = model.get_top_level();
+        let start = wall_top.min(datum);
+
+        let mut water_breaks = match &model.pore_pressure {
+            Some(regime) => regime.breakpoints(),
+            None => vec![model.groundwater],
+        };
+        water_breaks.retain(|level| level.is_finite());
+
+        let mut points = Vec::new();
+        for (top, base, params) in model.stress_intervals() {
+            let top = top.min(start);
+            let base = base.max(wall_toe);
+            if top <= base {
+                continue;
+            }
+
+            let mut levels = vec![top, base];
+            levels.extend(water_breaks.iter().copied());
+            for profile in &params.profiles {
+                levels.extend(profile.points.iter().map(|point| match profile.variable {
+                    ProfileVariable::Depth => datum - point[0],
+                    ProfileVariable::Elevation => point[0],
+                }));
+            }
+            levels.retain(|level| *level <= top && *level >= base);
+            levels.sort_by(|a, b| b.total_cmp(a));
+            levels.dedup();
+
+            let pressure_at = |level: f64| {
+                let resolved = params.resolve_profiles(datum - level, level);
+                model.horizontal_pressure(&resolved, level, state, geometry, condition)
+            };
+            let mut interval_points: Vec<EarthPressurePoint> = Vec::new();
+            for level in levels {
+                let point = pressure_at(level)?;
+                // Add the level where the earth pressure crosses zero, so the tension crack
+                // depth is exact
+                if let Some(previous) = interval_points.last() {
+                    if previous.earth_pressure * point.earth_pressure < 0.0 {
+                        let fraction = previous.earth_pressure
+                            / (previous.earth_pressure - point.earth_pressure);
+                        let crossing = previous.level + (level - previous.level) * fraction;
+                        interval_points.push(pressure_at(crossing)?);
+                    }
+                }
+                interval_points.push(point);
+            }
+            points.extend(interval_points);
+        }
+
+        for point in &mut points {
+            if point.earth_pressure < 0.0 {
+                point.earth_pressure = 0.0;
+                point.tension_crack = true;
+            }
+            point.total = point.earth_pressure + point.water_pressure;
+        }
+
+        Ok(EarthPressureProfile {
+            side,
+            state,
+            geometry: *geometry,
+            condition,
+            points,
+        })
+    }
+
+    /// Horizontal pressure at `level` in a unit with `params`, before tension cracks are
+    /// removed.
+    fn horizontal_pressure(
+        &self,
+        params: &SoilParams,
+        level: f64,
+        state: EarthPressureState,
+        geometry: &WallGeometry,
+        condition: DrainageCondition,
+    ) -> Result<EarthPressurePoint, GroundModelError> {
+        let stress = self.stress_profile(&[level])[0];
+        let sign = match state {
+            EarthPressureState::Active => -1.0,
+            EarthPressureState::Passive => 1.0,
+            EarthPressureState::AtRest => 0.0,
+        };
+
+        let undrained = condition == DrainageCondition::Undrained
+            && params.behaviour == SoilType::Cohesive
+            && params.cu.is_some();
+        let (coefficient, earth_pressure, water_pressure) = if undrained {
+            let cu = params.required(params.cu, "cu")?;
+            (1.0, stress.total + sign * 2.0 * cu, 0.0)
+        } else if state != EarthPressureState::AtRest && *geometry != WallGeometry::default() {
+            let k = params.annex_c_coefficients(state, geometry)?;
+            let c = params.c_prime.unwrap_or(0.0);
+            (
+                k.k_gamma,
+                k.k_gamma * (stress.effective - self.surcharge)
+                    + k.k_q * self.surcharge
+                    + sign * k.k_c * c,
+                stress.pore_pressure,
+            )
+        } else {
+            let k = match state {
+                EarthPressureState::Active => params.get_k_active(None)?,
+                EarthPressureState::Passive => params.get_k_passive(None)?,
+                EarthPressureState::AtRest => params.k0_at(stress.effective)?,
+            };
+            let c = params.c_prime.unwrap_or(0.0);
+            (
+                k,
+                k * stress.effective + sign * 2.0 * c * k.sqrt(),
+                stress.pore_pressure,
+            )
+        };
+
+        Ok(EarthPressurePoint {
+            level,
+            reference: params.reference.clone(),
+            vertical_stress: stress.total,
+            pore_pressure: stress.pore_pressure,
+            coefficient,
+            earth_pressure,
+            water_pressure,
+            total: earth_pressure + water_pressure,
+            tension_crack: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::sand;
+    use crate::SoilLayer;
+
+    #[test]
+    fn active_pressures_with_water_and_layer_step() {
+        let mut clay = sand("Clay", 24.0);
+        clay.behaviour = SoilType::Cohesive;
+        clay.c_prime = Some(5.0);
+        let mut model = GroundModel::new(
+            vec![
+                SoilLayer::with_unit_reference(10.0, 6.0, String::new(), "Sand".to_string()),
+                SoilLayer::with_unit_reference(6.0, -10.0, String::new(), "Clay".to_string()),
+            ],
+            vec![sand("Sand", 30.0), clay],
+        );
+        model.groundwater = 8.0;
+
+        let profile = model
+            .earth_pressure_profile(
+                10.0,
+                0.0,
+                WallSide::Retained,
+                EarthPressureState::Active,
+                &WallGeometry::default(),
+                DrainageCondition::Drained,
+                10.0,
+            )
+            .unwrap();
+
+        let levels: Vec<f64> = profile.points.iter().map(|p| p.level).collect();
+        assert_eq!(levels, vec![10.0, 8.0, 6.0, 6.0, 0.0]);
+
+        // Surcharge under Ka = 1/3 at the top
+        assert!((profile.points[0].earth_pressure - 10.0 / 3.0).abs() < 1e-9);
+        // Step in earth pressure at the boundary: σ′v = 10 + 40 + 20 = 70 kPa, u = 20 kPa
+        let (upper, lower) = (&profile.points[2], &profile.points[3]);
+        assert!((upper.earth_pressure - 70.0 / 3.0).abs() < 1e-9);
+        let ka = lower.coefficient;
+        assert!((lower.earth_pressure - (ka * 70.0 - 10.0 * ka.sqrt())).abs() < 1e-9);
+        assert_eq!(upper.water_pressure, 20.0);
+        assert_eq!(lower.total, lower.earth_pressure + 20.0);
+        assert!(profile.resultant() > 0.0);
+    }
+
+    #[test]
+    fn undrained_active_pressure_has_tension_crack() {
+        let mut clay = sand("Clay", 0.0);
+        clay.behaviour = SoilType::Cohesive;
+        clay.cu = Some(40.0);
+        let model = GroundModel::quick_init(clay, 0.0, -20.0);
+
+        let profile = model
+            .earth_pressure_profile(
+                0.0,
+                -10.0,
+                WallSide::Retained,
+                EarthPressureState::Active,
+                &WallGeometry::default(),
+                DrainageCondition::Undrained,
+                0.0,
+            )
+            .unwrap();
+
+        // σv - 2cu crosses zero 4 m down
+        let crack = profile
+            .points
+            .iter()
+            .find(|p| !p.tension_crack && p.level < 0.0)
+            .unwrap();
+        assert!((crack.level + 4.0).abs() < 1e-9);
+        assert!(profile.points[0].tension_crack);
+        assert_eq!(profile.points[0].total, 0.0);
+        assert!((profile.points.last().unwrap().total - 120.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn coulomb_and_annex_c_coefficients() {
+        let params = sand("Sand", 30.0);
+        let phi = 30.0_f64.to_radians();
+        let rankine_ka = (1.0 - phi.sin()) / (1.0 + phi.sin());
+
+        // Both reduce to Rankine for a smooth vertical wall and level ground
+        let smooth = WallGeometry::default();
+        assert!((params.coulomb_k_active(&smooth).unwrap() - rankine_ka).abs() < 1e-10);
+        assert!((params.coulomb_k_passive(&smooth).unwrap() - 1.0 / rankine_ka).abs() < 1e-10);
+        let active = params
+            .annex_c_coefficients(EarthPressureState::Active, &smooth)
+            .unwrap();
+        assert!((active.k_gamma - rankine_ka).abs() < 1e-10);
+        assert!((active.k_c - 2.0 * rankine_ka.sqrt()).abs() < 1e-10);
+        let passive = params
+            .annex_c_coefficients(EarthPressureState::Passive, &smooth)
+            .unwrap();
+        assert!((passive.k_c - 2.0 / rankine_ka.sqrt()).abs() < 1e-10);
+
+        // Coulomb with a 10° backfill slope: cos²φ / (1 + √(sinφ sin(φ−β) / cosβ))²
+        let beta = 10.0_f64.to_radians();
+        let sloping = WallGeometry::sloping(beta);
+        let expected = phi.cos().powi(2)
+            / (1.0 + (phi.sin() * (phi - beta).sin() / beta.cos()).sqrt()).powi(2);
+        assert!((params.coulomb_k_active(&sloping).unwrap() - expected).abs() < 1e-10);
+        let annex_c = params
+            .annex_c_coefficients(EarthPressureState::Active, &sloping)
+            .unwrap();
+        assert!((annex_c.k_gamma - expected).abs() < 0.01);
+
+        // Rough wall, δ = φ′: Annex C gives Kp = 1.5 exp(2π/3 tanφ′) for a vertical wall
+        let rough = WallGeometry::new(0.0, 0.0, 1.0);
+        let passive = params
+            .annex_c_coefficients(EarthPressureState::Passive, &rough)
+            .unwrap();
+        let expected = 1.5 * (2.0 * std::f64::consts::PI / 3.0 * phi.tan()).exp();
+        assert!((passive.k_n - expected).abs() < 1e-10);
+        assert!(
+            params
+                .annex_c_coefficients(EarthPressureState::Active, &rough)
+                .unwrap()
+                .k_n
+                < rankine_ka
+        );
+
+        assert!(params
+            .coulomb_k_active(&WallGeometry::sloping(35.0_f64.to_radians()))
+            .is_err());
+        assert!(params
+            .coulomb_k_active(&WallGeometry::new(0.0, 0.0, 1.5))
+            .is_err());
+    }
+
+    #[test]
+    fn passive_pressure_below_formation() {
+        let model = GroundModel::quick_init(sand("Sand", 30.0), 10.0, 0.0);
+
+        let profile = model
+            .earth_pressure_profile(
+                10.0,
+                2.0,
+                WallSide::Excavated {
+                    formation_level: 6.0,
+                },
+                EarthPressureState::Passive,
+                &WallGeometry::default(),
+                DrainageCondition::Drained,
+                0.0,
+            )
+            .unwrap();
+
+        assert_eq!(profile.points[0].level, 6.0);
+        assert_eq!(profile.points[0].total, 0.0);
+        // Kp = 3 on 4 m of dry sand below formation
+        assert!((profile.points.last().unwrap().total - 240.0).abs() < 1e-9);
+
+        let csv = profile.to_csv();
+        assert!(csv.starts_with("LEVEL(m),REFERENCE"));
+        assert_eq!(csv.lines().count(), profile.points.len() + 1);
+        assert!(profile.render_svg(Some("Passive")).contains("<polygon"));
+    }
+
+    #[test]
+    fn rough_wall_passive_and_sloping_backfill_profiles() {
+        let model = GroundModel::quick_init(sand("Sand", 30.0), 10.0, 0.0);
+        let phi = 30.0_f64.to_radians();
+        let excavated = WallSide::Excavated {
+            formation_level: 6.0,
+        };
+
+        let rough = WallGeometry::new(0.0, 0.0, 1.0);
+        let profile = model
+            .earth_pressure_profile(
+                10.0,
+                2.0,
+                excavated,
+                EarthPressureState::Passive,
+                &rough,
+                DrainageCondition::Drained,
+                0.0,
+            )
+            .unwrap();
+        // Kp = 1.5 exp(2π/3 tanφ′) on 4 m of dry sand below formation, well above Rankine's 3
+        let kp = 1.5 * (2.0 * std::f64::consts::PI / 3.0 * phi.tan()).exp();
+        let toe = profile.points.last().unwrap();
+        assert!((toe.coefficient - kp).abs() < 1e-10);
+        assert!((toe.total - kp * 80.0).abs() < 1e-9);
+        assert_eq!(profile.geometry, rough);
+
+        // A sloping backfill takes the surcharge with K_q and the soil weight with K_γ
+        let sloping = WallGeometry::sloping(10.0_f64.to_radians());
+        let k = sand("Sand", 30.0)
+            .annex_c_coefficients(EarthPressureState::Active, &sloping)
+            .unwrap();
+        let profile = model
+            .earth_pressure_profile(
+                10.0,
+                6.0,
+                WallSide::Retained,
+                EarthPressureState::Active,
+                &sloping,
+                DrainageCondition::Drained,
+                10.0,
+            )
+            .unwrap();
+        assert!((profile.points[0].earth_pressure - k.k_q * 10.0).abs() < 1e-9);
+        let base = profile.points.last().unwrap();
+        assert!((base.earth_pressure - (k.k_gamma * 80.0 + k.k_q * 10.0)).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::agsi_io::AgsiError;
+use std::fmt;
+
+/// Errors raised by ground model queries and parameter calculations.
+#[derive(Debug)]
+pub enum GroundModelError {
+    /// A calculation needs a parameter that is not set on the unit.
+    MissingParameter {
+        reference: String,
+        name: &'static str,
+    },
+    /// A parameter is set but cannot be used, e.g. a zero UCS.
+    InvalidParameter {
+        reference: String,
+        name: &'static str,
+        message: String,
+    },
+    /// A calculation has no real result for the supplied parameters.
+    Calculation { reference: String, message: String },
+    /// No layer with parameters covers the requested level.
+    LevelOutsideModel { level: f64 },
+    /// The layer geometry cannot be used, e.g. a base above its top.
+    InvalidGeometry { reference: String, message: String },
+    /// The model has no parameter set for the requested design case.
+    UnknownCase { case_id: String },
+    /// Partial factors were requested to be removed from unfactored parameters.
+    NotFactored { reference: String },
+    /// The conversion type is not one of `soilparams` or `groundmodel`.
+    InvalidConvertType { value: String },
+    /// A design approach, validation rule set or construction sequence cannot be read, parsed
+    /// or used, e.g. an approach that is not built in, an unknown rule or a stage that leaves
+    /// no ground. `kind` names the settings and `name` the file, format or entry.
+    InvalidConfig {
+        kind: &'static str,
+        name: String,
+        message: String,
+    },
+    /// The AGSi file could not be read, does not match the schema or does not contain the
+    /// requested model.
+    Agsi(AgsiError),
+}
+
+impl fmt::Display for GroundModelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GroundModelError::MissingParameter { reference, name } => {
+                write!(f, "{} must be set for '{}'", name, reference)
+            }
+            GroundModelError::InvalidParameter {
+                reference,
+                name,
+                message,
+            } => write!(f, "Invalid {} for '{}': {}", name, reference, message),
+            GroundModelError::Calculation { reference, message } => {
+                write!(f, "Calculation failed for '{}': {}", reference, message)
+            }
+            GroundModelError::LevelOutsideModel { level } => {
+                write!(f, "Level {} is outside the ground model", level)
+            }
+            GroundModelError::InvalidGeometry { reference, message } => {
+                write!(f, "Invalid geometry for '{}': {}", reference, message)
+            }
+            GroundModelError::UnknownCase { case_id } => {
+                write!(f, "Case not present: {}", case_id)
+            }
+            GroundModelError::NotFactored { reference } => {
+                write!(f, "No factors stored on '{}'", reference)
+            }
+            GroundModelError::InvalidConvertType { value } => write!(
+                f,
+                "Invalid convert type '{}'. Use 'soilparams' or 'groundmodel'",
+                value
+            ),
+            GroundModelError::InvalidConfig {
+                kind,
+                name,
+                message,
+            } => write!(f, "Invalid {} '{}': {}", kind, name, message),
+            GroundModelError::Agsi(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for GroundModelError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GroundModelError::Agsi(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<AgsiError> for GroundModelError {
+    fn from(err: AgsiError) -> Self {
+        GroundModelError::Agsi(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agsi_parse_errors_keep_their_path() {
+        let err = GroundModelError::from(AgsiError::Parse {
+            path: "agsiModel[0].modelID".to_string(),
+            message: "invalid type".to_string(),
+        });
+        assert!(
+            matches!(err, GroundModelError::Agsi(AgsiError::Parse { ref path, .. }) if path == "agsiModel[0].modelID")
+        );
+        assert_eq!(
+            err.to_string(),
+            "Invalid AGSi at agsiModel[0].modelID: invalid type"
+        );
+    }
+
+    #[test]
+    fn missing_parameter_names_unit() {
+        let err = GroundModelError::MissingParameter {
+            reference: "London Clay".to_string(),
+            name: "phi_prime",
+        };
+        assert_eq!(err.to_string(), "phi_prime must be set for 'London Clay'");
+    }
+}
@@ -0,0 +1,179 @@
+use crate::interpolate;
+use serde::{Deserialize, Serialize};
+
+/// Unit weight of water used when a regime does not give one (kN/m³).
+pub const DEFAULT_UNIT_WEIGHT_WATER: f64 = 10.0;
+
+fn default_unit_weight_water() -> f64 {
+    DEFAULT_UNIT_WEIGHT_WATER
+}
+
+/// How the pore pressure varies within a [`PiezometricZone`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PiezometricHead {
+    /// Hydrostatic below a piezometric level (m). A level above the top of the zone gives an
+    /// artesian pressure at the top.
+    Level(f64),
+    /// Pore pressure (kPa) against level (m) as `[level, pwp]` points, interpolated linearly and
+    /// held constant beyond the ends. Used for underdrainage and other non-hydrostatic profiles.
+    Profile(Vec<[f64; 2]>),
+}
+
+/// A band of levels with its own piezometric head.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PiezometricZone {
+    /// Top of the zone, or `None` if it is unbounded above.
+    #[serde(default)]
+    pub top_level: Option<f64>,
+    /// Base of the zone, or `None` if it is unbounded below.
+    #[serde(default)]
+    pub base_level: Option<f64>,
+    pub head: PiezometricHead,
+}
+
+impl PiezometricZone {
+    pub fn new(top_level: Option<f64>, base_level: Option<f64>, head: PiezometricHead) -> Self {
+        let head = match head {
+            PiezometricHead::Profile(mut points) => {
+                points.sort_by(|a, b| a[0].total_cmp(&b[0]));
+                PiezometricHead::Profile(points)
+            }
+            level => level,
+        };
+        PiezometricZone {
+            top_level,
+            base_level,
+            head,
+        }
+    }
+
+    pub fn contains(&self, level: f64) -> bool {
+        self.top_level.is_none_or(|top| level <= top)
+            && self.base_level.is_none_or(|base| level >= base)
+    }
+
+    fn pore_pressure_at(&self, level: f64, unit_weight_water: f64) -> f64 {
+        match &self.head {
+            PiezometricHead::Level(head) => (unit_weight_water * (head - level)).max(0.0),
+            PiezometricHead::Profile(points) => interpolate(points, level).unwrap_or(0.0),
+        }
+    }
+}
+
+/// Pore water pressures through the ground. Zones are checked in order and the first that
+/// contains a level sets its pore pressure, so perched water is listed before the main water
+/// table. Levels outside every zone are dry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PorePressureRegime {
+    #[serde(default = "default_unit_weight_water")]
+    pub unit_weight_water: f64,
+    pub zones: Vec<PiezometricZone>,
+}
+
+impl PorePressureRegime {
+    /// Hydrostatic pore pressures below a single water table.
+    pub fn hydrostatic(level: f64) -> Self {
+        PorePressureRegime {
+            unit_weight_water: DEFAULT_UNIT_WEIGHT_WATER,
+            zones: vec![PiezometricZone::new(
+                None,
+                None,
+                PiezometricHead::Level(level),
+            )],
+        }
+    }
+
+    pub fn zone_at(&self, level: f64) -> Option<&PiezometricZone> {
+        self.zones.iter().find(|zone| zone.contains(level))
+    }
+
+    /// Pore pressure at `level` (kPa). Never negative.
+    pub fn pore_pressure_at(&self, level: f64) -> f64 {
+        self.zone_at(level)
+            .map_or(0.0, |zone| {
+                zone.pore_pressure_at(level, self.unit_weight_water)
+            })
+            .max(0.0)
+    }
+
+    /// Whether the ground at `level` is below a water table, i.e. carries a positive pore
+    /// pressure or lies below the piezometric level of its zone.
+    pub fn is_saturated(&self, level: f64) -> bool {
+        match self.zone_at(level) {
+            Some(PiezometricZone {
+                head: PiezometricHead::Level(head),
+                ..
+            }) => level < *head,
+            Some(zone) => zone.pore_pressure_at(level, self.unit_weight_water) > 0.0,
+            None => false,
+        }
+    }
+
+    /// Levels where the pore pressure can change gradient or the ground changes between dry
+    /// and saturated: zone boundaries, piezometric levels, profile points and the levels
+    /// where a profile crosses zero.
+    pub fn breakpoints(&self) -> Vec<f64> {
+        let mut levels = Vec::new();
+        for zone in &self.zones {
+            levels.extend(zone.top_level);
+            levels.extend(zone.base_level);
+            match &zone.head {
+                PiezometricHead::Level(head) => levels.push(*head),
+                PiezometricHead::Profile(points) => {
+                    levels.extend(points.iter().map(|point| point[0]));
+                    for pair in points.windows(2) {
+                        let ([x0, y0], [x1, y1]) = (pair[0], pair[1]);
+                        if y0 * y1 < 0.0 {
+                            levels.push(x0 + (x1 - x0) * y0 / (y0 - y1));
+                        }
+                    }
+                }
+            }
+        }
+        levels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn perched_water_above_main_table() {
+        let regime = PorePressureRegime {
+            unit_weight_water: 9.81,
+            zones: vec![
+                PiezometricZone::new(Some(10.0), Some(8.0), PiezometricHead::Level(10.0)),
+                PiezometricZone::new(None, None, PiezometricHead::Level(2.0)),
+            ],
+        };
+
+        assert!((regime.pore_pressure_at(8.0) - 19.62).abs() < 1e-9);
+        assert!(regime.is_saturated(9.0));
+        // Dry between the perched water and the main water table
+        assert_eq!(regime.pore_pressure_at(5.0), 0.0);
+        assert!(!regime.is_saturated(5.0));
+        assert!((regime.pore_pressure_at(0.0) - 19.62).abs() < 1e-9);
+    }
+
+    #[test]
+    fn artesian_and_underdrained_zones() {
+        let regime = PorePressureRegime {
+            unit_weight_water: DEFAULT_UNIT_WEIGHT_WATER,
+            zones: vec![
+                PiezometricZone::new(
+                    Some(0.0),
+                    Some(-10.0),
+                    PiezometricHead::Profile(vec![[-10.0, 60.0], [0.0, 0.0]]),
+                ),
+                PiezometricZone::new(Some(-10.0), None, PiezometricHead::Level(5.0)),
+            ],
+        };
+
+        // Underdrained: 30 kPa at mid-depth instead of 50 kPa hydrostatic
+        assert!((regime.pore_pressure_at(-5.0) - 30.0).abs() < 1e-9);
+        // Artesian head 5 m above the top of the lower aquifer; the upper zone wins at -10
+        assert!((regime.pore_pressure_at(-10.0) - 60.0).abs() < 1e-9);
+        assert!((regime.pore_pressure_at(-12.0) - 170.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,573 @@
+use crate::stratigraphy::LayerInterval;
+use crate::{GroundModel, GroundModelError, SoilParams, SoilType};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Where a Hoek–Brown rock mass is loaded, for the Hoek et al. (2002) choice of σ3max.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum HoekBrownApplication {
+    /// A tunnel at `depth` below the ground surface (m).
+    Tunnel { depth: f64 },
+    /// A slope of `height` (m).
+    Slope { height: f64 },
+}
+
+/// How σ3 is chosen when converting rock layers to equivalent Mohr-Coulomb parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RockConfinement {
+    /// The in-situ minor principal effective stress at the middle of each layer, taking
+    /// σ′h = ν/(1 − ν) σ′v. A lowest layer without a base runs to the rigid boundary, or σ3
+    /// is taken at its top when there is none.
+    InSitu,
+    /// The Hoek et al. (2002) σ3max for the application.
+    Sig3Max(HoekBrownApplication),
+}
+
+/// One point on the Hoek–Brown envelope, in the units of `ucs`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HoekBrownPoint {
+    pub sig3: f64,
+    pub sig1: f64,
+    /// Normal stress on the failure plane.
+    pub normal_stress: f64,
+    /// Shear strength on the failure plane.
+    pub shear_stress: f64,
+}
+
+/// The σ1–σ3 envelope and the matching τ–σn envelope from the tensile strength upwards.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoekBrownEnvelope {
+    pub points: Vec<HoekBrownPoint>,
+}
+
+impl HoekBrownEnvelope {
+    pub fn principal_stresses(&self) -> Vec<[f64; 2]> {
+        self.points.iter().map(|p| [p.sig3, p.sig1]).collect()
+    }
+
+    pub fn shear_strength(&self) -> Vec<[f64; 2]> {
+        self.points
+            .iter()
+            .map(|p| [p.normal_stress, p.shear_stress])
+            .collect()
+    }
+}
+
+/// A triaxial or uniaxial (σ3 = 0) test result at failure.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TriaxialResult {
+    pub sig3: f64,
+    pub sig1: f64,
+}
+
+/// Intact rock parameters fitted to triaxial results.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HoekBrownFit {
+    pub ucs: f64,
+    pub mi: f64,
+    /// Coefficient of determination of the linearised fit.
+    pub r_squared: f64,
+}
+
+impl SoilParams {
+    /// Major principal stress at failure for a minor principal stress `sig3`.
+    pub fn hb_sigma1(&self, sig3: f64) -> Result<f64, GroundModelError> {
+        let ucs = self.required_nonzero_ucs()?;
+        let (mb, s, a) = (self.mb()?, self.s()?, self.a()?);
+        let base = mb * sig3 / ucs + s;
+        if base < 0.0 {
+            return Err(self.calculation_error("sig3 is below the rock mass tensile strength"));
+        }
+        Ok(sig3 + ucs * base.powf(a))
+    }
+
+    /// Rock mass tensile strength `−s σci / mb`, as a negative stress.
+    pub fn hb_tensile_strength(&self) -> Result<f64, GroundModelError> {
+        let ucs = self.required_nonzero_ucs()?;
+        Ok(-self.s()? * ucs / self.mb()?)
+    }
+
+    /// Global rock mass strength σ′cm (Hoek et al., 2002).
+    pub fn rock_mass_strength(&self) -> Result<f64, GroundModelError> {
+        let ucs = self.required_nonzero_ucs()?;
+        let (mb, s, a) = (self.mb()?, self.s()?, self.a()?);
+        Ok(
+            ucs * (mb + 4.0 * s - a * (mb - 8.0 * s)) * (mb / 4.0 + s).powf(a - 1.0)
+                / (2.0 * (1.0 + a) * (2.0 + a)),
+        )
+    }
+
+    /// Upper limit of σ3 for fitting equivalent Mohr-Coulomb parameters (Hoek et al., 2002).
+    /// `ucs` must be in kPa to match the overburden `unit_weight × depth`.
+    pub fn hb_sig3_max(&self, application: HoekBrownApplication) -> Result<f64, GroundModelError> {
+        let (height, factor, exponent) = match application {
+            HoekBrownApplication::Tunnel { depth } => (depth, 0.47, -0.94),
+            HoekBrownApplication::Slope { height } => (height, 0.72, -0.91),
+        };
+        let overburden = self.unit_weight * height;
+        if overburden <= 0.0 {
+            return Err(GroundModelError::InvalidParameter {
+                reference: self.reference.clone(),
+                name: "unit_weight",
+                message: "unit weight and depth must be positive to select sig3max".to_string(),
+            });
+        }
+        let sigma_cm = self.rock_mass_strength()?;
+        Ok(factor * sigma_cm * (sigma_cm / overburden).powf(exponent))
+    }
+
+    /// [`SoilParams::convert_equivalent_rock`] with σ3max chosen for the application.
+    pub fn convert_equivalent_rock_for(
+        &self,
+        application: HoekBrownApplication,
+    ) -> Result<SoilParams, GroundModelError> {
+        self.convert_equivalent_rock(self.hb_sig3_max(application)?)
+    }
+
+    /// `steps + 1` points from the tensile strength to `sig3_max`. Shear strengths use
+    /// Balmer's (1952) relations for the tangent to each Mohr circle.
+    pub fn hoek_brown_envelope(
+        &self,
+        sig3_max: f64,
+        steps: usize,
+    ) -> Result<HoekBrownEnvelope, GroundModelError> {
+        let ucs = self.required_nonzero_ucs()?;
+        let (mb, s, a) = (self.mb()?, self.s()?, self.a()?);
+        let tensile = self.hb_tensile_strength()?;
+        if steps == 0 || sig3_max <= tensile {
+            return Err(self.calculation_error(
+                "envelope needs at least one step and sig3_max above the tensile strength",
+            ));
+        }
+
+        let points = (0..=steps)
+            .map(|i| {
+                let sig3 = tensile + (sig3_max - tensile) * i as f64 / steps as f64;
+                let base = (mb * sig3 / ucs + s).max(0.0);
+                let sig1 = sig3 + ucs * base.powf(a);
+                if base == 0.0 {
+                    return HoekBrownPoint {
+                        sig3,
+                        sig1,
+                        normal_stress: sig3,
+                        shear_stress: 0.0,
+                    };
+                }
+                let slope = 1.0 + a * mb * base.powf(a - 1.0);
+                HoekBrownPoint {
+                    sig3,
+                    sig1,
+                    normal_stress: (sig1 + sig3) / 2.0
+                        - (sig1 - sig3) / 2.0 * (slope - 1.0) / (slope + 1.0),
+                    shear_stress: (sig1 - sig3) * slope.sqrt() / (slope + 1.0),
+                }
+            })
+            .collect();
+        Ok(HoekBrownEnvelope { points })
+    }
+}
+
+impl GroundModel {
+    /// Returns a copy of the model in which every rock layer carries equivalent φ′ and c′
+    /// from [`SoilParams::convert_equivalent_rock`] and the `rock_e_val` modulus. Layers are
+    /// taken from [`GroundModel::stratigraphy`], so inferred bases and clipped overlaps set
+    /// the mid-layer level. A rock unit that appears in several layers is split into one
+    /// parameter set per layer, named `"<unit> <n>"` from the top down (skipping names that
+    /// are already used), since each layer has its own σ3. Design case parameter sets are
+    /// converted and renamed the same way, with σ3 from the case's own parameters.
+    pub fn to_equivalent_mohr_coulomb(
+        &self,
+        confinement: RockConfinement,
+    ) -> Result<GroundModel, GroundModelError> {
+        let stratigraphy = self.stratigraphy();
+        let rock_layers: Vec<&LayerInterval> = stratigraphy
+            .layers
+            .iter()
+            .filter(|interval| {
+                self.get_soil_params(&interval.unit_reference)
+                    .is_some_and(|params| params.behaviour == SoilType::Rock)
+            })
+            .collect();
+
+        // The unit reference each rock layer takes
+        let mut taken: BTreeSet<String> = self
+            .soil_params
+            .iter()
+            .chain(self.cases.values().flatten())
+            .map(|params| params.reference.clone())
+            .chain(
+                self.soil_layers
+                    .iter()
+                    .map(|layer| layer.unit_reference.clone()),
+            )
+            .collect();
+        let mut names: Vec<String> = Vec::new();
+        let mut seen: BTreeMap<&str, usize> = BTreeMap::new();
+        for interval in &rock_layers {
+            let reference = interval.unit_reference.as_str();
+            let shared = rock_layers
+                .iter()
+                .filter(|other| other.unit_reference == reference)
+                .count();
+            if shared == 1 {
+                names.push(reference.to_string());
+                continue;
+            }
+            let n = seen.entry(reference).or_insert(0);
+            let name = loop {
+                *n += 1;
+                let name = format!("{} {}", reference, n);
+                if taken.insert(name.clone()) {
+                    break name;
+                }
+            };
+            names.push(name);
+        }
+
+        let case_models = self
+            .cases
+            .keys()
+            .map(|case_id| Ok((case_id, self.for_case(case_id)?)))
+            .collect::<Result<Vec<_>, GroundModelError>>()?;
+
+        let mut model = self.clone();
+        for (interval, name) in rock_layers.iter().zip(&names) {
+            let reference = interval.unit_reference.as_str();
+            if let Some(params) = self.get_soil_params(reference) {
+                let converted =
+                    self.equivalent_layer_params(params, interval, name, confinement)?;
+                replace_params(&mut model.soil_params, converted);
+            }
+            for (case_id, case_model) in &case_models {
+                let Some(params) = self.cases[*case_id]
+                    .iter()
+                    .find(|params| params.reference == reference)
+                else {
+                    continue;
+                };
+                let converted =
+                    case_model.equivalent_layer_params(params, interval, name, confinement)?;
+                let case = model.cases.get_mut(*case_id).expect("case is in the model");
+                replace_params(case, converted);
+            }
+            model.soil_layers[interval.index].unit_reference = name.clone();
+        }
+
+        // Units split into one parameter set per layer are no longer referenced
+        let split: Vec<&str> = rock_layers
+            .iter()
+            .map(|interval| interval.unit_reference.as_str())
+            .filter(|reference| {
+                !model
+                    .soil_layers
+                    .iter()
+                    .any(|layer| layer.unit_reference == *reference)
+            })
+            .collect();
+        model
+            .soil_params
+            .retain(|params| !split.contains(&params.reference.as_str()));
+        for case in model.cases.values_mut() {
+            case.retain(|params| !split.contains(&params.reference.as_str()));
+        }
+        Ok(model)
+    }
+
+    /// `params` converted for the layer `interval`, under this model's stresses, and renamed
+    /// `name`.
+    fn equivalent_layer_params(
+        &self,
+        params: &SoilParams,
+        interval: &LayerInterval,
+        name: &str,
+        confinement: RockConfinement,
+    ) -> Result<SoilParams, GroundModelError> {
+        let sig3 = match confinement {
+            RockConfinement::InSitu => {
+                let base = interval
+                    .base
+                    .or(self.rigid_boundary.filter(|base| *base < interval.top));
+                let level = base.map_or(interval.top, |base| (interval.top + base) / 2.0);
+                let vertical = self.stress_profile(&[level])[0].effective;
+                let k0 = params.poissons_ratio / (1.0 - params.poissons_ratio);
+                vertical.min(k0 * vertical).max(0.0)
+            }
+            RockConfinement::Sig3Max(application) => params.hb_sig3_max(application)?,
+        };
+        let mut converted = params.convert_equivalent_rock(sig3)?;
+        converted.reference = name.to_string();
+        Ok(converted)
+    }
+}
+
+/// Replaces the parameter set with the same reference as `params`, or adds it.
+fn replace_params(sets: &mut Vec<SoilParams>, params: SoilParams) {
+    match sets
+        .iter_mut()
+        .find(|existing| existing.reference == params.reference)
+    {
+        Some(existing) => *existing = params,
+        None => sets.push(params),
+    }
+}
+
+/// Least-squares fit of intact `ucs` and `mi` (s = 1, a = 0.5) to triaxial results, from the
+/// linear form `(σ1 − σ3)² = σci² + mi σci σ3` (Hoek and Brown, 1997).
+pub fn fit_hoek_brown(results: &[TriaxialResult]) -> Result<HoekBrownFit, GroundModelError> {
+    let fit_error = |message: &str| GroundModelError::Calculation {
+        reference: "triaxial results".to_string(),
+        message: message.to_string(),
+    };
+
+    let n = results.len() as f64;
+    let (mut sx, mut sy, mut sxx, mut sxy, mut syy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for result in results {
+        let x = result.sig3;
+        let y = (result.sig1 - result.sig3).powi(2);
+        sx += x;
+        sy += y;
+        sxx += x * x;
+        sxy += x * y;
+        syy += y * y;
+    }
+
+    let sxx_c = sxx - sx * sx / n;
+    if results.len() < 2 || sxx_c <= 0.0 {
+        return Err(fit_error("at least two different sig3 values are needed"));
+    }
+    let sxy_c = sxy - sx * sy / n;
+    let slope = sxy_c / sxx_c;
+    let ucs_squared = sy / n - slope * sx / n;
+    if ucs_squared <= 0.0 {
+        return Err(fit_error("the fitted UCS is not positive"));
+    }
+    let ucs = ucs_squared.sqrt();
+    let syy_c = syy - sy * sy / n;
+    let r_squared = if syy_c > 0.0 {
+        sxy_c * sxy_c / (sxx_c * syy_c)
+    } else {
+        1.0
+    };
+
+    Ok(HoekBrownFit {
+        ucs,
+        mi: slope / ucs,
+        r_squared,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{rock, soil};
+    use crate::SoilLayer;
+
+    #[test]
+    fn envelope_for_intact_rock() {
+        let params = rock(100.0, 50000.0, 10.0);
+        assert!((params.a().unwrap() - 0.5).abs() < 1e-12);
+        assert!((params.hb_sigma1(0.0).unwrap() - 50000.0).abs() < 1e-6);
+        assert!((params.hb_tensile_strength().unwrap() + 5000.0).abs() < 1e-6);
+
+        let envelope = params.hoek_brown_envelope(20000.0, 25).unwrap();
+        assert_eq!(envelope.points.len(), 26);
+        assert_eq!(envelope.points[0].shear_stress, 0.0);
+        for p in &envelope.points[1..] {
+            // Each (σn, τ) lies on the Mohr circle for (σ3, σ1)
+            let centre = (p.sig1 + p.sig3) / 2.0;
+            let radius = (p.sig1 - p.sig3) / 2.0;
+            let distance = ((p.normal_stress - centre).powi(2) + p.shear_stress.powi(2)).sqrt();
+            assert!((distance - radius).abs() < 1e-6 * radius);
+        }
+        let sig1: Vec<f64> = envelope.principal_stresses().iter().map(|p| p[1]).collect();
+        assert!(sig1.windows(2).all(|w| w[1] > w[0]));
+
+        assert!(params.hoek_brown_envelope(-6000.0, 10).is_err());
+    }
+
+    #[test]
+    fn sig3_max_for_tunnels_and_slopes() {
+        let params = rock(50.0, 30000.0, 12.0);
+        let sigma_cm = params.rock_mass_strength().unwrap();
+        assert!(sigma_cm > 0.0 && sigma_cm < 30000.0);
+
+        let tunnel = params
+            .hb_sig3_max(HoekBrownApplication::Tunnel { depth: 100.0 })
+            .unwrap();
+        let expected = 0.47 * sigma_cm * (sigma_cm / 2500.0).powf(-0.94);
+        assert!((tunnel - expected).abs() < 1e-6);
+
+        let slope = params
+            .hb_sig3_max(HoekBrownApplication::Slope { height: 20.0 })
+            .unwrap();
+        let converted = params
+            .convert_equivalent_rock_for(HoekBrownApplication::Slope { height: 20.0 })
+            .unwrap();
+        assert_eq!(
+            converted.phi_prime,
+            Some(params.hb_equiv_phi_ang(slope).unwrap())
+        );
+        assert!(params
+            .hb_sig3_max(HoekBrownApplication::Slope { height: 0.0 })
+            .is_err());
+    }
+
+    #[test]
+    fn rock_layers_convert_to_equivalent_mohr_coulomb() {
+        let mut clay = soil("Clay", SoilType::Cohesive, 20.0);
+        clay.cu = Some(80.0);
+        let mut mudstone = rock(40.0, 5000.0, 9.0);
+        mudstone.reference = "Mudstone".to_string();
+        let sandstone = rock(60.0, 40000.0, 17.0);
+
+        let mut layers = vec![
+            SoilLayer::new(0.0, -5.0, "L1".to_string()),
+            SoilLayer::new(-5.0, -10.0, "L2".to_string()),
+            SoilLayer::new(-10.0, -20.0, "L3".to_string()),
+            SoilLayer::new(-20.0, -40.0, "L4".to_string()),
+        ];
+        for (layer, unit) in layers
+            .iter_mut()
+            .zip(["Clay", "Sandstone", "Mudstone", "Sandstone"])
+        {
+            layer.unit_reference = unit.to_string();
+        }
+        let mut model = GroundModel::new(layers, vec![clay, mudstone.clone(), sandstone.clone()]);
+        model.groundwater = 0.0;
+
+        let converted = model
+            .to_equivalent_mohr_coulomb(RockConfinement::InSitu)
+            .unwrap();
+        assert!(converted
+            .get_soil_params("Clay")
+            .unwrap()
+            .phi_prime
+            .is_none());
+        assert!(converted.get_soil_params("Sandstone").is_none());
+        let units: Vec<&str> = converted
+            .soil_layers
+            .iter()
+            .map(|layer| layer.unit_reference.as_str())
+            .collect();
+        assert_eq!(units, ["Clay", "Sandstone 1", "Mudstone", "Sandstone 2"]);
+
+        // Mudstone mid-level -15 m: σ′v through 5 m of clay and 10 m of rock, σ3 = ν/(1 − ν) σ′v
+        let sig3 = (5.0 * (20.0 - 10.0) + 10.0 * (25.0 - 10.0)) / 3.0;
+        let mudstone_mc = converted.get_soil_params("Mudstone").unwrap();
+        assert!(
+            (mudstone_mc.phi_prime.unwrap() - mudstone.hb_equiv_phi_ang(sig3).unwrap()).abs()
+                < 1e-6
+        );
+        assert_eq!(mudstone_mc.youngs_modulus, mudstone.rock_e_val().unwrap());
+
+        let upper = converted.get_soil_params("Sandstone 1").unwrap();
+        let lower = converted.get_soil_params("Sandstone 2").unwrap();
+        assert!(lower.c_prime.unwrap() > upper.c_prime.unwrap());
+
+        let slope = model
+            .to_equivalent_mohr_coulomb(RockConfinement::Sig3Max(HoekBrownApplication::Slope {
+                height: 30.0,
+            }))
+            .unwrap();
+        let expected = sandstone
+            .convert_equivalent_rock_for(HoekBrownApplication::Slope { height: 30.0 })
+            .unwrap();
+        assert_eq!(
+            slope.get_soil_params("Sandstone 2").unwrap().c_prime,
+            expected.c_prime
+        );
+    }
+
+    #[test]
+    fn split_rock_units_convert_cases_and_avoid_existing_names() {
+        let sandstone = rock(60.0, 40000.0, 17.0);
+        let lower_bound = SoilParams {
+            case_id: Some("Lower bound".to_string()),
+            gsi: Some(45.0),
+            ..sandstone.clone()
+        };
+        let existing = soil("Sandstone 1", SoilType::Granular, 20.0);
+
+        let layer = |top: f64, base: Option<f64>, unit: &str| SoilLayer {
+            top_level: top,
+            base_level: base,
+            unit_reference: unit.to_string(),
+            ..SoilLayer::default()
+        };
+        let mut model = GroundModel::new(
+            vec![
+                layer(0.0, Some(-5.0), "Clay"),
+                // No base: it ends at the top of the mudstone
+                layer(-5.0, None, "Sandstone"),
+                layer(-10.0, Some(-20.0), "Mudstone"),
+                layer(-20.0, Some(-40.0), "Sandstone"),
+            ],
+            vec![
+                soil("Clay", SoilType::Cohesive, 20.0),
+                sandstone.clone(),
+                SoilParams {
+                    reference: "Mudstone".to_string(),
+                    ..rock(40.0, 5000.0, 9.0)
+                },
+                existing.clone(),
+            ],
+        );
+        model.groundwater = 0.0;
+        model
+            .cases
+            .insert("Lower bound".to_string(), vec![lower_bound.clone()]);
+
+        let converted = model
+            .to_equivalent_mohr_coulomb(RockConfinement::InSitu)
+            .unwrap();
+        let units: Vec<&str> = converted
+            .soil_layers
+            .iter()
+            .map(|layer| layer.unit_reference.as_str())
+            .collect();
+        assert_eq!(units, ["Clay", "Sandstone 2", "Mudstone", "Sandstone 3"]);
+        assert_eq!(
+            converted.get_soil_params("Sandstone 1").unwrap().behaviour,
+            existing.behaviour
+        );
+
+        // The upper sandstone's base is inferred, so σ3 is taken at -7.5 m
+        let sig3 = (5.0 * (20.0 - 10.0) + 2.5 * (25.0 - 10.0)) / 3.0;
+        let upper = converted.get_soil_params("Sandstone 2").unwrap();
+        assert!(
+            (upper.phi_prime.unwrap() - sandstone.hb_equiv_phi_ang(sig3).unwrap()).abs() < 1e-9
+        );
+
+        let case: Vec<&str> = converted.cases["Lower bound"]
+            .iter()
+            .map(|params| params.reference.as_str())
+            .collect();
+        assert_eq!(case, ["Sandstone 2", "Sandstone 3"]);
+        let lower = converted.for_case("Lower bound").unwrap();
+        let upper_case = lower.get_soil_params("Sandstone 2").unwrap();
+        assert_eq!(upper_case.case_id.as_deref(), Some("Lower bound"));
+        assert!(
+            (upper_case.phi_prime.unwrap() - lower_bound.hb_equiv_phi_ang(sig3).unwrap()).abs()
+                < 1e-9
+        );
+    }
+
+    #[test]
+    fn fit_recovers_intact_parameters() {
+        let intact = rock(100.0, 80000.0, 15.0);
+        let results: Vec<TriaxialResult> = [0.0, 5000.0, 10000.0, 20000.0, 40000.0]
+            .iter()
+            .map(|&sig3| TriaxialResult {
+                sig3,
+                sig1: intact.hb_sigma1(sig3).unwrap(),
+            })
+            .collect();
+
+        let fit = fit_hoek_brown(&results).unwrap();
+        assert!((fit.ucs - 80000.0).abs() < 1e-3);
+        assert!((fit.mi - 15.0).abs() < 1e-9);
+        assert!((fit.r_squared - 1.0).abs() < 1e-9);
+
+        assert!(fit_hoek_brown(&results[..1]).is_err());
+    }
+}
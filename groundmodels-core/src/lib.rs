@@ -1,13 +1,36 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::Write;
 pub mod agsi;
+pub mod agsi_io;
+pub mod bearing;
+mod config;
+pub mod construction;
+pub mod design_approach;
+pub mod earth_pressure;
+pub mod error;
+pub mod groundwater;
+pub mod hoek_brown;
+pub mod loads;
+pub mod piles;
+pub mod settlement;
 pub mod soil_description;
+pub mod stratigraphy;
+pub mod stress_history;
 pub mod strip_log;
+pub mod units;
+pub mod validation;
 
 #[cfg(test)]
 mod soil_description_tests;
 use crate::agsi::AgsiDataParameterValue;
+use crate::agsi_io::load_agsi;
+use crate::earth_pressure::WallGeometry;
+pub use crate::error::GroundModelError;
+use crate::groundwater::{PorePressureRegime, DEFAULT_UNIT_WEIGHT_WATER};
+use crate::loads::{Load, StressDistribution};
+use crate::units::{Stress, UnitTable};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ConvertType {
@@ -15,12 +38,16 @@ pub enum ConvertType {
     GroundModel,
 }
 
-impl ConvertType {
-    pub fn from_str(s: &str) -> Result<Self, &'static str> {
+impl std::str::FromStr for ConvertType {
+    type Err = GroundModelError;
+
+    fn from_str(s: &str) -> Result<Self, GroundModelError> {
         match s.to_lowercase().as_str() {
             "soilparams" => Ok(ConvertType::SoilParams),
             "groundmodel" => Ok(ConvertType::GroundModel),
-            _ => Err("Invalid convert type. Use 'soilparams' or 'groundmodel'"),
+            _ => Err(GroundModelError::InvalidConvertType {
+                value: s.to_string(),
+            }),
         }
     }
 }
@@ -30,51 +57,12 @@ pub fn convert_agsi_file(
     convert_type: ConvertType,
     output_path: Option<&str>,
 ) -> Result<String, Box<dyn std::error::Error>> {
-    let text = fs::read_to_string(file_path)?;
-    let agsi: serde_json::Value = serde_json::from_str(&text)?;
+    let agsi = load_agsi(file_path)?;
+    let ground_model = GroundModel::try_from(&agsi)?;
 
     let output_json = match convert_type {
-        ConvertType::GroundModel => {
-            let ground_model = GroundModel::from_agsi_file(&agsi);
-            serde_json::to_string_pretty(&ground_model)?
-        }
-        ConvertType::SoilParams => {
-            let mut soil_params = Vec::new();
-
-            if let Some(agsi_models) = agsi["agsiModel"].as_array() {
-                if let Some(first_model) = agsi_models.get(0) {
-                    if let Some(elements) = first_model["agsiModelElement"].as_array() {
-                        for element in elements {
-                            if let Some(param_values) = element["agsiDataParameterValue"].as_array()
-                            {
-                                let params_data: Vec<AgsiDataParameterValue> = param_values
-                                    .iter()
-                                    .filter_map(|p| {
-                                        Some(AgsiDataParameterValue {
-                                            code_id: p["codeID"].as_str()?.parse().ok()?,
-                                            case_id: None,
-                                            data_id: None,
-                                            remarks: None,
-                                            value_numeric: p["valueNumeric"].as_f64(),
-                                            value_profile: None,
-                                            value_profile_ind_var_code_id: None,
-                                            value_text: None,
-                                        })
-                                    })
-                                    .collect();
-
-                                if !params_data.is_empty() {
-                                    soil_params
-                                        .push(SoilParams::from_agsi_data_parameters(&params_data));
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-
-            serde_json::to_string_pretty(&soil_params)?
-        }
+        ConvertType::GroundModel => serde_json::to_string_pretty(&ground_model)?,
+        ConvertType::SoilParams => serde_json::to_string_pretty(&ground_model.soil_params)?,
     };
 
     if let Some(output_file) = output_path {
@@ -92,6 +80,70 @@ pub fn convert_agsi_file(
 pub struct AdvancedParameter {
     pub name: String,
     pub value: f64,
+    /// Unit declared for this code in the AGSi file it was read from. The value is not
+    /// converted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub units: Option<String>,
+}
+
+/// Independent variable of a [`ParameterProfile`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ProfileVariable {
+    /// Depth below the top of the ground model (m).
+    Depth,
+    /// Elevation (m).
+    Elevation,
+}
+
+/// A parameter that varies with depth or elevation, taken from an AGSi `valueProfile`.
+/// Values are interpolated linearly between points and held constant beyond the ends.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParameterProfile {
+    /// AGSi `codeID` of the parameter, e.g. `UndrainedShearStrength`.
+    pub code_id: String,
+    pub variable: ProfileVariable,
+    /// `[position, value]` pairs ordered by position.
+    pub points: Vec<[f64; 2]>,
+}
+
+impl ParameterProfile {
+    pub fn new(code_id: String, variable: ProfileVariable, mut points: Vec<[f64; 2]>) -> Self {
+        points.sort_by(|a, b| a[0].partial_cmp(&b[0]).unwrap_or(std::cmp::Ordering::Equal));
+        ParameterProfile {
+            code_id,
+            variable,
+            points,
+        }
+    }
+
+    pub fn value_at(&self, position: f64) -> Option<f64> {
+        interpolate(&self.points, position)
+    }
+}
+
+/// Linear interpolation through `[x, y]` points sorted by `x`, held constant beyond the ends.
+pub(crate) fn interpolate(points: &[[f64; 2]], position: f64) -> Option<f64> {
+    let first = points.first()?;
+    let last = points.last()?;
+    if position <= first[0] {
+        return Some(first[1]);
+    }
+    if position >= last[0] {
+        return Some(last[1]);
+    }
+
+    points.windows(2).find_map(|pair| {
+        let ([x0, y0], [x1, y1]) = (pair[0], pair[1]);
+        if position >= x0 && position <= x1 {
+            if x1 == x0 {
+                Some(y1)
+            } else {
+                Some(y0 + (y1 - y0) * (position - x0) / (x1 - x0))
+            }
+        } else {
+            None
+        }
+    })
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -101,12 +153,34 @@ pub enum SoilType {
     Rock,
 }
 
+/// Whether a calculation uses effective or total stress parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DrainageCondition {
+    /// Effective stress parameters `phi_prime` and `c_prime`.
+    Drained,
+    /// Total stress with `cu`.
+    Undrained,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PartialFactors {
     pub gamma_phi: f64,
     pub gamma_c: f64,
     pub gamma_gamma: f64,
     pub gamma_cu: f64,
+    /// Divides `ucs` (γqu).
+    #[serde(default = "unit_factor")]
+    pub gamma_ucs: f64,
+    /// Divides the Hoek–Brown `mi`, and so `mb`.
+    #[serde(default = "unit_factor")]
+    pub gamma_hb: f64,
+    /// Divides `youngs_modulus` and multiplies `mv`.
+    #[serde(default = "unit_factor")]
+    pub gamma_e: f64,
+}
+
+fn unit_factor() -> f64 {
+    1.0
 }
 
 impl PartialFactors {
@@ -116,6 +190,54 @@ impl PartialFactors {
             gamma_c,
             gamma_gamma,
             gamma_cu,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_rock(mut self, gamma_ucs: f64, gamma_hb: f64) -> Self {
+        self.gamma_ucs = gamma_ucs;
+        self.gamma_hb = gamma_hb;
+        self
+    }
+
+    pub fn with_modulus(mut self, gamma_e: f64) -> Self {
+        self.gamma_e = gamma_e;
+        self
+    }
+
+    /// Factor dividing the field mapped to an AGSi `codeID`, or 1.0 where none applies.
+    /// Angles are factored through `tan φ′`.
+    fn divisor(&self, code_id: &str) -> f64 {
+        match code_id {
+            "AngleFriction" | "EffectiveFrictionAngle" => self.gamma_phi,
+            "Cohesion" | "EffectiveCohesion" => self.gamma_c,
+            "UnitWeight" | "UnitWeightSaturated" => self.gamma_gamma,
+            "UndrainedShearStrength" => self.gamma_cu,
+            "UnconfinedCompressiveStrength" => self.gamma_ucs,
+            "HoekBrownParamMi" => self.gamma_hb,
+            "YoungsModulus" => self.gamma_e,
+            "ModulusOfVolumeCompressibility" | "ModulusOfVolumeCompressibilityRecompression" => {
+                1.0 / self.gamma_e
+            }
+            _ => 1.0,
+        }
+    }
+
+    /// Design value from the characteristic value of the field mapped to `code_id`.
+    fn factor(&self, code_id: &str, value: f64) -> f64 {
+        let divisor = self.divisor(code_id);
+        match code_id {
+            "AngleFriction" | "EffectiveFrictionAngle" => (value.tan() / divisor).atan(),
+            _ => value / divisor,
+        }
+    }
+
+    /// Characteristic value from the design value of the field mapped to `code_id`.
+    fn unfactor(&self, code_id: &str, value: f64) -> f64 {
+        let divisor = self.divisor(code_id);
+        match code_id {
+            "AngleFriction" | "EffectiveFrictionAngle" => (value.tan() * divisor).atan(),
+            _ => value * divisor,
         }
     }
 }
@@ -127,6 +249,9 @@ impl Default for PartialFactors {
             gamma_c: 1.0,
             gamma_gamma: 1.0,
             gamma_cu: 1.0,
+            gamma_ucs: 1.0,
+            gamma_hb: 1.0,
+            gamma_e: 1.0,
         }
     }
 }
@@ -134,22 +259,53 @@ impl Default for PartialFactors {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SoilParams {
     pub reference: String,
+    /// AGSi `caseID` these values belong to, or `None` for the base parameter set.
+    #[serde(default)]
+    pub case_id: Option<String>,
     pub behaviour: SoilType,
+    /// Effective angle of friction in radians.
     pub phi_prime: Option<f64>,
+    /// Effective cohesion in kPa.
     pub c_prime: Option<f64>,
+    /// Bulk unit weight in kN/m³.
     pub unit_weight: f64,
+    /// Unit weight below the water table. `unit_weight` is used when not set.
+    #[serde(default)]
+    pub saturated_unit_weight: Option<f64>,
+    /// Undrained shear strength in kPa.
     pub cu: Option<f64>,
+    /// Coefficient of volume compressibility in m²/kN.
     pub mv: f64,
+    /// Young's modulus in kPa.
     pub youngs_modulus: f64,
     pub poissons_ratio: f64,
+    /// Coefficient of consolidation in m²/year.
     pub coefficient_of_consolidation: f64,
+    /// Coefficient of volume compressibility for unloading and reloading below the
+    /// preconsolidation pressure, in m²/kN. `mv` is used when not set.
+    #[serde(default)]
+    pub mv_recompression: Option<f64>,
+    /// Overconsolidation ratio σ′p/σ′v0 (see [`stress_history`]).
+    #[serde(default)]
+    pub ocr: Option<f64>,
+    /// Pre-overburden pressure σ′p − σ′v0 in kPa.
+    #[serde(default)]
+    pub pop: Option<f64>,
+    /// Preconsolidation pressure σ′p in kPa.
+    #[serde(default)]
+    pub preconsolidation_pressure: Option<f64>,
     pub gsi: Option<f64>,
+    /// Unconfined compressive strength of intact rock in kPa.
     pub ucs: Option<f64>,
     pub mi: Option<f64>,
     pub disturbance: f64,
     pub advanced_parameters: Option<Vec<AdvancedParameter>>,
     pub factored: bool,
     pub factors: Option<PartialFactors>,
+    /// Parameters that vary with depth or elevation. The matching scalar field holds the
+    /// value at the top of the profile.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<ParameterProfile>,
 }
 
 impl SoilParams {
@@ -164,15 +320,21 @@ impl SoilParams {
     ) -> Self {
         SoilParams {
             reference,
+            case_id: None,
             behaviour,
             phi_prime: None,
             c_prime: None,
             unit_weight,
+            saturated_unit_weight: None,
             cu: None,
             mv,
             youngs_modulus,
             poissons_ratio,
             coefficient_of_consolidation,
+            mv_recompression: None,
+            ocr: None,
+            pop: None,
+            preconsolidation_pressure: None,
             gsi: None,
             ucs: None,
             mi: None,
@@ -180,74 +342,176 @@ impl SoilParams {
             advanced_parameters: None,
             factored: false,
             factors: None,
+            profiles: Vec::new(),
         }
     }
 
+    /// Reads AGSi parameter values given in the AGSi default units (degrees, kPa, kN/m³).
     pub fn from_agsi_data_parameters(data: &[AgsiDataParameterValue]) -> Self {
+        SoilParams::from_agsi_data_parameters_with_units(data, &UnitTable::default())
+    }
+
+    /// Reads AGSi parameter values, converting each from the unit `units` gives for its
+    /// `codeID` to the units of the `SoilParams` fields. Profile values are converted too.
+    pub fn from_agsi_data_parameters_with_units(
+        data: &[AgsiDataParameterValue],
+        units: &UnitTable,
+    ) -> Self {
         let mut sp = SoilParams::default();
 
         for item in data {
-            match item.code_id.as_str() {
-                "UnitWeight" => {
-                    sp.unit_weight = item.value_numeric.unwrap_or(0.0);
-                }
-                "AngleFriction" | "EffectiveFrictionAngle" => {
-                    sp.phi_prime = item.value_numeric;
-                }
-                "UndrainedShearStrength" => {
-                    if let Some(value) = item.value_numeric {
-                        if value > 0.0 {
-                            sp.cu = Some(value);
-                            sp.behaviour = SoilType::Cohesive;
-                        } else {
-                            sp.cu = Some(0.0);
+            let code_id = item.code_id.as_str();
+            let profile = item
+                .value_profile
+                .as_ref()
+                .filter(|profile| !profile.is_empty())
+                .map(|profile| {
+                    let variable = match item.value_profile_ind_var_code_id.as_deref() {
+                        Some(var) if var.to_lowercase().contains("elevation") => {
+                            ProfileVariable::Elevation
                         }
-                    } else {
-                        sp.cu = Some(0.0);
-                    }
-                }
-                "YoungsModulus" => {
-                    sp.youngs_modulus = item.value_numeric.unwrap_or(0.0);
-                }
-                "Cohesion" | "EffectiveCohesion" => {
-                    sp.c_prime = item.value_numeric;
-                }
-                "ModulusOfVolumeCompressibility" => {
-                    sp.mv = item.value_numeric.unwrap_or(0.0);
+                        _ => ProfileVariable::Depth,
+                    };
+                    let points = profile
+                        .iter()
+                        .map(|&[position, value]| [position, units.to_internal(code_id, value)])
+                        .collect();
+                    ParameterProfile::new(code_id.to_string(), variable, points)
+                });
+
+            let value = item
+                .value_numeric
+                .map(|value| units.to_internal(code_id, value));
+            let value = value.or_else(|| {
+                profile
+                    .as_ref()
+                    .and_then(|p| p.points.first())
+                    .map(|p| p[1])
+            });
+            sp.apply_agsi_value(code_id, value);
+            if let Some(unit) = units.declared_unit(code_id) {
+                let mut advanced = sp.advanced_parameters.iter_mut().flatten();
+                if let Some(advanced) = advanced.find(|p| p.name == code_id) {
+                    advanced.units = Some(unit.to_string());
                 }
-                "GeologicalStrengthIndex" => {
-                    sp.gsi = item.value_numeric;
-                }
-                "UnconfinedCompressiveStrength" => {
-                    sp.ucs = item.value_numeric;
-                    if item.value_numeric.is_some() {
-                        sp.behaviour = SoilType::Rock;
+            }
+
+            // A later value for the same code replaces any earlier profile
+            sp.profiles.retain(|p| p.code_id != code_id);
+            sp.profiles.extend(profile);
+        }
+
+        sp
+    }
+
+    /// Sets the field mapped to an AGSi `codeID`. `value` is in the field's unit (see
+    /// [`units`]), not the AGSi unit. Unrecognised codes are kept as advanced parameters.
+    pub fn apply_agsi_value(&mut self, code_id: &str, value: Option<f64>) {
+        match code_id {
+            "UnitWeight" => {
+                self.unit_weight = value.unwrap_or(0.0);
+            }
+            "UnitWeightSaturated" => {
+                self.saturated_unit_weight = value;
+            }
+            "AngleFriction" | "EffectiveFrictionAngle" => {
+                self.phi_prime = value;
+            }
+            "UndrainedShearStrength" => {
+                if let Some(value) = value {
+                    if value > 0.0 {
+                        self.cu = Some(value);
+                        self.behaviour = SoilType::Cohesive;
+                    } else {
+                        self.cu = Some(0.0);
                     }
+                } else {
+                    self.cu = Some(0.0);
                 }
-                "HoekBrownParamMi" => {
-                    sp.mi = item.value_numeric;
-                }
-                "Disturbance" => {
-                    sp.disturbance = item.value_numeric.unwrap_or(0.0);
+            }
+            "YoungsModulus" => {
+                self.youngs_modulus = value.unwrap_or(0.0);
+            }
+            "Cohesion" | "EffectiveCohesion" => {
+                self.c_prime = value;
+            }
+            "ModulusOfVolumeCompressibility" => {
+                self.mv = value.unwrap_or(0.0);
+            }
+            "PoissonsRatio" => {
+                self.poissons_ratio = value.unwrap_or(0.0);
+            }
+            "CoefficientOfConsolidation" => {
+                self.coefficient_of_consolidation = value.unwrap_or(0.0);
+            }
+            "ModulusOfVolumeCompressibilityRecompression" => {
+                self.mv_recompression = value;
+            }
+            "OverconsolidationRatio" => {
+                self.ocr = value;
+            }
+            "PreOverburdenPressure" => {
+                self.pop = value;
+            }
+            "PreconsolidationPressure" => {
+                self.preconsolidation_pressure = value;
+            }
+            "GeologicalStrengthIndex" => {
+                self.gsi = value;
+            }
+            "UnconfinedCompressiveStrength" => {
+                self.ucs = value;
+                if value.is_some() {
+                    self.behaviour = SoilType::Rock;
                 }
-                _ => {
-                    if sp.advanced_parameters.is_none() {
-                        sp.advanced_parameters = Some(Vec::new());
-                    }
-                    sp.advanced_parameters
-                        .as_mut()
-                        .unwrap()
-                        .push(AdvancedParameter {
-                            name: item.code_id.to_string(),
-                            value: item.value_numeric.unwrap_or(0.0),
-                        });
+            }
+            "HoekBrownParamMi" => {
+                self.mi = value;
+            }
+            "Disturbance" => {
+                self.disturbance = value.unwrap_or(0.0);
+            }
+            _ => {
+                let advanced = self.advanced_parameters.get_or_insert_with(Vec::new);
+                let value = value.unwrap_or(0.0);
+                match advanced.iter_mut().find(|p| p.name == code_id) {
+                    Some(existing) => existing.value = value,
+                    None => advanced.push(AdvancedParameter {
+                        name: code_id.to_string(),
+                        value,
+                        units: None,
+                    }),
                 }
             }
         }
+    }
 
-        sp
+    /// Returns a copy with every profiled parameter evaluated at the given depth below the
+    /// top of the model and elevation.
+    pub fn resolve_profiles(&self, depth: f64, level: f64) -> SoilParams {
+        let mut resolved = self.clone();
+        for profile in &self.profiles {
+            let position = match profile.variable {
+                ProfileVariable::Depth => depth,
+                ProfileVariable::Elevation => level,
+            };
+            if let Some(value) = profile.value_at(position) {
+                resolved.apply_agsi_value(&profile.code_id, Some(value));
+            }
+        }
+        resolved
+    }
+
+    /// Unit weight of saturated or unsaturated ground. Falls back to `unit_weight` when no
+    /// saturated value is set.
+    pub fn unit_weight_for(&self, saturated: bool) -> f64 {
+        match self.saturated_unit_weight {
+            Some(saturated_unit_weight) if saturated => saturated_unit_weight,
+            _ => self.unit_weight,
+        }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn with_all_fields(
         reference: String,
         behaviour: SoilType,
@@ -266,15 +530,21 @@ impl SoilParams {
     ) -> Self {
         SoilParams {
             reference,
+            case_id: None,
             behaviour,
             phi_prime,
             c_prime,
             unit_weight,
+            saturated_unit_weight: None,
             cu,
             mv,
             youngs_modulus,
             poissons_ratio,
             coefficient_of_consolidation,
+            mv_recompression: None,
+            ocr: None,
+            pop: None,
+            preconsolidation_pressure: None,
             gsi,
             ucs,
             mi,
@@ -282,123 +552,143 @@ impl SoilParams {
             advanced_parameters: None,
             factored: false,
             factors: None,
+            profiles: Vec::new(),
         }
     }
 
+    /// Design parameters from these characteristic ones. Profiled parameters are factored at
+    /// every profile point.
     pub fn apply_partial_factors(&self, pf: &PartialFactors) -> SoilParams {
-        let mut result = self.clone();
-
-        if let Some(phi) = self.phi_prime {
-            result.phi_prime = Some((phi.tan() / pf.gamma_phi).atan());
-        }
-
-        if let Some(c) = self.c_prime {
-            result.c_prime = Some(c / pf.gamma_c);
-        }
-
-        result.unit_weight = self.unit_weight / pf.gamma_gamma;
-
-        if let Some(cu_val) = self.cu {
-            result.cu = Some(cu_val / pf.gamma_cu);
-        }
-
+        let mut result = self.map_factored_values(|code_id, value| pf.factor(code_id, value));
         result.factored = true;
         result.factors = Some(pf.clone());
         result
     }
 
-    pub fn remove_partial_factors(&self) -> Result<SoilParams, &'static str> {
+    pub fn remove_partial_factors(&self) -> Result<SoilParams, GroundModelError> {
         let pf = self
             .factors
             .as_ref()
-            .ok_or("No factors stored on this instance")?;
-        let mut result = self.clone();
-
-        if let Some(phi) = self.phi_prime {
-            result.phi_prime = Some((phi.tan() * pf.gamma_phi).atan());
-        }
+            .ok_or_else(|| GroundModelError::NotFactored {
+                reference: self.reference.clone(),
+            })?;
+        let mut result = self.map_factored_values(|code_id, value| pf.unfactor(code_id, value));
+        result.factored = false;
+        result.factors = None;
+        Ok(result)
+    }
 
-        if let Some(c) = self.c_prime {
-            result.c_prime = Some(c * pf.gamma_c);
+    /// Copy with `f(code_id, value)` applied to every field a partial factor acts on and to
+    /// the points of any profile of those fields.
+    fn map_factored_values(&self, f: impl Fn(&str, f64) -> f64) -> SoilParams {
+        let mut result = self.clone();
+        result.phi_prime = self.phi_prime.map(|phi| f("AngleFriction", phi));
+        result.c_prime = self.c_prime.map(|c| f("Cohesion", c));
+        result.unit_weight = f("UnitWeight", self.unit_weight);
+        result.saturated_unit_weight = self
+            .saturated_unit_weight
+            .map(|gamma| f("UnitWeightSaturated", gamma));
+        result.cu = self.cu.map(|cu| f("UndrainedShearStrength", cu));
+        result.ucs = self.ucs.map(|ucs| f("UnconfinedCompressiveStrength", ucs));
+        result.mi = self.mi.map(|mi| f("HoekBrownParamMi", mi));
+        result.youngs_modulus = f("YoungsModulus", self.youngs_modulus);
+        result.mv = f("ModulusOfVolumeCompressibility", self.mv);
+        result.mv_recompression = self
+            .mv_recompression
+            .map(|mv| f("ModulusOfVolumeCompressibilityRecompression", mv));
+        for profile in &mut result.profiles {
+            for point in &mut profile.points {
+                point[1] = f(&profile.code_id, point[1]);
+            }
         }
+        result
+    }
 
-        result.unit_weight = self.unit_weight * pf.gamma_gamma;
+    fn required(&self, value: Option<f64>, name: &'static str) -> Result<f64, GroundModelError> {
+        value.ok_or_else(|| GroundModelError::MissingParameter {
+            reference: self.reference.clone(),
+            name,
+        })
+    }
 
-        if let Some(cu_val) = self.cu {
-            result.cu = Some(cu_val * pf.gamma_cu);
+    fn required_nonzero_ucs(&self) -> Result<f64, GroundModelError> {
+        let ucs = self.required(self.ucs, "ucs")?;
+        if ucs == 0.0 {
+            return Err(GroundModelError::InvalidParameter {
+                reference: self.reference.clone(),
+                name: "ucs",
+                message: "must be nonzero for Hoek-Brown conversion".to_string(),
+            });
         }
-
-        result.factored = false;
-        result.factors = None;
-        Ok(result)
+        Ok(ucs)
     }
 
-    fn coulomb_ka(&self, phi: f64, beta: f64) -> f64 {
-        let cos_beta = beta.cos();
-        let cos_phi = phi.cos();
-        let discriminant = cos_beta.powi(2) - cos_phi.powi(2);
-        let sqrt_disc = discriminant.sqrt();
-
-        (cos_beta - sqrt_disc) / (cos_beta + sqrt_disc)
+    fn calculation_error(&self, message: &str) -> GroundModelError {
+        GroundModelError::Calculation {
+            reference: self.reference.clone(),
+            message: message.to_string(),
+        }
     }
 
-    pub fn get_k_active(&self, slope: Option<f64>) -> Result<f64, &'static str> {
-        let phi = self.phi_prime.ok_or("Phi must be a value")?;
+    /// Rankine coefficient for level ground, or Coulomb for a smooth vertical wall retaining a
+    /// surface sloping at `slope` (radians).
+    pub fn get_k_active(&self, slope: Option<f64>) -> Result<f64, GroundModelError> {
+        let phi = self.required(self.phi_prime, "phi_prime")?;
 
         match slope {
             None => Ok((1.0 - phi.sin()) / (1.0 + phi.sin())),
-            Some(beta) => Ok(self.coulomb_ka(phi, beta)),
+            Some(beta) => self.coulomb_k_active(&WallGeometry::sloping(beta)),
         }
     }
 
-    pub fn get_k_passive(&self, slope: Option<f64>) -> Result<f64, &'static str> {
+    pub fn get_k_passive(&self, slope: Option<f64>) -> Result<f64, GroundModelError> {
         match slope {
             None => Ok(1.0 / self.get_k_active(None)?),
-            Some(_) => self.get_k_passive(None), // TODO: implement Coulomb method for passive
+            Some(beta) => self.coulomb_k_passive(&WallGeometry::sloping(beta)),
         }
     }
 
-    pub fn k0(&self) -> Result<f64, &'static str> {
-        let phi = self.phi_prime.ok_or("Phi must be a value")?;
+    pub fn k0(&self) -> Result<f64, GroundModelError> {
+        let phi = self.required(self.phi_prime, "phi_prime")?;
         Ok(1.0 - phi.sin())
     }
 
-    pub fn mb(&self) -> Result<f64, &'static str> {
-        let mi = self
-            .mi
-            .ok_or("mi (rock mass factor) must be set for mb calculation")?;
-        let gsi = self
-            .gsi
-            .ok_or("gsi (geological strength index) must be set for mb calculation")?;
+    /// At-rest coefficient for an overconsolidated soil, `(1 − sinφ′)·OCR^sinφ′`.
+    pub fn k0_overconsolidated(&self, ocr: f64) -> Result<f64, GroundModelError> {
+        if ocr < 1.0 {
+            return Err(GroundModelError::InvalidParameter {
+                reference: self.reference.clone(),
+                name: "ocr",
+                message: "must be at least 1".to_string(),
+            });
+        }
+        let phi = self.required(self.phi_prime, "phi_prime")?;
+        Ok((1.0 - phi.sin()) * ocr.powf(phi.sin()))
+    }
+
+    pub fn mb(&self) -> Result<f64, GroundModelError> {
+        let mi = self.required(self.mi, "mi")?;
+        let gsi = self.required(self.gsi, "gsi")?;
 
         let exponent = (gsi - 100.0) / (28.0 - (14.0 * self.disturbance));
         Ok(mi * exponent.exp())
     }
 
-    pub fn s(&self) -> Result<f64, &'static str> {
-        let gsi = self
-            .gsi
-            .ok_or("gsi (geological strength index) must be set for s calculation")?;
+    pub fn s(&self) -> Result<f64, GroundModelError> {
+        let gsi = self.required(self.gsi, "gsi")?;
 
         let exponent = (gsi - 100.0) / (9.0 - (3.0 * self.disturbance));
         Ok(exponent.exp())
     }
 
-    pub fn a(&self) -> Result<f64, &'static str> {
-        let gsi = self
-            .gsi
-            .ok_or("gsi (geological strength index) must be set for a calculation")?;
+    pub fn a(&self) -> Result<f64, GroundModelError> {
+        let gsi = self.required(self.gsi, "gsi")?;
 
-        Ok(0.5 + ((-gsi / 15.0).exp() - (-20.0_f64 / 3.0).exp()))
+        Ok(0.5 + ((-gsi / 15.0).exp() - (-20.0_f64 / 3.0).exp()) / 6.0)
     }
 
-    fn hb_to_mc_conv(&self, sig3: f64) -> Result<f64, &'static str> {
-        let ucs = self.ucs.ok_or("ucs (unconfined compressive strength) must be set and nonzero for Hoek-Brown conversion")?;
-        if ucs == 0.0 {
-            return Err(
-                "ucs (unconfined compressive strength) must be nonzero for Hoek-Brown conversion",
-            );
-        }
+    fn hb_to_mc_conv(&self, sig3: f64) -> Result<f64, GroundModelError> {
+        let ucs = self.required_nonzero_ucs()?;
 
         let sig3n = sig3 / ucs;
         let first_bit = 6.0 * self.a()? * self.mb()?;
@@ -407,22 +697,24 @@ impl SoilParams {
         Ok(first_bit * second_bit)
     }
 
-    pub fn hb_equiv_phi_ang(&self, sig3: f64) -> Result<f64, &'static str> {
+    /// Equivalent φ′ (radians) fitted to the Hoek–Brown envelope up to σ3max = `sig3`
+    /// (Hoek et al., 2002). `sig3` is in the units of `ucs`.
+    pub fn hb_equiv_phi_ang(&self, sig3: f64) -> Result<f64, GroundModelError> {
         let top = self.hb_to_mc_conv(sig3)?;
         let bottom = (2.0 * (1.0 + self.a()?) * (2.0 + self.a()?)) + top;
 
         if bottom == 0.0 {
-            return Err("Denominator for equivalent phi angle calculation is zero");
+            return Err(
+                self.calculation_error("denominator for equivalent phi angle calculation is zero")
+            );
         }
 
-        Ok(top / bottom)
+        Ok((top / bottom).asin())
     }
 
-    pub fn hb_equiv_c_prime(&self, sig3: f64) -> Result<f64, &'static str> {
-        let ucs = self.ucs.ok_or("ucs (unconfined compressive strength) must be set and nonzero for equivalent cohesion calculation")?;
-        if ucs == 0.0 {
-            return Err("ucs must be nonzero for equivalent cohesion calculation");
-        }
+    /// Equivalent c′ fitted to the Hoek–Brown envelope up to σ3max = `sig3`.
+    pub fn hb_equiv_c_prime(&self, sig3: f64) -> Result<f64, GroundModelError> {
+        let ucs = self.required_nonzero_ucs()?;
 
         let sig3n = sig3 / ucs;
         let a_val = self.a()?;
@@ -430,58 +722,73 @@ impl SoilParams {
         let mb_val = self.mb()?;
 
         let first_brack = ((1.0 + (2.0 * a_val)) * s_val) + ((1.0 - a_val) * mb_val * sig3n);
-        let top = ucs * first_brack * ((s_val * mb_val * sig3n).powf(a_val - 1.0));
+        let top = ucs * first_brack * ((s_val + mb_val * sig3n).powf(a_val - 1.0));
         let denom = (1.0 + a_val) * (2.0 + a_val);
 
         if denom == 0.0 {
-            return Err("Denominator for equivalent cohesion calculation is zero");
+            return Err(
+                self.calculation_error("denominator for equivalent cohesion calculation is zero")
+            );
         }
 
         let sqrt_bit = 1.0 + (self.hb_to_mc_conv(sig3)? / denom);
         if sqrt_bit < 0.0 {
-            return Err("sqrtBit for equivalent cohesion calculation is negative");
+            return Err(self.calculation_error(
+                "square root term for equivalent cohesion calculation is negative",
+            ));
         }
 
         let bottom = denom * sqrt_bit.sqrt();
         if bottom == 0.0 {
-            return Err("Denominator for equivalent cohesion calculation is zero");
+            return Err(
+                self.calculation_error("denominator for equivalent cohesion calculation is zero")
+            );
         }
 
         Ok(top / bottom)
     }
 
-    pub fn rock_e_val(&self) -> Result<f64, &'static str> {
-        let ucs = self.ucs.ok_or(
-            "ucs (unconfined compressive strength) must be set for Young's Modulus calculation",
-        )?;
-        let gsi = self
-            .gsi
-            .ok_or("gsi (geological strength index) must be set for Young's Modulus calculation")?;
+    /// Rock mass modulus in kPa from Hoek, Carranza-Torres & Corkum (2002),
+    /// Em = (1 − D/2) √(σci/100) 10^((GSI − 10)/40) GPa, with σci in MPa and the root capped
+    /// at 1 for σci ≥ 100 MPa.
+    pub fn rock_e_val(&self) -> Result<f64, GroundModelError> {
+        let ucs = Stress::from_kpa(self.required(self.ucs, "ucs")?);
+        let gsi = self.required(self.gsi, "gsi")?;
 
-        let rock_val = if ucs < 100.0 { 1.0 } else { ucs / 100.0 };
+        let rock_val = (ucs.mpa() / 100.0).min(1.0);
 
-        Ok((1.0 - (self.disturbance / 2.0))
+        let modulus_gpa = (1.0 - (self.disturbance / 2.0))
             * rock_val.sqrt()
-            * (10.0_f64.powf((gsi - 10.0) / 40.0)))
-    }
-
-    pub fn convert_equivalent_rock(&self, sig3: f64) -> Result<SoilParams, &'static str> {
-        Ok(SoilParams::with_all_fields(
-            self.reference.clone(),
-            self.behaviour,
-            Some(self.hb_equiv_phi_ang(sig3)?),
-            Some(self.hb_equiv_c_prime(sig3)?),
-            self.unit_weight,
-            self.cu,
-            self.mv,
-            self.rock_e_val()?,
-            self.poissons_ratio,
-            self.coefficient_of_consolidation,
-            self.gsi,
-            self.ucs,
-            self.mi,
-            self.disturbance,
-        ))
+            * (10.0_f64.powf((gsi - 10.0) / 40.0));
+        Ok(Stress::from_mpa(modulus_gpa * 1000.0).kpa())
+    }
+
+    /// Equivalent Mohr-Coulomb parameters at `sig3`. Factored parameters are converted from
+    /// their characteristic values and the result carries the same factors, so φ′, c′ and E
+    /// are factored once and `remove_partial_factors` still reverses them.
+    pub fn convert_equivalent_rock(&self, sig3: f64) -> Result<SoilParams, GroundModelError> {
+        if let (true, Some(pf)) = (self.factored, &self.factors) {
+            let characteristic = self.remove_partial_factors()?;
+            return Ok(characteristic
+                .convert_equivalent_rock(sig3)?
+                .apply_partial_factors(pf));
+        }
+        let mut converted = self.clone();
+        converted.phi_prime = Some(self.hb_equiv_phi_ang(sig3)?);
+        converted.c_prime = Some(self.hb_equiv_c_prime(sig3)?);
+        converted.youngs_modulus = self.rock_e_val()?;
+        // Profiles of the replaced parameters would override them when resolved
+        converted.profiles.retain(|profile| {
+            !matches!(
+                profile.code_id.as_str(),
+                "AngleFriction"
+                    | "EffectiveFrictionAngle"
+                    | "Cohesion"
+                    | "EffectiveCohesion"
+                    | "YoungsModulus"
+            )
+        });
+        Ok(converted)
     }
 }
 
@@ -489,15 +796,21 @@ impl Default for SoilParams {
     fn default() -> Self {
         SoilParams {
             reference: String::new(),
+            case_id: None,
             behaviour: SoilType::Granular,
             phi_prime: None, // Set to None so error handling tests work
             c_prime: None,
             unit_weight: 0.0,
+            saturated_unit_weight: None,
             cu: None,
             mv: 0.0,
             youngs_modulus: 0.0,
             poissons_ratio: 0.0,
             coefficient_of_consolidation: 0.0,
+            mv_recompression: None,
+            ocr: None,
+            pop: None,
+            preconsolidation_pressure: None,
             gsi: None,
             ucs: None,
             mi: None,
@@ -505,6 +818,7 @@ impl Default for SoilParams {
             advanced_parameters: None,
             factored: false,
             factors: None,
+            profiles: Vec::new(),
         }
     }
 }
@@ -569,57 +883,16 @@ impl SoilLayer {
         }
     }
 
-    pub fn excavate_layer(&self, level: f64) -> (SoilLayer, bool) {
-        let base_level = self.base_level.unwrap_or(0.0);
-
-        if level < self.top_level && level > base_level {
-            // Excavation cuts through the layer
-            (
-                SoilLayer {
-                    unit_reference: self.unit_reference.clone(),
-                    top_level: level,
-                    base_level: self.base_level,
-                    base_unit_reference: self.base_unit_reference.clone(),
-                    typical_description: self.typical_description.clone(),
-                    geol_code: self.geol_code.clone(),
-                    reference: self.reference.clone(),
-                },
-                false,
-            )
-        } else if level > self.top_level {
-            // Excavation is above the layer - keep the layer
-            (self.clone(), false)
-        } else {
-            // Excavation removes the entire layer
-            (
-                SoilLayer {
-                    unit_reference: self.unit_reference.clone(),
-                    top_level: self.top_level,
-                    base_level: self.base_level,
-                    base_unit_reference: self.base_unit_reference.clone(),
-                    typical_description: self.typical_description.clone(),
-                    geol_code: "DELETE".to_string(),
-                    reference: self.reference.clone(),
-                },
-                true,
-            )
-        }
-    }
-
-    pub fn process_layer(&self, sig3: f64, soil_type: SoilType) -> SoilLayer {
-        match soil_type {
-            SoilType::Rock => {
-                // In a real implementation, you would apply rock conversion logic here
-                // For now, just return a clone
-                let mut processed = self.clone();
-                processed.typical_description = format!(
-                    "{} (processed with sig3: {})",
-                    processed.typical_description, sig3
-                );
-                processed
-            }
-            _ => self.clone(),
+    /// The part of the layer below `level`, or `None` when the excavation removes the whole
+    /// layer. A layer without a base level is cut off at `level` and kept.
+    pub fn excavate_layer(&self, level: f64) -> Option<SoilLayer> {
+        if self.base_level.is_some_and(|base| base >= level) {
+            return None;
         }
+        Some(SoilLayer {
+            top_level: self.top_level.min(level),
+            ..self.clone()
+        })
     }
 }
 
@@ -637,13 +910,39 @@ impl Default for SoilLayer {
     }
 }
 
+/// Vertical stresses at one level (kPa).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StressPoint {
+    pub level: f64,
+    pub total: f64,
+    pub pore_pressure: f64,
+    pub effective: f64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GroundModel {
     pub soil_layers: Vec<SoilLayer>,
     pub soil_params: Vec<SoilParams>,
     pub rigid_boundary: Option<f64>,
     pub groundwater: f64,
+    /// Uniform surcharge on the ground surface (kPa).
+    #[serde(default)]
+    pub surcharge: f64,
+    /// Perched, artesian or underdrained pore pressures. When `None`, pore pressures are
+    /// hydrostatic below `groundwater`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pore_pressure: Option<PorePressureRegime>,
+    /// Footing, embankment and other applied loads. Unlike `surcharge`, these only add stress
+    /// when a plan position is given.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub loads: Vec<Load>,
+    #[serde(default)]
+    pub stress_distribution: StressDistribution,
     pub reference: String,
+    /// Parameter sets for each AGSi design case, keyed by `caseID`. Each set only holds
+    /// the units that define values for that case.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub cases: BTreeMap<String, Vec<SoilParams>>,
 }
 
 impl GroundModel {
@@ -653,52 +952,15 @@ impl GroundModel {
             soil_params,
             rigid_boundary: None,
             groundwater: 0.0,
+            surcharge: 0.0,
+            pore_pressure: None,
+            loads: Vec::new(),
+            stress_distribution: StressDistribution::default(),
             reference: String::new(),
+            cases: BTreeMap::new(),
         }
     }
 
-    pub fn from_agsi_file(agsi_json: &serde_json::Value) -> Self {
-        let mut soil_params = Vec::new();
-
-        if let Some(agsi_models) = agsi_json["agsiModel"].as_array() {
-            if let Some(first_model) = agsi_models.get(0) {
-                if let Some(elements) = first_model["agsiModelElement"].as_array() {
-                    for element in elements {
-                        if let Some(param_values) = element["agsiDataParameterValue"].as_array() {
-                            let params_data: Vec<AgsiDataParameterValue> = param_values
-                                .iter()
-                                .filter_map(|p| {
-                                    Some(AgsiDataParameterValue {
-                                        code_id: p["codeID"].as_str()?.parse().ok()?,
-                                        case_id: None,
-                                        data_id: None,
-                                        remarks: None,
-                                        value_numeric: p["valueNumeric"].as_f64(),
-                                        value_profile: None,
-                                        value_profile_ind_var_code_id: None,
-                                        value_text: None,
-                                    })
-                                })
-                                .collect();
-
-                            if !params_data.is_empty() {
-                                let mut soil_param =
-                                    SoilParams::from_agsi_data_parameters(&params_data);
-                                soil_param.reference = element["elementName"]
-                                    .as_str()
-                                    .unwrap_or("unknown")
-                                    .to_string();
-                                soil_params.push(soil_param);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        GroundModel::new(Vec::new(), soil_params)
-    }
-
     pub fn with_all_fields(
         soil_layers: Vec<SoilLayer>,
         soil_params: Vec<SoilParams>,
@@ -711,29 +973,32 @@ impl GroundModel {
             soil_params,
             rigid_boundary,
             groundwater,
+            surcharge: 0.0,
+            pore_pressure: None,
+            loads: Vec::new(),
+            stress_distribution: StressDistribution::default(),
             reference,
+            cases: BTreeMap::new(),
         }
     }
 
+    /// Base of the lowest layer. When the lowest layer has no base its top is returned, and
+    /// a model without layers gives 0.0.
     pub fn get_base_level(&self) -> f64 {
-        let mut base = 10000.0;
-        for layer in &self.soil_layers {
-            let layer_base = layer.base_level.unwrap_or(0.0);
-            if base > layer_base {
-                base = layer_base;
-            }
-        }
-        base
+        let stratigraphy = self.stratigraphy();
+        stratigraphy
+            .base_level()
+            .or_else(|| stratigraphy.layers.last().map(|layer| layer.top))
+            .unwrap_or(0.0)
     }
 
+    /// Top of the highest layer, or 0.0 for a model without layers.
     pub fn get_top_level(&self) -> f64 {
-        let mut top = -10000.0;
-        for layer in &self.soil_layers {
-            if top < layer.top_level {
-                top = layer.top_level;
-            }
-        }
-        top
+        self.soil_layers
+            .iter()
+            .map(|layer| layer.top_level)
+            .reduce(f64::max)
+            .unwrap_or(0.0)
     }
 
     pub fn get_soil_params(&self, reference: &str) -> Option<&SoilParams> {
@@ -742,73 +1007,324 @@ impl GroundModel {
             .find(|param| param.reference == reference)
     }
 
-    pub fn get_params_at_level(&self, level: f64) -> Result<&SoilParams, &'static str> {
-        for layer in &self.soil_layers {
-            let base_level = layer.base_level.unwrap_or(0.0);
-            if layer.top_level >= level && level >= base_level {
-                let unit_ref = &layer.unit_reference;
-                for param in &self.soil_params {
-                    if param.reference == *unit_ref {
-                        return Ok(param);
-                    }
-                }
+    /// Returns the design case identifiers that have parameter sets in this model.
+    pub fn case_ids(&self) -> Vec<&str> {
+        self.cases.keys().map(String::as_str).collect()
+    }
+
+    /// Returns a copy of the model with the parameters of the given design case swapped in.
+    /// Units without values for the case keep their base parameters.
+    pub fn for_case(&self, case_id: &str) -> Result<GroundModel, GroundModelError> {
+        let case_params = self
+            .cases
+            .get(case_id)
+            .ok_or_else(|| GroundModelError::UnknownCase {
+                case_id: case_id.to_string(),
+            })?;
+        let mut model = self.clone();
+        for params in &mut model.soil_params {
+            if let Some(case) = case_params
+                .iter()
+                .find(|case| case.reference == params.reference)
+            {
+                *params = case.clone();
             }
         }
-        Err("Layer not present")
+        Ok(model)
     }
 
-    pub fn get_layer_at_level(&self, level: f64) -> Result<&SoilLayer, &'static str> {
-        for layer in &self.soil_layers {
-            let base_level = layer.base_level.unwrap_or(0.0);
-            if layer.top_level >= level && level >= base_level {
-                return Ok(layer);
-            }
+    /// Returns a copy of the model with partial factors applied to every unit that is not
+    /// already factored.
+    pub fn apply_partial_factors(&self, pf: &PartialFactors) -> GroundModel {
+        let mut model = self.clone();
+        for params in model.soil_params.iter_mut().filter(|p| !p.factored) {
+            *params = params.apply_partial_factors(pf);
+        }
+        model
+    }
+
+    /// Returns a copy of the model excavated to `level`: layers are cut off at that level,
+    /// layers wholly above it are removed and the surcharge is cleared. Layers without a base
+    /// level are removed when the base inferred by the [`stratigraphy`] is above `level`.
+    /// Depth profiles are anchored to the original surface (see [`GroundModel::place_fill`]).
+    pub fn excavate(&self, level: f64) -> GroundModel {
+        let stratigraphy = self.stratigraphy();
+        let mut model = self.clone();
+        model.anchor_depth_profiles();
+        model.soil_layers = self
+            .soil_layers
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| {
+                stratigraphy
+                    .layers
+                    .iter()
+                    .find(|layer| layer.index == *index)
+                    .is_none_or(|layer| layer.base.is_none_or(|base| base < level))
+            })
+            .filter_map(|(_, layer)| layer.excavate_layer(level))
+            .collect();
+        model.surcharge = 0.0;
+        model.keep_stress_history(self);
+        model
+    }
+
+    /// Returns a copy of the model with fill placed from the ground surface up to `level`.
+    /// The fill is a new layer named after `params.reference`, and `params` replaces any
+    /// parameter set with that reference.
+    ///
+    /// Depth profiles of the units already in the model are rewritten as elevation profiles
+    /// from the current surface, so moving the surface does not move them. Depth profiles in
+    /// `params` are measured from the top of the fill.
+    pub fn place_fill(
+        &self,
+        level: f64,
+        params: &SoilParams,
+    ) -> Result<GroundModel, GroundModelError> {
+        let surface = self.get_top_level();
+        if level <= surface {
+            return Err(GroundModelError::InvalidGeometry {
+                reference: params.reference.clone(),
+                message: format!(
+                    "fill level {} is not above the ground surface at {}",
+                    level, surface
+                ),
+            });
         }
-        Err("Layer not present")
-    }
 
-    pub fn get_soil_params_at_level(&self, level: f64) -> Option<&SoilParams> {
-        for (i, layer) in self.soil_layers.iter().enumerate() {
-            let base_level = layer.base_level.unwrap_or(0.0);
-            if level <= layer.top_level && level >= base_level {
-                return self.soil_params.get(i);
+        let mut model = self.clone();
+        model.anchor_depth_profiles();
+        model.soil_layers.insert(
+            0,
+            SoilLayer::with_all_fields(
+                params.reference.clone(),
+                level,
+                Some(surface),
+                None,
+                String::new(),
+                String::new(),
+            ),
+        );
+        match model
+            .soil_params
+            .iter_mut()
+            .find(|existing| existing.reference == params.reference)
+        {
+            Some(existing) => *existing = params.clone(),
+            None => model.soil_params.push(params.clone()),
+        }
+        model.keep_stress_history(self);
+        Ok(model)
+    }
+
+    /// Rewrites every depth profile, including those of the design cases, as an elevation
+    /// profile below the current ground surface.
+    fn anchor_depth_profiles(&mut self) {
+        let surface = self.get_top_level();
+        let params = self
+            .soil_params
+            .iter_mut()
+            .chain(self.cases.values_mut().flatten());
+        for profile in params.flat_map(|params| params.profiles.iter_mut()) {
+            if profile.variable == ProfileVariable::Depth {
+                *profile = ParameterProfile::new(
+                    std::mem::take(&mut profile.code_id),
+                    ProfileVariable::Elevation,
+                    profile
+                        .points
+                        .iter()
+                        .map(|&[depth, value]| [surface - depth, value])
+                        .collect(),
+                );
             }
         }
-        None
     }
 
-    fn get_unit_weight_at_level(&self, level: f64) -> Result<f64, &'static str> {
-        Ok(self.get_params_at_level(level)?.unit_weight)
+    /// Returns a copy of the model with a hydrostatic water table at `level`, replacing any
+    /// pore pressure regime.
+    pub fn dewater(&self, level: f64) -> GroundModel {
+        let mut model = self.clone();
+        model.groundwater = level;
+        model.pore_pressure = None;
+        model.keep_stress_history(self);
+        model
+    }
+
+    /// Returns the parameters of the layer at `level`, with any depth or elevation profiles
+    /// evaluated at that level. A level on an interface takes the upper layer (see
+    /// [`stratigraphy`]).
+    pub fn get_params_at_level(&self, level: f64) -> Result<SoilParams, GroundModelError> {
+        self.get_soil_params_at_level(level)
+            .map(|params| params.resolve_profiles(self.get_top_level() - level, level))
+            .ok_or(GroundModelError::LevelOutsideModel { level })
+    }
+
+    pub fn get_layer_at_level(&self, level: f64) -> Result<&SoilLayer, GroundModelError> {
+        self.stratigraphy()
+            .layer_at(level)
+            .map(|layer| &self.soil_layers[layer.index])
+            .ok_or(GroundModelError::LevelOutsideModel { level })
+    }
+
+    /// The parameter set of the layer at `level`, matched by unit reference.
+    pub fn get_soil_params_at_level(&self, level: f64) -> Option<&SoilParams> {
+        self.stratigraphy()
+            .layer_at(level)
+            .and_then(|layer| self.get_soil_params(&layer.unit_reference))
     }
 
     pub fn get_pwp_at_level(&self, level: f64) -> f64 {
-        if level > self.groundwater {
-            0.0
-        } else {
-            10.0 * (self.groundwater - level)
+        match &self.pore_pressure {
+            Some(regime) => regime.pore_pressure_at(level),
+            None if level > self.groundwater => 0.0,
+            None => DEFAULT_UNIT_WEIGHT_WATER * (self.groundwater - level),
         }
     }
 
-    pub fn get_total_stress_at_level(&self, level: f64) -> f64 {
-        let top = self.get_top_level();
-        let spacing = 0.1;
-        let mut result = 0.0;
-        let mut current_level = level;
-
-        while current_level <= top {
-            if let Ok(unit_weight) = self.get_unit_weight_at_level(current_level) {
-                result += unit_weight;
-            }
-            current_level += spacing;
+    /// Whether the ground at `level` is below a water table.
+    pub fn is_saturated_at(&self, level: f64) -> bool {
+        match &self.pore_pressure {
+            Some(regime) => regime.is_saturated(level),
+            None => level < self.groundwater,
         }
+    }
 
-        result * spacing
+    /// Total vertical stress at `level`, including the surcharge. Zero above the ground
+    /// surface.
+    pub fn get_total_stress_at_level(&self, level: f64) -> f64 {
+        self.stress_profile(&[level])[0].total
     }
 
     pub fn get_effective_stress_at_level(&self, level: f64) -> f64 {
         self.get_total_stress_at_level(level) - self.get_pwp_at_level(level)
     }
 
+    /// Vertical stresses at each of `levels`, integrated exactly through the layers. Unit
+    /// weights switch to `saturated_unit_weight` below the water table and follow any unit
+    /// weight profiles.
+    pub fn stress_profile(&self, levels: &[f64]) -> Vec<StressPoint> {
+        let top = self.get_top_level();
+        let intervals = self.stress_intervals();
+
+        levels
+            .iter()
+            .map(|&level| {
+                let total = if level > top {
+                    0.0
+                } else {
+                    self.surcharge
+                        + intervals
+                            .iter()
+                            .filter(|(upper, _, _)| *upper > level)
+                            .map(|(upper, lower, params)| {
+                                self.interval_weight(params, *upper, lower.max(level))
+                            })
+                            .sum::<f64>()
+                };
+                let pore_pressure = self.get_pwp_at_level(level);
+                StressPoint {
+                    level,
+                    total,
+                    pore_pressure,
+                    effective: total - pore_pressure,
+                }
+            })
+            .collect()
+    }
+
+    /// Vertical stress increment from `loads` at `level` below plan position `(x, y)`.
+    pub fn load_stress_at(&self, level: f64, x: f64, y: f64) -> f64 {
+        self.loads
+            .iter()
+            .map(|load| self.single_load_stress_at(load, level, x, y))
+            .sum()
+    }
+
+    /// Vertical stress increment from one load at `level` below plan position `(x, y)`.
+    pub(crate) fn single_load_stress_at(&self, load: &Load, level: f64, x: f64, y: f64) -> f64 {
+        let depth = load.level.unwrap_or_else(|| self.get_top_level()) - level;
+        load.stress_increment(self.stress_distribution, x, y, depth)
+    }
+
+    /// Total vertical stress at `level` below plan position `(x, y)`, including the stress
+    /// increments from `loads`.
+    pub fn get_total_stress_at_point(&self, level: f64, x: f64, y: f64) -> f64 {
+        self.get_total_stress_at_level(level) + self.load_stress_at(level, x, y)
+    }
+
+    /// [`GroundModel::stress_profile`] below plan position `(x, y)`, with the stress increments
+    /// from `loads` added to the total and effective stresses.
+    pub fn stress_profile_at(&self, levels: &[f64], x: f64, y: f64) -> Vec<StressPoint> {
+        self.stress_profile(levels)
+            .into_iter()
+            .map(|point| {
+                let increment = self.load_stress_at(point.level, x, y);
+                StressPoint {
+                    total: point.total + increment,
+                    effective: point.effective + increment,
+                    ..point
+                }
+            })
+            .collect()
+    }
+
+    /// Layers of the [`stratigraphy`](GroundModel::stratigraphy) as `(top, base, params)`
+    /// from the top down. The lowest layer extends indefinitely if it has no base. Layers
+    /// without parameters are skipped.
+    pub(crate) fn stress_intervals(&self) -> Vec<(f64, f64, &SoilParams)> {
+        self.stratigraphy()
+            .layers
+            .iter()
+            .filter_map(|layer| {
+                self.get_soil_params(&layer.unit_reference)
+                    .map(|params| (layer.top, layer.base.unwrap_or(f64::NEG_INFINITY), params))
+            })
+            .collect()
+    }
+
+    /// Weight of the soil column between `upper` and `lower` within one unit. The interval is
+    /// split at the water table and at unit weight profile points so that each piece is
+    /// integrated exactly.
+    fn interval_weight(&self, params: &SoilParams, upper: f64, lower: f64) -> f64 {
+        if upper <= lower {
+            return 0.0;
+        }
+
+        let datum = self.get_top_level();
+        let mut breaks = vec![upper, lower];
+        match &self.pore_pressure {
+            Some(regime) => breaks.extend(regime.breakpoints()),
+            None => breaks.push(self.groundwater),
+        }
+        for profile in params
+            .profiles
+            .iter()
+            .filter(|p| p.code_id == "UnitWeight" || p.code_id == "UnitWeightSaturated")
+        {
+            breaks.extend(profile.points.iter().map(|point| match profile.variable {
+                ProfileVariable::Depth => datum - point[0],
+                ProfileVariable::Elevation => point[0],
+            }));
+        }
+        breaks.retain(|level| *level <= upper && *level >= lower);
+        breaks.sort_by(|a, b| b.total_cmp(a));
+        breaks.dedup();
+
+        breaks
+            .windows(2)
+            .map(|pair| {
+                let (top, base) = (pair[0], pair[1]);
+                let saturated = self.is_saturated_at((top + base) / 2.0);
+                let gamma = |level: f64| {
+                    params
+                        .resolve_profiles(datum - level, level)
+                        .unit_weight_for(saturated)
+                };
+                (gamma(top) + gamma(base)) / 2.0 * (top - base)
+            })
+            .sum()
+    }
+
     pub fn quick_init(soil_params: SoilParams, top_level: f64, groundwater_level: f64) -> Self {
         let reference = if soil_params.reference.is_empty() {
             "gm_soil".to_string()
@@ -833,19 +1349,18 @@ impl GroundModel {
             soil_params: vec![params],
             rigid_boundary: None,
             groundwater: groundwater_level,
+            surcharge: 0.0,
+            pore_pressure: None,
+            loads: Vec::new(),
+            stress_distribution: StressDistribution::default(),
             reference,
+            cases: BTreeMap::new(),
         }
     }
 
+    /// Depth below the top of the highest layer, or `None` above it.
     pub fn get_depth_at_level(&self, level: f64) -> Option<f64> {
-        if self.soil_layers.is_empty() {
-            return None;
-        }
-        let ground_surface = self.soil_layers[0].top_level;
-        if level > ground_surface {
-            return None;
-        }
-        Some(ground_surface - level)
+        self.stratigraphy().depth_at(level)
     }
 }
 
@@ -856,7 +1371,60 @@ impl Default for GroundModel {
             soil_params: vec![SoilParams::default()],
             rigid_boundary: None,
             groundwater: 0.0,
+            surcharge: 0.0,
+            pore_pressure: None,
+            loads: Vec::new(),
+            stress_distribution: StressDistribution::default(),
             reference: String::new(),
+            cases: BTreeMap::new(),
+        }
+    }
+}
+
+/// Soil parameter sets shared by the module tests.
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use crate::{SoilParams, SoilType};
+
+    /// E = 20 MPa and ν = 0.3, with no strength or consolidation parameters.
+    pub(crate) fn soil(reference: &str, behaviour: SoilType, unit_weight: f64) -> SoilParams {
+        SoilParams::new(
+            reference.to_string(),
+            0.0,
+            20000.0,
+            0.3,
+            0.0,
+            behaviour,
+            unit_weight,
+        )
+    }
+
+    /// Granular soil at 20 kN/m³ with φ′ in degrees.
+    pub(crate) fn sand(reference: &str, phi_deg: f64) -> SoilParams {
+        SoilParams {
+            phi_prime: Some(phi_deg.to_radians()),
+            ..soil(reference, SoilType::Granular, 20.0)
+        }
+    }
+
+    /// Cohesive soil at 20 kN/m³ with mv in m²/kN and cv in m²/year.
+    pub(crate) fn clay(reference: &str, mv: f64, cv: f64) -> SoilParams {
+        SoilParams {
+            mv,
+            coefficient_of_consolidation: cv,
+            ..soil(reference, SoilType::Cohesive, 20.0)
+        }
+    }
+
+    /// Sandstone at 25 kN/m³ with the Hoek–Brown parameters; `ucs` is in kPa.
+    pub(crate) fn rock(gsi: f64, ucs: f64, mi: f64) -> SoilParams {
+        SoilParams {
+            youngs_modulus: 0.0,
+            poissons_ratio: 0.25,
+            gsi: Some(gsi),
+            ucs: Some(ucs),
+            mi: Some(mi),
+            ..soil("Sandstone", SoilType::Rock, 25.0)
         }
     }
 }
@@ -899,9 +1467,20 @@ mod tests {
 
     #[test]
     fn test_get_soil_params_at_level() {
+        // Parameters are matched by unit reference, not by position
         let layers = vec![
-            SoilLayer::new(10.0, 5.0, "Layer 1".to_string()),
-            SoilLayer::new(5.0, 0.0, "Layer 2".to_string()),
+            SoilLayer::with_unit_reference(
+                5.0,
+                0.0,
+                "Layer 2".to_string(),
+                "Granular soil".to_string(),
+            ),
+            SoilLayer::with_unit_reference(
+                10.0,
+                5.0,
+                "Layer 1".to_string(),
+                "Cohesive soil".to_string(),
+            ),
         ];
         let params = vec![
             SoilParams::new(
@@ -948,6 +1527,13 @@ mod tests {
         assert_eq!(ground_model.get_depth_at_level(8.0), Some(2.0));
         assert_eq!(ground_model.get_depth_at_level(10.0), Some(0.0));
         assert_eq!(ground_model.get_depth_at_level(12.0), None);
+
+        // Depth is measured from the highest layer, wherever it is listed
+        let mut unordered = ground_model.clone();
+        unordered
+            .soil_layers
+            .push(SoilLayer::new(12.0, 10.0, "Layer 0".to_string()));
+        assert_eq!(unordered.get_depth_at_level(8.0), Some(4.0));
     }
 
     #[test]
@@ -963,6 +1549,11 @@ mod tests {
 
         assert_eq!(ground_model.get_top_level(), 15.0);
         assert_eq!(ground_model.get_base_level(), -5.0);
+
+        // A lowest layer without a base gives its top rather than 0.0
+        let mut open_base = ground_model.clone();
+        open_base.soil_layers[2].base_level = None;
+        assert_eq!(open_base.get_base_level(), 2.0);
     }
 
     #[test]
@@ -1059,6 +1650,116 @@ mod tests {
         assert_eq!(ground_model.get_pwp_at_level(3.0), 20.0);
     }
 
+    #[test]
+    fn test_stress_profile_layered() {
+        let mut made_ground = SoilParams::new(
+            "MG".to_string(),
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            SoilType::Granular,
+            18.0,
+        );
+        made_ground.saturated_unit_weight = Some(20.0);
+        let clay = SoilParams::new(
+            "CL".to_string(),
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            SoilType::Cohesive,
+            19.0,
+        );
+        let mut ground_model = GroundModel::with_all_fields(
+            vec![
+                SoilLayer::with_unit_reference(5.0, -5.0, "L2".to_string(), "CL".to_string()),
+                SoilLayer::with_unit_reference(10.0, 5.0, "L1".to_string(), "MG".to_string()),
+            ],
+            vec![made_ground, clay],
+            None,
+            7.0,
+            "test".to_string(),
+        );
+        ground_model.surcharge = 10.0;
+
+        let profile = ground_model.stress_profile(&[12.0, 10.0, 0.0]);
+        assert_eq!(profile[0].total, 0.0);
+        assert_eq!(profile[1].total, 10.0);
+        // 10 + 18 * 3 + 20 * 2 + 19 * 5
+        assert!((profile[2].total - 199.0).abs() < 1e-9);
+        assert!((profile[2].pore_pressure - 70.0).abs() < 1e-9);
+        assert!((profile[2].effective - 129.0).abs() < 1e-9);
+        assert_eq!(
+            ground_model.get_total_stress_at_level(0.0),
+            profile[2].total
+        );
+    }
+
+    #[test]
+    fn test_stress_profile_uses_pore_pressure_regime() {
+        use crate::groundwater::{PiezometricHead, PiezometricZone};
+
+        let soil_params = SoilParams {
+            unit_weight: 18.0,
+            saturated_unit_weight: Some(20.0),
+            ..Default::default()
+        };
+        let mut ground_model = GroundModel::quick_init(soil_params, 10.0, 2.0);
+        ground_model.pore_pressure = Some(PorePressureRegime {
+            unit_weight_water: DEFAULT_UNIT_WEIGHT_WATER,
+            zones: vec![
+                PiezometricZone::new(Some(10.0), Some(8.0), PiezometricHead::Level(10.0)),
+                PiezometricZone::new(None, None, PiezometricHead::Level(2.0)),
+            ],
+        });
+
+        let stress = ground_model.stress_profile(&[0.0])[0];
+        // Saturated in the perched water, dry between 8 and 2, saturated below 2
+        assert!((stress.total - (20.0 * 2.0 + 18.0 * 6.0 + 20.0 * 2.0)).abs() < 1e-9);
+        assert!((stress.pore_pressure - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stress_at_point_includes_loads() {
+        let soil_params = SoilParams {
+            unit_weight: 20.0,
+            ..Default::default()
+        };
+        let mut ground_model = GroundModel::quick_init(soil_params, 10.0, -100.0);
+        ground_model
+            .loads
+            .push(loads::Load::strip(0.0, 2.0, 100.0).at_level(9.0));
+
+        // Below the centre of a 2 m strip founded at 9.0, 1 m below the founding level
+        let alpha = 2.0 * 1.0_f64.atan();
+        let increment = 100.0 / std::f64::consts::PI * (alpha + alpha.sin());
+        let sigma = ground_model.get_total_stress_at_point(8.0, 0.0, 0.0);
+        assert!((sigma - (40.0 + increment)).abs() < 1e-9);
+
+        // Loads only apply at a plan position
+        assert!((ground_model.get_total_stress_at_level(8.0) - 40.0).abs() < 1e-9);
+        let profile = ground_model.stress_profile_at(&[9.5, 8.0], 0.0, 0.0);
+        assert!((profile[1].effective - sigma).abs() < 1e-9);
+        assert_eq!(ground_model.load_stress_at(9.5, 0.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_stress_profile_follows_unit_weight_profile() {
+        let mut soil_params = SoilParams {
+            unit_weight: 16.0,
+            ..Default::default()
+        };
+        soil_params.profiles.push(ParameterProfile::new(
+            "UnitWeight".to_string(),
+            ProfileVariable::Depth,
+            vec![[0.0, 16.0], [10.0, 20.0]],
+        ));
+        let ground_model = GroundModel::quick_init(soil_params, 10.0, -100.0);
+
+        assert!((ground_model.get_total_stress_at_level(0.0) - 180.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_quick_init() {
         let soil_params = SoilParams::new(
@@ -1143,40 +1844,110 @@ mod tests {
         );
 
         // Excavation cuts through the layer
-        let (excavated, should_delete) = layer.excavate_layer(7.0);
-        assert!(!should_delete);
+        let excavated = layer.excavate_layer(7.0).unwrap();
         assert_eq!(excavated.top_level, 7.0);
         assert_eq!(excavated.base_level, Some(5.0));
+        assert_eq!(excavated.geol_code, "CL");
 
-        // Excavation above the layer
-        let (excavated, should_delete) = layer.excavate_layer(12.0);
-        assert!(!should_delete);
-        assert_eq!(excavated.top_level, 10.0);
+        // Excavation above the layer, or to its top
+        assert_eq!(layer.excavate_layer(12.0).unwrap().top_level, 10.0);
+        assert_eq!(layer.excavate_layer(10.0).unwrap().top_level, 10.0);
 
         // Excavation removes the entire layer
-        let (excavated, should_delete) = layer.excavate_layer(3.0);
-        assert!(should_delete);
-        assert_eq!(excavated.geol_code, "DELETE");
+        assert!(layer.excavate_layer(5.0).is_none());
+        assert!(layer.excavate_layer(3.0).is_none());
     }
 
     #[test]
-    fn test_process_layer() {
-        let layer = SoilLayer::with_all_fields(
-            "ROCK".to_string(),
-            10.0,
-            Some(5.0),
-            None,
-            "Weathered limestone".to_string(),
-            "LS".to_string(),
+    fn test_depth_profiles_stay_put_when_the_surface_moves() {
+        let mut clay = SoilParams::new(
+            "Clay".to_string(),
+            0.0001,
+            40000.0,
+            0.3,
+            1.0,
+            SoilType::Cohesive,
+            20.0,
+        );
+        clay.cu = Some(50.0);
+        clay.profiles.push(ParameterProfile::new(
+            "UndrainedShearStrength".to_string(),
+            ProfileVariable::Depth,
+            vec![[0.0, 50.0], [20.0, 90.0]],
+        ));
+        let mut model = GroundModel::new(
+            vec![SoilLayer::with_unit_reference(
+                10.0,
+                -10.0,
+                String::new(),
+                "Clay".to_string(),
+            )],
+            vec![clay.clone()],
+        );
+        model.cases.insert("Lower bound".to_string(), vec![clay]);
+        let cu_at = |model: &GroundModel, level: f64| model.get_params_at_level(level).unwrap().cu;
+        assert_eq!(cu_at(&model, 0.0), Some(70.0));
+
+        let excavated = model.excavate(5.0);
+        assert_eq!(cu_at(&excavated, 0.0), Some(70.0));
+        assert_eq!(cu_at(&excavated, 5.0), Some(60.0));
+        let case = excavated.for_case("Lower bound").unwrap();
+        assert_eq!(cu_at(&case, 0.0), Some(70.0));
+
+        let mut fill = SoilParams {
+            reference: "Fill".to_string(),
+            cu: Some(20.0),
+            ..SoilParams::default()
+        };
+        fill.profiles.push(ParameterProfile::new(
+            "UndrainedShearStrength".to_string(),
+            ProfileVariable::Depth,
+            vec![[0.0, 20.0], [10.0, 40.0]],
+        ));
+        let filled = model.place_fill(12.0, &fill).unwrap();
+        assert_eq!(cu_at(&filled, 0.0), Some(70.0));
+        // The fill's own profile starts at its top
+        assert_eq!(cu_at(&filled, 11.0), Some(22.0));
+        assert_eq!(cu_at(&filled.excavate(8.0), 0.0), Some(70.0));
+    }
+
+    #[test]
+    fn test_excavate_and_place_fill() {
+        let mut made_ground =
+            SoilLayer::with_unit_reference(12.0, 0.0, String::new(), "Made Ground".to_string());
+        made_ground.base_level = None;
+        let model = GroundModel::new(
+            vec![
+                SoilLayer::with_unit_reference(10.0, 0.0, String::new(), "Clay".to_string()),
+                made_ground,
+            ],
+            vec![],
         );
 
-        let processed = layer.process_layer(100.0, SoilType::Rock);
-        assert!(processed
-            .typical_description
-            .contains("processed with sig3: 100"));
+        // The made ground base is inferred from the clay, so it is removed
+        let excavated = model.excavate(8.0);
+        assert_eq!(excavated.soil_layers.len(), 1);
+        assert_eq!(excavated.get_top_level(), 8.0);
 
-        let processed_soil = layer.process_layer(0.0, SoilType::Cohesive);
-        assert_eq!(processed_soil.typical_description, "Weathered limestone");
+        let fill = SoilParams {
+            reference: "Fill".to_string(),
+            ..SoilParams::default()
+        };
+        let filled = excavated.place_fill(9.5, &fill).unwrap();
+        assert_eq!(filled.get_top_level(), 9.5);
+        assert_eq!(
+            filled.get_layer_at_level(9.0).unwrap().unit_reference,
+            "Fill"
+        );
+        assert_eq!(
+            filled.get_soil_params_at_level(9.0).unwrap().reference,
+            "Fill"
+        );
+        assert_eq!(filled.stratigraphy().boundaries(), [9.5, 8.0, 0.0]);
+        assert!(matches!(
+            filled.place_fill(9.0, &fill),
+            Err(GroundModelError::InvalidGeometry { .. })
+        ));
     }
 
     #[test]
@@ -1348,6 +2119,9 @@ mod tests {
         let k0 = params.k0().unwrap();
         let expected_k0 = 1.0 - 30.0_f64.to_radians().sin();
         assert!((k0 - expected_k0).abs() < 1e-10);
+        let k0_oc = params.k0_overconsolidated(4.0).unwrap();
+        assert!((k0_oc - expected_k0 * 2.0).abs() < 1e-10);
+        assert!(params.k0_overconsolidated(0.5).is_err());
     }
 
     #[test]
@@ -1363,10 +2137,10 @@ mod tests {
             0.0,
             0.25,
             0.0,
-            Some(65.0), // gsi
-            Some(25.0), // ucs in MPa
-            Some(10.0), // mi
-            0.0,        // disturbance
+            Some(65.0),    // gsi
+            Some(25000.0), // ucs in kPa
+            Some(10.0),    // mi
+            0.0,           // disturbance
         );
 
         // Test mb calculation
@@ -1381,9 +2155,9 @@ mod tests {
         let a = params.a().unwrap();
         assert!(a > 0.0 && a < 1.0);
 
-        // Test rock E value
+        // Test rock E value: 0.5 × 10^1.375 GPa
         let e_val = params.rock_e_val().unwrap();
-        assert!(e_val > 0.0);
+        assert!((e_val - 0.5 * 10.0_f64.powf(1.375) * 1.0e6).abs() < 1e-3);
     }
 
     #[test]
@@ -1399,17 +2173,17 @@ mod tests {
             0.0,
             0.25,
             0.0,
-            Some(65.0), // gsi
-            Some(25.0), // ucs in MPa
-            Some(10.0), // mi
-            0.0,        // disturbance
+            Some(65.0),    // gsi
+            Some(25000.0), // ucs in kPa
+            Some(10.0),    // mi
+            0.0,           // disturbance
         );
 
-        let sig3 = 100.0; // confining stress in kPa
+        let sig3 = 1000.0; // confining stress in kPa
 
         // Test equivalent phi angle
         let phi_equiv = params.hb_equiv_phi_ang(sig3).unwrap();
-        assert!(phi_equiv >= 0.0 && phi_equiv <= 1.0); // Should be reasonable phi value in radians
+        assert!((0.0..=1.0).contains(&phi_equiv)); // Should be reasonable phi value in radians
 
         // Test equivalent cohesion
         let c_equiv = params.hb_equiv_c_prime(sig3).unwrap();
@@ -1422,6 +2196,134 @@ mod tests {
         assert!(converted.phi_prime.unwrap() > 0.0);
         assert!(converted.c_prime.unwrap() > 0.0);
         assert_eq!(converted.unit_weight, params.unit_weight);
+
+        // Fields the conversion does not touch are kept
+        let mut params = params;
+        params.case_id = Some("Lower bound".to_string());
+        params.saturated_unit_weight = Some(26.0);
+        params.mv_recompression = Some(1e-6);
+        params.pop = Some(200.0);
+        params.advanced_parameters = Some(vec![AdvancedParameter {
+            name: "RQD".to_string(),
+            value: 60.0,
+            units: Some("%".to_string()),
+        }]);
+        for code_id in ["UnitWeight", "AngleFriction"] {
+            params.profiles.push(ParameterProfile::new(
+                code_id.to_string(),
+                ProfileVariable::Depth,
+                vec![[0.0, 25.0], [10.0, 26.0]],
+            ));
+        }
+        let converted = params.convert_equivalent_rock(sig3).unwrap();
+        assert_eq!(converted.case_id.as_deref(), Some("Lower bound"));
+        assert_eq!(converted.saturated_unit_weight, Some(26.0));
+        assert_eq!(converted.mv_recompression, Some(1e-6));
+        assert_eq!(converted.pop, Some(200.0));
+        assert_eq!(converted.advanced_parameters.unwrap()[0].name, "RQD");
+        assert_eq!(converted.profiles.len(), 1);
+        assert_eq!(converted.profiles[0].code_id, "UnitWeight");
+    }
+
+    #[test]
+    fn test_partial_factors_rock_and_stiffness() {
+        let params = SoilParams::with_all_fields(
+            "rock".to_string(),
+            SoilType::Rock,
+            None,
+            None,
+            25.0,
+            None,
+            1e-5,
+            2.0e6,
+            0.25,
+            0.0,
+            Some(65.0),
+            Some(25.0),
+            Some(10.0),
+            0.0,
+        );
+        let pf = PartialFactors::default()
+            .with_rock(1.4, 1.2)
+            .with_modulus(1.5);
+
+        let factored = params.apply_partial_factors(&pf);
+        assert!((factored.ucs.unwrap() - 25.0 / 1.4).abs() < 1e-10);
+        assert!((factored.mi.unwrap() - 10.0 / 1.2).abs() < 1e-10);
+        assert!((factored.youngs_modulus - 2.0e6 / 1.5).abs() < 1e-6);
+        assert!((factored.mv - 1.5e-5).abs() < 1e-15);
+
+        let unfactored = factored.remove_partial_factors().unwrap();
+        assert!((unfactored.ucs.unwrap() - 25.0).abs() < 1e-10);
+        assert!((unfactored.mi.unwrap() - 10.0).abs() < 1e-10);
+        assert!((unfactored.youngs_modulus - 2.0e6).abs() < 1e-6);
+
+        // Equivalent parameters of factored rock are factored once and still reversible
+        let pf = PartialFactors {
+            gamma_phi: 1.25,
+            gamma_c: 1.25,
+            ..PartialFactors::default().with_modulus(1.5)
+        };
+        let sig3 = 100.0;
+        let characteristic = params.convert_equivalent_rock(sig3).unwrap();
+        let converted = params
+            .apply_partial_factors(&pf)
+            .convert_equivalent_rock(sig3)
+            .unwrap();
+        assert!(converted.factored);
+        let expected_phi = (characteristic.phi_prime.unwrap().tan() / 1.25).atan();
+        assert!((converted.phi_prime.unwrap() - expected_phi).abs() < 1e-10);
+        assert!((converted.youngs_modulus - characteristic.youngs_modulus / 1.5).abs() < 1e-6);
+        let restored = converted.remove_partial_factors().unwrap();
+        assert!((restored.c_prime.unwrap() - characteristic.c_prime.unwrap()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_partial_factors_profiles_round_trip() {
+        let mut params = SoilParams::new(
+            "clay".to_string(),
+            0.0002,
+            20000.0,
+            0.3,
+            1.0,
+            SoilType::Cohesive,
+            19.0,
+        );
+        params.phi_prime = Some(24.0_f64.to_radians());
+        params.cu = Some(50.0);
+        params.saturated_unit_weight = Some(20.0);
+        params.mv_recompression = Some(0.00005);
+        params.profiles.push(ParameterProfile::new(
+            "UndrainedShearStrength".to_string(),
+            ProfileVariable::Depth,
+            vec![[0.0, 50.0], [10.0, 100.0]],
+        ));
+        params.profiles.push(ParameterProfile::new(
+            "AngleFriction".to_string(),
+            ProfileVariable::Depth,
+            vec![[0.0, 24.0_f64.to_radians()], [10.0, 28.0_f64.to_radians()]],
+        ));
+        let pf = PartialFactors::new(1.25, 1.25, 1.1, 1.4).with_modulus(1.5);
+
+        let factored = params.apply_partial_factors(&pf);
+        let resolved = factored.resolve_profiles(5.0, 0.0);
+        assert!((resolved.cu.unwrap() - 75.0 / 1.4).abs() < 1e-10);
+        let design_phi = |phi: f64| (phi.to_radians().tan() / 1.25).atan();
+        let phi = (design_phi(24.0) + design_phi(28.0)) / 2.0;
+        assert!((resolved.phi_prime.unwrap() - phi).abs() < 1e-10);
+        assert!((factored.saturated_unit_weight.unwrap() - 20.0 / 1.1).abs() < 1e-10);
+        assert!((factored.mv_recompression.unwrap() - 0.000075).abs() < 1e-15);
+
+        let unfactored = factored.remove_partial_factors().unwrap();
+        assert!((unfactored.saturated_unit_weight.unwrap() - 20.0).abs() < 1e-10);
+        assert!((unfactored.mv_recompression.unwrap() - 0.00005).abs() < 1e-15);
+        for (restored, original) in unfactored.profiles.iter().zip(&params.profiles) {
+            for (a, b) in restored.points.iter().zip(&original.points) {
+                assert!((a[1] - b[1]).abs() < 1e-10);
+            }
+        }
+        let resolved = unfactored.resolve_profiles(5.0, 0.0);
+        assert!((resolved.cu.unwrap() - 75.0).abs() < 1e-10);
     }
 
     #[test]
@@ -1443,6 +2345,56 @@ mod tests {
         assert!(params.remove_partial_factors().is_err());
     }
 
+    #[test]
+    fn test_error_variants() {
+        let mut params = SoilParams {
+            reference: "Chalk".to_string(),
+            ..Default::default()
+        };
+
+        match params.get_k_active(None) {
+            Err(GroundModelError::MissingParameter { reference, name }) => {
+                assert_eq!(reference, "Chalk");
+                assert_eq!(name, "phi_prime");
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        params.ucs = Some(0.0);
+        params.gsi = Some(50.0);
+        params.mi = Some(10.0);
+        assert!(matches!(
+            params.hb_equiv_phi_ang(100.0),
+            Err(GroundModelError::InvalidParameter { name: "ucs", .. })
+        ));
+        assert!(matches!(
+            params.remove_partial_factors(),
+            Err(GroundModelError::NotFactored { .. })
+        ));
+
+        let ground_model = GroundModel::new(
+            vec![SoilLayer::with_unit_reference(
+                10.0,
+                0.0,
+                "L1".to_string(),
+                "Chalk".to_string(),
+            )],
+            vec![params],
+        );
+        assert!(matches!(
+            ground_model.get_params_at_level(15.0),
+            Err(GroundModelError::LevelOutsideModel { level }) if level == 15.0
+        ));
+        assert!(matches!(
+            ground_model.for_case("DA1-C2"),
+            Err(GroundModelError::UnknownCase { .. })
+        ));
+        assert!(matches!(
+            "csv".parse::<ConvertType>(),
+            Err(GroundModelError::InvalidConvertType { .. })
+        ));
+    }
+
     #[test]
     fn test_from_agsi_data_parameters() {
         let data = vec![
@@ -1491,7 +2443,7 @@ mod tests {
         let soil_params = SoilParams::from_agsi_data_parameters(&data);
 
         assert_eq!(soil_params.unit_weight, 18.0);
-        assert_eq!(soil_params.phi_prime, Some(30.0));
+        assert_eq!(soil_params.phi_prime, Some(30.0_f64.to_radians()));
         assert_eq!(soil_params.c_prime, Some(5.0));
         assert_eq!(soil_params.youngs_modulus, 50000.0);
         assert_eq!(soil_params.behaviour, SoilType::Granular); // Default behavior
@@ -1559,6 +2511,76 @@ mod tests {
         assert_eq!(soil_params.behaviour, SoilType::Rock);
     }
 
+    #[test]
+    fn test_parameter_profile_interpolation() {
+        let profile = ParameterProfile::new(
+            "UndrainedShearStrength".to_string(),
+            ProfileVariable::Depth,
+            vec![[10.0, 100.0], [0.0, 40.0], [20.0, 120.0]],
+        );
+
+        assert_eq!(profile.points[0], [0.0, 40.0]);
+        assert_eq!(profile.value_at(-1.0), Some(40.0));
+        assert_eq!(profile.value_at(5.0), Some(70.0));
+        assert_eq!(profile.value_at(15.0), Some(110.0));
+        assert_eq!(profile.value_at(30.0), Some(120.0));
+    }
+
+    #[test]
+    fn test_from_agsi_data_parameters_value_profile() {
+        let data = vec![
+            AgsiDataParameterValue {
+                code_id: "UndrainedShearStrength".parse().unwrap(),
+                case_id: None,
+                data_id: None,
+                remarks: None,
+                value_numeric: None,
+                value_profile: Some(vec![[0.0, 40.0], [10.0, 100.0]].into()),
+                value_profile_ind_var_code_id: Some("Depth".to_string()),
+                value_text: None,
+            },
+            AgsiDataParameterValue {
+                code_id: "YoungsModulus".parse().unwrap(),
+                case_id: None,
+                data_id: None,
+                remarks: None,
+                value_numeric: None,
+                value_profile: Some(vec![[5.0, 20000.0], [-5.0, 60000.0]].into()),
+                value_profile_ind_var_code_id: Some("Elevation".to_string()),
+                value_text: None,
+            },
+        ];
+
+        let soil_params = SoilParams::from_agsi_data_parameters(&data);
+
+        assert_eq!(soil_params.cu, Some(40.0));
+        assert_eq!(soil_params.behaviour, SoilType::Cohesive);
+        assert_eq!(soil_params.youngs_modulus, 60000.0);
+        assert_eq!(soil_params.profiles.len(), 2);
+        assert_eq!(soil_params.profiles[1].variable, ProfileVariable::Elevation);
+
+        let ground_model = GroundModel::new(
+            vec![SoilLayer::with_unit_reference(
+                5.0,
+                -15.0,
+                "Clay".to_string(),
+                "CLAY".to_string(),
+            )],
+            vec![SoilParams {
+                reference: "CLAY".to_string(),
+                ..soil_params
+            }],
+        );
+
+        let at_depth_5 = ground_model.get_params_at_level(0.0).unwrap();
+        assert!((at_depth_5.cu.unwrap() - 70.0).abs() < 1e-9);
+        assert!((at_depth_5.youngs_modulus - 40000.0).abs() < 1e-9);
+
+        let at_base = ground_model.get_params_at_level(-15.0).unwrap();
+        assert_eq!(at_base.cu, Some(100.0));
+        assert_eq!(at_base.youngs_modulus, 60000.0);
+    }
+
     #[test]
     fn test_from_agsi_data_parameters_advanced_params() {
         let data = vec![
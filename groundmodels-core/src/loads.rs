@@ -0,0 +1,396 @@
+use serde::{Deserialize, Serialize};
+use std::f64::consts::{PI, SQRT_2};
+
+/// Number of directions used to integrate circular loads off their centre line.
+const CIRCLE_DIRECTIONS: usize = 720;
+
+/// Elastic solution used to spread surface loads with depth.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum StressDistribution {
+    /// Homogeneous isotropic half-space.
+    #[default]
+    Boussinesq,
+    /// Half-space reinforced against lateral strain, e.g. layered or varved soils. Uses a
+    /// Poisson's ratio of zero.
+    Westergaard,
+}
+
+/// Plan shape of a [`Load`]. Plan coordinates are in metres; strips and embankments run
+/// parallel to the y axis.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LoadShape {
+    /// Infinite uniform surcharge.
+    Uniform,
+    /// Infinitely long strip centred on `x`.
+    Strip { x: f64, width: f64 },
+    /// Rectangle centred on `(x, y)` with `width` along x and `length` along y.
+    Rectangular {
+        x: f64,
+        y: f64,
+        width: f64,
+        length: f64,
+    },
+    /// Circle centred on `(x, y)`.
+    Circular { x: f64, y: f64, radius: f64 },
+    /// Infinitely long symmetric embankment centred on `x`. The full pressure acts over the
+    /// crest and falls linearly to zero across each side slope.
+    Embankment {
+        x: f64,
+        crest_width: f64,
+        side_width: f64,
+    },
+}
+
+/// A vertical pressure applied at the ground surface or at a foundation level.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Load {
+    pub shape: LoadShape,
+    /// Applied pressure (kPa); the pressure under the crest for an embankment.
+    pub pressure: f64,
+    /// Level the load acts at. The top of the ground model when `None`.
+    #[serde(default)]
+    pub level: Option<f64>,
+}
+
+impl Load {
+    pub fn uniform(pressure: f64) -> Self {
+        Load {
+            shape: LoadShape::Uniform,
+            pressure,
+            level: None,
+        }
+    }
+
+    pub fn strip(x: f64, width: f64, pressure: f64) -> Self {
+        Load {
+            shape: LoadShape::Strip { x, width },
+            pressure,
+            level: None,
+        }
+    }
+
+    pub fn rectangular(x: f64, y: f64, width: f64, length: f64, pressure: f64) -> Self {
+        Load {
+            shape: LoadShape::Rectangular {
+                x,
+                y,
+                width,
+                length,
+            },
+            pressure,
+            level: None,
+        }
+    }
+
+    pub fn circular(x: f64, y: f64, radius: f64, pressure: f64) -> Self {
+        Load {
+            shape: LoadShape::Circular { x, y, radius },
+            pressure,
+            level: None,
+        }
+    }
+
+    /// Embankment fill of `height` (m) and `unit_weight` (kN/m³).
+    pub fn embankment(
+        x: f64,
+        crest_width: f64,
+        side_width: f64,
+        height: f64,
+        unit_weight: f64,
+    ) -> Self {
+        Load {
+            shape: LoadShape::Embankment {
+                x,
+                crest_width,
+                side_width,
+            },
+            pressure: height * unit_weight,
+            level: None,
+        }
+    }
+
+    /// Applies the load at `level` instead of the top of the model.
+    pub fn at_level(mut self, level: f64) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// Pressure applied directly at plan position `(x, y)`.
+    pub fn pressure_at(&self, x: f64, y: f64) -> f64 {
+        let inside = match &self.shape {
+            LoadShape::Uniform => true,
+            LoadShape::Strip { x: cx, width } => (x - cx).abs() <= width / 2.0,
+            LoadShape::Rectangular {
+                x: cx,
+                y: cy,
+                width,
+                length,
+            } => (x - cx).abs() <= width / 2.0 && (y - cy).abs() <= length / 2.0,
+            LoadShape::Circular {
+                x: cx,
+                y: cy,
+                radius,
+            } => (x - cx).hypot(y - cy) <= *radius,
+            LoadShape::Embankment {
+                x: cx,
+                crest_width,
+                side_width,
+            } => {
+                let offset = (x - cx).abs() - crest_width / 2.0;
+                if offset <= 0.0 {
+                    true
+                } else if offset < *side_width {
+                    return self.pressure * (1.0 - offset / side_width);
+                } else {
+                    false
+                }
+            }
+        };
+        if inside {
+            self.pressure
+        } else {
+            0.0
+        }
+    }
+
+    /// Vertical stress increment at plan position `(x, y)` and `depth` (m) below the level the
+    /// load acts at. Zero above that level.
+    pub fn stress_increment(
+        &self,
+        distribution: StressDistribution,
+        x: f64,
+        y: f64,
+        depth: f64,
+    ) -> f64 {
+        if depth < 0.0 {
+            return 0.0;
+        }
+        if depth == 0.0 {
+            return self.pressure_at(x, y);
+        }
+
+        match &self.shape {
+            LoadShape::Uniform => self.pressure,
+            LoadShape::Strip { x: cx, width } => {
+                let half = width / 2.0;
+                strip_segment(
+                    distribution,
+                    x,
+                    depth,
+                    [cx - half, self.pressure],
+                    [cx + half, self.pressure],
+                )
+            }
+            LoadShape::Rectangular {
+                x: cx,
+                y: cy,
+                width,
+                length,
+            } => {
+                let (x1, x2) = (cx - width / 2.0 - x, cx + width / 2.0 - x);
+                let (y1, y2) = (cy - length / 2.0 - y, cy + length / 2.0 - y);
+                let corner = |a: f64, b: f64| {
+                    a.signum() * b.signum() * corner_factor(distribution, a.abs(), b.abs(), depth)
+                };
+                self.pressure * (corner(x2, y2) - corner(x1, y2) - corner(x2, y1) + corner(x1, y1))
+            }
+            LoadShape::Circular {
+                x: cx,
+                y: cy,
+                radius,
+            } => {
+                self.pressure * circle_factor(distribution, (x - cx).hypot(y - cy), *radius, depth)
+            }
+            LoadShape::Embankment {
+                x: cx,
+                crest_width,
+                side_width,
+            } => {
+                let crest = crest_width / 2.0;
+                let toe = crest + side_width;
+                let q = self.pressure;
+                strip_segment(distribution, x, depth, [cx - toe, 0.0], [cx - crest, q])
+                    + strip_segment(distribution, x, depth, [cx - crest, q], [cx + crest, q])
+                    + strip_segment(distribution, x, depth, [cx + crest, q], [cx + toe, 0.0])
+            }
+        }
+    }
+}
+
+/// Stress under an infinitely long strip whose pressure varies linearly from `start` to
+/// `end`, each given as `[x, pressure]`, integrated exactly from the line load solution.
+fn strip_segment(
+    distribution: StressDistribution,
+    x: f64,
+    z: f64,
+    start: [f64; 2],
+    end: [f64; 2],
+) -> f64 {
+    let ([xa, pa], [xb, pb]) = (start, end);
+    if xb <= xa {
+        return 0.0;
+    }
+    let slope = (pb - pa) / (xb - xa);
+    let intercept = pa - slope * xa;
+
+    let (ua, ub) = (x - xb, x - xa);
+    let (ka, uka) = line_load_integrals(distribution, ua, z);
+    let (kb, ukb) = line_load_integrals(distribution, ub, z);
+    (intercept + slope * x) * (kb - ka) - slope * (ukb - uka)
+}
+
+/// Antiderivatives of the line load kernel K(u) and of u K(u) at horizontal offset `u`.
+fn line_load_integrals(distribution: StressDistribution, u: f64, z: f64) -> (f64, f64) {
+    match distribution {
+        StressDistribution::Boussinesq => (
+            ((u / z).atan() + u * z / (u * u + z * z)) / PI,
+            -z.powi(3) / (PI * (u * u + z * z)),
+        ),
+        StressDistribution::Westergaard => (
+            (SQRT_2 * u / z).atan() / PI,
+            SQRT_2 * z * (z * z + 2.0 * u * u).ln() / (4.0 * PI),
+        ),
+    }
+}
+
+/// Influence factor under the corner of a uniformly loaded `a` by `b` rectangle.
+fn corner_factor(distribution: StressDistribution, a: f64, b: f64, z: f64) -> f64 {
+    let (m, n) = (a / z, b / z);
+    match distribution {
+        StressDistribution::Boussinesq => {
+            let v = m * m + n * n + 1.0;
+            let mn = m * n;
+            (2.0 * mn * v.sqrt() / (v + mn * mn) * (v + 1.0) / v
+                + (2.0 * mn * v.sqrt()).atan2(v - mn * mn))
+                / (4.0 * PI)
+        }
+        StressDistribution::Westergaard => {
+            // η² = (1 - 2ν) / (2 - 2ν) with ν = 0
+            let eta2 = 0.5;
+            (m * n).atan2((eta2 * (m * m + n * n) + eta2 * eta2).sqrt()) / (2.0 * PI)
+        }
+    }
+}
+
+/// Influence factor at `offset` from the centre of a uniformly loaded circle. Integrates the
+/// point load solution along rays from the point, which is exact radially.
+fn circle_factor(distribution: StressDistribution, offset: f64, radius: f64, z: f64) -> f64 {
+    // Share of a point load's stress falling within `rho` of the point
+    let within = |rho: f64| match distribution {
+        StressDistribution::Boussinesq => 1.0 - (z * z / (rho * rho + z * z)).powf(1.5),
+        StressDistribution::Westergaard => 1.0 - z / (z * z + 2.0 * rho * rho).sqrt(),
+    };
+
+    if offset == 0.0 {
+        return within(radius);
+    }
+
+    // Rays at angle phi to the line towards the centre cross the circle at
+    // rho = s ± sqrt(s² - offset² + radius²), s = offset cos(phi)
+    let inside = offset < radius;
+    let phi_max = if inside { PI } else { (radius / offset).asin() };
+    let step = 2.0 * phi_max / CIRCLE_DIRECTIONS as f64;
+    let total: f64 = (0..CIRCLE_DIRECTIONS)
+        .map(|i| {
+            let phi = -phi_max + (i as f64 + 0.5) * step;
+            let s = offset * phi.cos();
+            let root = (s * s - offset * offset + radius * radius).max(0.0).sqrt();
+            let near = if inside { 0.0 } else { s - root };
+            within(s + root) - within(near)
+        })
+        .sum();
+    total * step / (2.0 * PI)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOTH: [StressDistribution; 2] = [
+        StressDistribution::Boussinesq,
+        StressDistribution::Westergaard,
+    ];
+
+    #[test]
+    fn strip_centre_matches_closed_form() {
+        let load = Load::strip(0.0, 2.0, 100.0);
+        let z: f64 = 1.5;
+        let alpha = 2.0 * (1.0 / z).atan();
+        let expected = 100.0 / PI * (alpha + alpha.sin());
+
+        let sigma = load.stress_increment(StressDistribution::Boussinesq, 0.0, 0.0, z);
+        assert!((sigma - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rectangle_corner_matches_chart_value() {
+        // m = n = 1 gives the tabulated Boussinesq factor 0.1752
+        let load = Load::rectangular(1.0, 1.0, 2.0, 2.0, 100.0);
+        let sigma = load.stress_increment(StressDistribution::Boussinesq, 0.0, 0.0, 2.0);
+        assert!((sigma - 17.52).abs() < 0.01);
+    }
+
+    #[test]
+    fn long_rectangle_tends_to_strip() {
+        for distribution in BOTH {
+            let strip = Load::strip(0.0, 2.0, 100.0).stress_increment(distribution, 0.5, 0.0, 3.0);
+            let rectangle = Load::rectangular(0.0, 0.0, 2.0, 1.0e6, 100.0).stress_increment(
+                distribution,
+                0.5,
+                0.0,
+                3.0,
+            );
+            assert!((strip - rectangle).abs() < 1e-3, "{:?}", distribution);
+        }
+    }
+
+    #[test]
+    fn circle_off_centre_is_continuous_with_centre() {
+        for distribution in BOTH {
+            let load = Load::circular(0.0, 0.0, 2.0, 100.0);
+            let centre = load.stress_increment(distribution, 0.0, 0.0, 3.0);
+            let near_centre = load.stress_increment(distribution, 1.0e-4, 0.0, 3.0);
+            assert!((centre - near_centre).abs() < 1e-4, "{:?}", distribution);
+
+            // Outside points see less stress but not zero
+            let outside = load.stress_increment(distribution, 4.0, 0.0, 3.0);
+            assert!(outside > 0.0 && outside < centre);
+        }
+    }
+
+    #[test]
+    fn circle_centre_matches_closed_form() {
+        let load = Load::circular(0.0, 0.0, 2.0, 100.0);
+        let z: f64 = 2.0;
+        let expected = 100.0 * (1.0 - (1.0 / (1.0 + (2.0 / z).powi(2))).powf(1.5));
+        let sigma = load.stress_increment(StressDistribution::Boussinesq, 0.0, 0.0, z);
+        assert!((sigma - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn embankment_approaches_crest_pressure_near_surface() {
+        let load = Load::embankment(0.0, 10.0, 6.0, 3.0, 20.0);
+        assert_eq!(load.pressure, 60.0);
+        assert_eq!(load.pressure_at(8.0, 0.0), 30.0);
+
+        for distribution in BOTH {
+            let shallow = load.stress_increment(distribution, 0.0, 0.0, 0.01);
+            assert!((shallow - 60.0).abs() < 0.1, "{:?}", distribution);
+            let under_slope = load.stress_increment(distribution, 8.0, 0.0, 0.01);
+            assert!((under_slope - 30.0).abs() < 0.5, "{:?}", distribution);
+        }
+    }
+
+    #[test]
+    fn no_stress_above_load_level() {
+        let load = Load::strip(0.0, 2.0, 100.0);
+        assert_eq!(
+            load.stress_increment(StressDistribution::Boussinesq, 0.0, 0.0, -1.0),
+            0.0
+        );
+        assert_eq!(
+            load.stress_increment(StressDistribution::Boussinesq, 0.0, 0.0, 0.0),
+            100.0
+        );
+    }
+}
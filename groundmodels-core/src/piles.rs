@@ -0,0 +1,420 @@
+use crate::{GroundModel, GroundModelError, PartialFactors, SoilParams, SoilType};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::{FRAC_PI_4, PI};
+
+/// Distance above the toe used to pick the unit the pile is founded in (m).
+const LEVEL_TOLERANCE: f64 = 1e-6;
+
+/// Sublayers each unit is split into when integrating shaft friction.
+const SHAFT_SUBLAYERS: usize = 10;
+
+/// End bearing factor for piles in clay.
+const CLAY_END_BEARING_FACTOR: f64 = 9.0;
+
+/// Rowe and Armitage (1987) rock socket shaft friction, τ = 0.45 √UCS in MPa.
+const ROCK_SHAFT_COEFFICIENT: f64 = 0.45;
+
+/// Zhang and Einstein (1998) rock socket end bearing, qb = 4.83 √UCS in MPa.
+const ROCK_BASE_COEFFICIENT: f64 = 4.83;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PileType {
+    /// Replacement pile, e.g. a CFA or rotary bored pile.
+    Bored,
+    /// Displacement pile.
+    Driven,
+}
+
+/// Cross-section of a pile (m).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PileSection {
+    Circular { diameter: f64 },
+    Square { width: f64 },
+}
+
+impl PileSection {
+    pub fn perimeter(&self) -> f64 {
+        match self {
+            PileSection::Circular { diameter } => PI * diameter,
+            PileSection::Square { width } => 4.0 * width,
+        }
+    }
+
+    pub fn base_area(&self) -> f64 {
+        match self {
+            PileSection::Circular { diameter } => FRAC_PI_4 * diameter * diameter,
+            PileSection::Square { width } => width * width,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pile {
+    pub pile_type: PileType,
+    pub section: PileSection,
+    /// Level from which shaft friction is counted, e.g. the underside of the pile cap.
+    pub head_level: f64,
+    pub toe_level: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PileOptions {
+    /// Material factors applied with `SoilParams::apply_partial_factors`. Parameters that are
+    /// already factored are used as they are.
+    pub partial_factors: Option<PartialFactors>,
+    /// Model factor γRd dividing the calculated resistances.
+    pub model_factor: f64,
+    /// Shaft resistance factor γs.
+    pub shaft_factor: f64,
+    /// Base resistance factor γb.
+    pub base_factor: f64,
+    /// Adhesion factor α for cohesive units. When `None`, 0.5 for bored piles and the API
+    /// RP2A value from cu/σ′v for driven piles.
+    pub adhesion_factor: Option<f64>,
+    /// Ks/K0 for granular units. When `None`, 1.0 for bored and 1.5 for driven piles.
+    pub earth_pressure_ratio: Option<f64>,
+    /// δ/φ′ for granular units. When `None`, 1.0 for bored and 0.8 for driven piles.
+    pub interface_friction_ratio: Option<f64>,
+}
+
+impl Default for PileOptions {
+    fn default() -> Self {
+        PileOptions {
+            partial_factors: None,
+            model_factor: 1.0,
+            shaft_factor: 1.0,
+            base_factor: 1.0,
+            adhesion_factor: None,
+            earth_pressure_ratio: None,
+            interface_friction_ratio: None,
+        }
+    }
+}
+
+/// How the shaft friction of a layer is calculated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShaftMethod {
+    /// τ = α cu, for cohesive units.
+    Alpha,
+    /// τ = K tanδ σ′v, for granular units.
+    Beta,
+    /// τ from the UCS, for rock.
+    RockSocket,
+}
+
+/// Shaft resistance from one layer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PileLayerResistance {
+    pub reference: String,
+    pub top_level: f64,
+    pub base_level: f64,
+    pub method: ShaftMethod,
+    /// Average unit shaft friction over the layer (kPa).
+    pub shaft_friction: f64,
+    /// Calculated shaft resistance (kN).
+    pub shaft_resistance: f64,
+}
+
+/// End bearing at the pile toe.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PileBaseResistance {
+    pub reference: String,
+    pub level: f64,
+    /// Unit end bearing (kPa).
+    pub end_bearing: f64,
+    /// Calculated base resistance (kN).
+    pub base_resistance: f64,
+}
+
+/// Axial compression resistance of a single pile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PileCapacity {
+    /// Shaft resistance layer by layer from the head down.
+    pub layers: Vec<PileLayerResistance>,
+    pub base: PileBaseResistance,
+    pub model_factor: f64,
+    pub shaft_factor: f64,
+    pub base_factor: f64,
+}
+
+impl PileCapacity {
+    /// Calculated shaft resistance Rs;cal (kN).
+    pub fn shaft_resistance(&self) -> f64 {
+        self.layers.iter().map(|layer| layer.shaft_resistance).sum()
+    }
+
+    /// Calculated base resistance Rb;cal (kN).
+    pub fn base_resistance(&self) -> f64 {
+        self.base.base_resistance
+    }
+
+    /// Calculated resistance Rc;cal (kN).
+    pub fn ultimate(&self) -> f64 {
+        self.shaft_resistance() + self.base_resistance()
+    }
+
+    /// Characteristic resistance Rc;k = Rc;cal / γRd (kN).
+    pub fn characteristic(&self) -> f64 {
+        self.ultimate() / self.model_factor
+    }
+
+    /// Design resistance Rc;d = Rs;k / γs + Rb;k / γb (kN).
+    pub fn design(&self) -> f64 {
+        self.shaft_resistance() / (self.model_factor * self.shaft_factor)
+            + self.base_resistance() / (self.model_factor * self.base_factor)
+    }
+}
+
+impl GroundModel {
+    /// Axial compression resistance of a single pile. Shaft friction is integrated from the
+    /// head, or the top of the model if lower, to the toe: the alpha method in cohesive units,
+    /// K tanδ σ′v in granular units and a rock socket from the UCS (kPa) in rock. End bearing
+    /// uses 9 cu, Nq σ′v or the UCS of the unit at the toe.
+    pub fn pile_resistance(
+        &self,
+        pile: &Pile,
+        options: &PileOptions,
+    ) -> Result<PileCapacity, GroundModelError> {
+        let model = match &options.partial_factors {
+            Some(pf) => self.apply_partial_factors(pf),
+            None => self.clone(),
+        };
+        let datum = model.get_top_level();
+        let head = pile.head_level.min(datum);
+        if pile.toe_level >= head {
+            return Err(GroundModelError::InvalidGeometry {
+                reference: "pile".to_string(),
+                message: "toe must be below the head and the top of the model".to_string(),
+            });
+        }
+
+        let mut layers = Vec::new();
+        for (top, base, params) in model.stress_intervals() {
+            let top = top.min(head);
+            let base = base.max(pile.toe_level);
+            if top <= base {
+                continue;
+            }
+
+            let h = (top - base) / SHAFT_SUBLAYERS as f64;
+            let mids: Vec<f64> = (0..SHAFT_SUBLAYERS)
+                .map(|j| top - (j as f64 + 0.5) * h)
+                .collect();
+            let stresses = model.stress_profile(&mids);
+            let mut friction_sum = 0.0;
+            let mut method = ShaftMethod::Alpha;
+            for point in stresses {
+                let resolved = params.resolve_profiles(datum - point.level, point.level);
+                let (tau, used) = shaft_friction(&resolved, pile, options, point.effective)?;
+                friction_sum += tau;
+                method = used;
+            }
+            let shaft_friction = friction_sum / SHAFT_SUBLAYERS as f64;
+
+            layers.push(PileLayerResistance {
+                reference: params.reference.clone(),
+                top_level: top,
+                base_level: base,
+                method,
+                shaft_friction,
+                shaft_resistance: shaft_friction * pile.section.perimeter() * (top - base),
+            });
+        }
+
+        let toe_params = model.get_params_at_level(pile.toe_level - LEVEL_TOLERANCE)?;
+        let toe_stress = model.stress_profile(&[pile.toe_level])[0].effective;
+        let end_bearing = end_bearing(&toe_params, toe_stress)?;
+
+        Ok(PileCapacity {
+            layers,
+            base: PileBaseResistance {
+                reference: toe_params.reference.clone(),
+                level: pile.toe_level,
+                end_bearing,
+                base_resistance: end_bearing * pile.section.base_area(),
+            },
+            model_factor: options.model_factor,
+            shaft_factor: options.shaft_factor,
+            base_factor: options.base_factor,
+        })
+    }
+}
+
+/// Unit shaft friction (kPa) at a level with vertical effective stress `sigma_v`.
+fn shaft_friction(
+    params: &SoilParams,
+    pile: &Pile,
+    options: &PileOptions,
+    sigma_v: f64,
+) -> Result<(f64, ShaftMethod), GroundModelError> {
+    match params.behaviour {
+        SoilType::Cohesive => {
+            let cu = params.required(params.cu, "cu")?;
+            let alpha = options
+                .adhesion_factor
+                .unwrap_or_else(|| match pile.pile_type {
+                    PileType::Bored => 0.5,
+                    PileType::Driven => api_adhesion_factor(cu, sigma_v),
+                });
+            Ok((alpha * cu, ShaftMethod::Alpha))
+        }
+        SoilType::Granular => {
+            let phi = params.required(params.phi_prime, "phi_prime")?;
+            let (k_ratio, delta_ratio) = match pile.pile_type {
+                PileType::Bored => (1.0, 1.0),
+                PileType::Driven => (1.5, 0.8),
+            };
+            let k = options.earth_pressure_ratio.unwrap_or(k_ratio) * params.k0_at(sigma_v)?;
+            let delta = options.interface_friction_ratio.unwrap_or(delta_ratio) * phi;
+            Ok((k * delta.tan() * sigma_v.max(0.0), ShaftMethod::Beta))
+        }
+        SoilType::Rock => {
+            let ucs_mpa = params.required(params.ucs, "ucs")? / 1000.0;
+            Ok((
+                ROCK_SHAFT_COEFFICIENT * ucs_mpa.max(0.0).sqrt() * 1000.0,
+                ShaftMethod::RockSocket,
+            ))
+        }
+    }
+}
+
+/// API RP2A adhesion factor for driven piles from ψ = cu/σ′v, capped at 1.
+fn api_adhesion_factor(cu: f64, sigma_v: f64) -> f64 {
+    if sigma_v <= 0.0 {
+        return 0.5;
+    }
+    let psi = cu / sigma_v;
+    let alpha = if psi <= 1.0 {
+        0.5 * psi.powf(-0.5)
+    } else {
+        0.5 * psi.powf(-0.25)
+    };
+    alpha.min(1.0)
+}
+
+/// Unit end bearing (kPa) with vertical effective stress `sigma_v` at the toe.
+fn end_bearing(params: &SoilParams, sigma_v: f64) -> Result<f64, GroundModelError> {
+    match params.behaviour {
+        SoilType::Cohesive => Ok(CLAY_END_BEARING_FACTOR * params.required(params.cu, "cu")?),
+        SoilType::Granular => {
+            let phi = params.required(params.phi_prime, "phi_prime")?;
+            let nq = (PI * phi.tan()).exp() * (FRAC_PI_4 + phi / 2.0).tan().powi(2);
+            Ok(nq * sigma_v.max(0.0))
+        }
+        SoilType::Rock => {
+            let ucs_mpa = params.required(params.ucs, "ucs")? / 1000.0;
+            Ok(ROCK_BASE_COEFFICIENT * ucs_mpa.max(0.0).sqrt() * 1000.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::soil;
+    use crate::SoilLayer;
+
+    fn bored(diameter: f64, toe_level: f64) -> Pile {
+        Pile {
+            pile_type: PileType::Bored,
+            section: PileSection::Circular { diameter },
+            head_level: 0.0,
+            toe_level,
+        }
+    }
+
+    #[test]
+    fn bored_pile_in_clay() {
+        let mut clay = soil("Clay", SoilType::Cohesive, 18.0);
+        clay.cu = Some(100.0);
+        let model = GroundModel::quick_init(clay, 0.0, -100.0);
+
+        let result = model
+            .pile_resistance(&bored(0.6, -20.0), &PileOptions::default())
+            .unwrap();
+
+        assert_eq!(result.layers.len(), 1);
+        assert_eq!(result.layers[0].method, ShaftMethod::Alpha);
+        assert!((result.shaft_resistance() - 0.5 * 100.0 * PI * 0.6 * 20.0).abs() < 1e-9);
+        assert!((result.base_resistance() - 9.0 * 100.0 * PI * 0.09).abs() < 1e-9);
+    }
+
+    #[test]
+    fn bored_pile_in_sand() {
+        let mut sand = soil("Sand", SoilType::Granular, 18.0);
+        let phi = 30.0_f64.to_radians();
+        sand.phi_prime = Some(phi);
+        let model = GroundModel::quick_init(sand, 0.0, -100.0);
+
+        let result = model
+            .pile_resistance(&bored(0.5, -10.0), &PileOptions::default())
+            .unwrap();
+
+        // K0 tanφ′ times the mean σ′v of 90 kPa
+        let k0 = 1.0 - phi.sin();
+        let shaft = k0 * phi.tan() * 90.0 * PI * 0.5 * 10.0;
+        assert!((result.shaft_resistance() - shaft).abs() < 1e-9);
+        let nq = 18.401;
+        assert!((result.base.end_bearing / 180.0 - nq).abs() < 1e-3);
+    }
+
+    #[test]
+    fn rock_socket_with_design_factors() {
+        let mut clay = soil("Clay", SoilType::Cohesive, 18.0);
+        clay.cu = Some(60.0);
+        let mut rock = soil("Mudstone", SoilType::Rock, 18.0);
+        rock.ucs = Some(4000.0);
+        let model = GroundModel::new(
+            vec![
+                SoilLayer::with_unit_reference(0.0, -8.0, String::new(), "Clay".to_string()),
+                SoilLayer::with_unit_reference(-8.0, -30.0, String::new(), "Mudstone".to_string()),
+            ],
+            vec![clay, rock],
+        );
+
+        let options = PileOptions {
+            model_factor: 1.4,
+            shaft_factor: 1.6,
+            base_factor: 2.0,
+            ..Default::default()
+        };
+        let result = model.pile_resistance(&bored(0.9, -11.0), &options).unwrap();
+
+        assert_eq!(result.layers.len(), 2);
+        let socket = &result.layers[1];
+        assert_eq!(socket.method, ShaftMethod::RockSocket);
+        assert!((socket.shaft_friction - 900.0).abs() < 1e-9);
+        assert_eq!(result.base.reference, "Mudstone");
+        assert!((result.base.end_bearing - 9660.0).abs() < 1e-9);
+
+        let design = result.shaft_resistance() / (1.4 * 1.6) + result.base_resistance() / 2.8;
+        assert!((result.design() - design).abs() < 1e-9);
+        assert!(result.design() < result.characteristic());
+    }
+
+    #[test]
+    fn driven_pile_uses_api_adhesion() {
+        assert_eq!(api_adhesion_factor(50.0, 100.0), 0.5 * 0.5_f64.powf(-0.5));
+        assert_eq!(api_adhesion_factor(20.0, 100.0), 1.0);
+        assert!((api_adhesion_factor(400.0, 100.0) - 0.5 * 4.0_f64.powf(-0.25)).abs() < 1e-12);
+
+        let mut clay = soil("Clay", SoilType::Cohesive, 18.0);
+        clay.cu = Some(100.0);
+        let model = GroundModel::quick_init(clay, 0.0, -100.0);
+        let driven = Pile {
+            pile_type: PileType::Driven,
+            section: PileSection::Square { width: 0.4 },
+            head_level: 0.0,
+            toe_level: -15.0,
+        };
+        assert!(model
+            .pile_resistance(
+                &driven,
+                &PileOptions {
+                    adhesion_factor: Some(0.6),
+                    ..Default::default()
+                }
+            )
+            .is_ok_and(|result| (result.layers[0].shaft_friction - 60.0).abs() < 1e-12));
+    }
+}
@@ -0,0 +1,639 @@
+use crate::loads::Load;
+use crate::{interpolate, GroundModel, GroundModelError, SoilParams, SoilType};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// Permeability ratio at which a neighbouring unit is treated as a drainage boundary.
+const DRAINING_PERMEABILITY_RATIO: f64 = 10.0;
+
+/// Time factor below which the average degree of consolidation is `sqrt(4 Tv / π)`.
+const SMALL_TIME_FACTOR: f64 = 0.01;
+
+/// Sublayers each unit is split into when integrating elastic settlement.
+const ELASTIC_SUBLAYERS: usize = 10;
+
+/// Settlement of a rigid foundation as a fraction of the flexible settlement at its centre.
+/// Exact for a circle on a half-space and within a few percent for rectangles.
+const RIGID_FOUNDATION_FACTOR: f64 = PI / 4.0;
+
+/// Source of the change in vertical effective stress.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum StressChange {
+    /// Increments from the model's `loads` below plan position `(x, y)`.
+    ModelLoads { x: f64, y: f64 },
+    /// Increment from a single load below plan position `(x, y)`, spread with the model's
+    /// stress distribution.
+    Load { load: Load, x: f64, y: f64 },
+    /// Δσ′ (kPa) against level (m) as `[level, increment]` points, interpolated linearly and
+    /// held constant beyond the ends.
+    Profile(Vec<[f64; 2]>),
+}
+
+impl Default for StressChange {
+    fn default() -> Self {
+        StressChange::ModelLoads { x: 0.0, y: 0.0 }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsolidationOptions {
+    pub stress_change: StressChange,
+    /// Number of sublayers each unit is split into.
+    pub sublayers: usize,
+}
+
+impl Default for ConsolidationOptions {
+    fn default() -> Self {
+        ConsolidationOptions {
+            stress_change: StressChange::default(),
+            sublayers: 10,
+        }
+    }
+}
+
+/// How pore water leaves a consolidating layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Drainage {
+    /// Granular, or no coefficient of consolidation: settles as the load is applied.
+    Free,
+    /// Drains through one boundary only.
+    Single,
+    /// Drains through both boundaries.
+    Double,
+    /// Neither boundary drains, so the layer does not consolidate.
+    Sealed,
+}
+
+/// Primary consolidation of one layer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayerSettlement {
+    pub reference: String,
+    pub top_level: f64,
+    pub base_level: f64,
+    /// Thickness-weighted mean Δσ′ (kPa).
+    pub mean_stress_change: f64,
+    /// Final primary consolidation settlement (m).
+    pub settlement: f64,
+    pub coefficient_of_consolidation: f64,
+    pub drainage: Drainage,
+    /// Longest distance pore water travels to a drainage boundary (m).
+    pub drainage_path: f64,
+}
+
+impl LayerSettlement {
+    /// Average degree of consolidation at `time`, in the time units of the coefficient of
+    /// consolidation.
+    pub fn degree_at(&self, time: f64) -> f64 {
+        match self.drainage {
+            Drainage::Free => 1.0,
+            Drainage::Sealed => 0.0,
+            Drainage::Single | Drainage::Double => {
+                let time_factor =
+                    self.coefficient_of_consolidation * time / self.drainage_path.powi(2);
+                average_degree_of_consolidation(time_factor)
+            }
+        }
+    }
+
+    pub fn settlement_at(&self, time: f64) -> f64 {
+        self.settlement * self.degree_at(time)
+    }
+}
+
+/// Primary consolidation settlement through a ground model, layer by layer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsolidationSettlement {
+    pub layers: Vec<LayerSettlement>,
+}
+
+impl ConsolidationSettlement {
+    /// Final primary consolidation settlement (m).
+    pub fn total(&self) -> f64 {
+        self.layers.iter().map(|layer| layer.settlement).sum()
+    }
+
+    /// Settlement reached at `time` (m).
+    pub fn settlement_at(&self, time: f64) -> f64 {
+        self.layers
+            .iter()
+            .map(|layer| layer.settlement_at(time))
+            .sum()
+    }
+
+    /// Overall degree of consolidation at `time`, weighted by the settlement of each layer.
+    pub fn degree_at(&self, time: f64) -> f64 {
+        let total = self.total();
+        if total == 0.0 {
+            1.0
+        } else {
+            self.settlement_at(time) / total
+        }
+    }
+
+    /// `[time, settlement]` points for each of `times`.
+    pub fn time_settlement_curve(&self, times: &[f64]) -> Vec<[f64; 2]> {
+        times
+            .iter()
+            .map(|&time| [time, self.settlement_at(time)])
+            .collect()
+    }
+}
+
+/// Plan shape of a [`Foundation`] (m).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FoundationShape {
+    Rectangular { width: f64, length: f64 },
+    Circular { diameter: f64 },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FoundationStiffness {
+    /// Uniform pressure; settlement is reported at the centre.
+    #[default]
+    Flexible,
+    /// Uniform settlement.
+    Rigid,
+}
+
+/// A foundation applying a uniform net pressure.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Foundation {
+    pub shape: FoundationShape,
+    /// Net bearing pressure (kPa).
+    pub pressure: f64,
+    /// Founding level. The top of the ground model when `None`.
+    #[serde(default)]
+    pub level: Option<f64>,
+    #[serde(default)]
+    pub stiffness: FoundationStiffness,
+}
+
+impl Foundation {
+    pub fn rectangular(width: f64, length: f64, pressure: f64) -> Self {
+        Foundation {
+            shape: FoundationShape::Rectangular { width, length },
+            pressure,
+            level: None,
+            stiffness: FoundationStiffness::Flexible,
+        }
+    }
+
+    pub fn circular(diameter: f64, pressure: f64) -> Self {
+        Foundation {
+            shape: FoundationShape::Circular { diameter },
+            pressure,
+            level: None,
+            stiffness: FoundationStiffness::Flexible,
+        }
+    }
+
+    pub fn at_level(mut self, level: f64) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    pub fn rigid(mut self) -> Self {
+        self.stiffness = FoundationStiffness::Rigid;
+        self
+    }
+
+    /// Settlement (m) per unit `q/E` from compression of the ground between the founding level
+    /// and `depth` below it, for a half-space with Poisson's ratio `nu`. Flexible, at the centre.
+    fn compression_to(&self, depth: f64, nu: f64) -> f64 {
+        match self.shape {
+            FoundationShape::Rectangular { width, length } => {
+                4.0 * steinbrenner_corner(width / 2.0, length / 2.0, depth, nu)
+            }
+            FoundationShape::Circular { diameter } => {
+                let a = diameter / 2.0;
+                let displacement = |z: f64| {
+                    if z.is_infinite() {
+                        return 0.0;
+                    }
+                    let r = (a * a + z * z).sqrt();
+                    (1.0 + nu) * ((1.0 - 2.0 * nu) * (r - z) + a * a / r)
+                };
+                displacement(0.0) - displacement(depth)
+            }
+        }
+    }
+}
+
+/// Elastic settlement contributed by one layer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElasticLayerSettlement {
+    pub reference: String,
+    pub top_level: f64,
+    pub base_level: f64,
+    /// Young's modulus at the top of the layer (kPa).
+    pub youngs_modulus: f64,
+    pub poissons_ratio: f64,
+    /// Compression of the layer (m).
+    pub settlement: f64,
+}
+
+/// Immediate elastic settlement of a foundation, layer by layer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElasticSettlement {
+    pub layers: Vec<ElasticLayerSettlement>,
+}
+
+impl ElasticSettlement {
+    /// Settlement of the foundation (m).
+    pub fn total(&self) -> f64 {
+        self.layers.iter().map(|layer| layer.settlement).sum()
+    }
+}
+
+/// Terzaghi average degree of consolidation for time factor `Tv = cv t / d²`, for a uniform
+/// initial excess pore pressure.
+pub fn average_degree_of_consolidation(time_factor: f64) -> f64 {
+    if time_factor <= 0.0 {
+        return 0.0;
+    }
+    if time_factor < SMALL_TIME_FACTOR {
+        return (4.0 * time_factor / PI).sqrt();
+    }
+
+    let mut remaining = 0.0;
+    for m in 0.. {
+        let big_m = PI * (2 * m + 1) as f64 / 2.0;
+        let term = 2.0 / big_m.powi(2) * (-big_m.powi(2) * time_factor).exp();
+        remaining += term;
+        if term < 1e-12 {
+            break;
+        }
+    }
+    (1.0 - remaining).clamp(0.0, 1.0)
+}
+
+impl GroundModel {
+    /// Immediate settlement of a foundation from `youngs_modulus` and `poissons_ratio`. Each
+    /// layer below the founding level contributes the compression between its top and base
+    /// from Steinbrenner's (rectangles) or the Boussinesq axial (circles) solution, using its
+    /// own stiffness. Layers stop at `rigid_boundary`; the lowest unit extends indefinitely if
+    /// it has no base.
+    pub fn elastic_settlement(
+        &self,
+        foundation: &Foundation,
+    ) -> Result<ElasticSettlement, GroundModelError> {
+        let datum = self.get_top_level();
+        let founding_level = foundation.level.unwrap_or(datum);
+        let rigid_factor = match foundation.stiffness {
+            FoundationStiffness::Flexible => 1.0,
+            FoundationStiffness::Rigid => RIGID_FOUNDATION_FACTOR,
+        };
+
+        let mut layers = Vec::new();
+        for (top, base, params) in self.stress_intervals() {
+            let top = top.min(founding_level);
+            let base = self.rigid_boundary.map_or(base, |rigid| base.max(rigid));
+            if top <= base {
+                continue;
+            }
+
+            // An unbounded unit is taken as a single piece with its stiffness at the top
+            let bounds: Vec<f64> = if base.is_finite() {
+                let h = (top - base) / ELASTIC_SUBLAYERS as f64;
+                (0..=ELASTIC_SUBLAYERS)
+                    .map(|j| top - j as f64 * h)
+                    .collect()
+            } else {
+                vec![top, base]
+            };
+            let mut settlement = 0.0;
+            for pair in bounds.windows(2) {
+                let (upper, lower) = (pair[0], pair[1]);
+                let mid = if lower.is_finite() {
+                    (upper + lower) / 2.0
+                } else {
+                    upper
+                };
+                let resolved = params.resolve_profiles(datum - mid, mid);
+                let (youngs_modulus, nu) = elastic_properties(&resolved)?;
+                settlement += foundation.pressure / youngs_modulus
+                    * (foundation.compression_to(founding_level - lower, nu)
+                        - foundation.compression_to(founding_level - upper, nu));
+            }
+
+            let at_top = params.resolve_profiles(datum - top, top);
+            layers.push(ElasticLayerSettlement {
+                reference: params.reference.clone(),
+                top_level: top,
+                base_level: base,
+                youngs_modulus: at_top.youngs_modulus,
+                poissons_ratio: at_top.poissons_ratio,
+                settlement: settlement * rigid_factor,
+            });
+        }
+
+        Ok(ElasticSettlement { layers })
+    }
+
+    /// Primary consolidation settlement `mv·Δσ′·H`, summed over sublayers of each unit down
+    /// to `rigid_boundary`. Drainage is taken through the ground surface and through any
+    /// neighbouring unit that is granular or at least ten times more permeable, with
+    /// permeability estimated as `cv·mv·γw`.
+    ///
+    /// Where a unit is overconsolidated, `mv_recompression` is used up to the preconsolidation
+    /// pressure (see [`SoilParams::mv_for`]).
+    pub fn consolidation_settlement(
+        &self,
+        options: &ConsolidationOptions,
+    ) -> Result<ConsolidationSettlement, GroundModelError> {
+        let datum = self.get_top_level();
+        let sublayers = options.sublayers.max(1);
+        let profile = match &options.stress_change {
+            StressChange::Profile(points) => {
+                let mut points = points.clone();
+                points.sort_by(|a, b| a[0].total_cmp(&b[0]));
+                points
+            }
+            _ => Vec::new(),
+        };
+        let stress_change = |level: f64| match &options.stress_change {
+            StressChange::ModelLoads { x, y } => self.load_stress_at(level, *x, *y),
+            StressChange::Load { load, x, y } => self.single_load_stress_at(load, level, *x, *y),
+            StressChange::Profile(_) => interpolate(&profile, level).unwrap_or(0.0),
+        };
+
+        let intervals: Vec<(f64, f64, &SoilParams)> = self
+            .stress_intervals()
+            .into_iter()
+            .filter_map(|(top, base, params)| {
+                let base = self.rigid_boundary.map_or(base, |rigid| base.max(rigid));
+                (top > base).then_some((top, base, params))
+            })
+            .collect();
+
+        let mut layers = Vec::new();
+        for (i, &(top, base, params)) in intervals.iter().enumerate() {
+            if !base.is_finite() {
+                return Err(GroundModelError::InvalidGeometry {
+                    reference: params.reference.clone(),
+                    message: "unit has no base level; set a rigid boundary".to_string(),
+                });
+            }
+
+            let thickness = top - base;
+            let h = thickness / sublayers as f64;
+            let mut settlement = 0.0;
+            let mut stress_area = 0.0;
+            for j in 0..sublayers {
+                let mid = top - (j as f64 + 0.5) * h;
+                let delta = stress_change(mid);
+                let effective = self.get_effective_stress_at_level(mid);
+                let mv = params
+                    .resolve_profiles(datum - mid, mid)
+                    .mv_for(effective, delta);
+                settlement += mv * delta * h;
+                stress_area += delta * h;
+            }
+
+            let above = i
+                .checked_sub(1)
+                .map(|k| intervals[k])
+                .filter(|(_, above_base, _)| (above_base - top).abs() < 1e-9);
+            let below = intervals
+                .get(i + 1)
+                .filter(|(below_top, _, _)| (below_top - base).abs() < 1e-9);
+            let top_drains = top >= datum || above.is_some_and(|(_, _, n)| drains_into(params, n));
+            let base_drains = below.is_some_and(|(_, _, n)| drains_into(params, n));
+
+            let cv = params.coefficient_of_consolidation;
+            let (drainage, drainage_path) = if params.behaviour == SoilType::Granular || cv <= 0.0 {
+                (Drainage::Free, 0.0)
+            } else {
+                match (top_drains, base_drains) {
+                    (true, true) => (Drainage::Double, thickness / 2.0),
+                    (true, false) | (false, true) => (Drainage::Single, thickness),
+                    (false, false) => (Drainage::Sealed, thickness),
+                }
+            };
+
+            layers.push(LayerSettlement {
+                reference: params.reference.clone(),
+                top_level: top,
+                base_level: base,
+                mean_stress_change: stress_area / thickness,
+                settlement,
+                coefficient_of_consolidation: cv,
+                drainage,
+                drainage_path,
+            });
+        }
+
+        Ok(ConsolidationSettlement { layers })
+    }
+}
+
+/// Steinbrenner settlement (per unit `q/E`) of the corner of a flexible `b` × `l` rectangle on
+/// a layer `depth` thick over a rigid base.
+fn steinbrenner_corner(b: f64, l: f64, depth: f64, nu: f64) -> f64 {
+    let (b, l) = if b <= l { (b, l) } else { (l, b) };
+    let m = l / b;
+    let root_m = (m * m + 1.0).sqrt();
+    let (f1, f2) = if depth.is_infinite() {
+        (((1.0 + root_m) / m).ln() * m + (m + root_m).ln(), 0.0)
+    } else {
+        let n = depth / b;
+        let root_mn = (m * m + n * n).sqrt();
+        let root_mn1 = (m * m + n * n + 1.0).sqrt();
+        (
+            m * ((1.0 + root_m) * root_mn / (m * (1.0 + root_mn1))).ln()
+                + ((m + root_m) * (1.0 + n * n).sqrt() / (m + root_mn1)).ln(),
+            n / 2.0 * (m / (n * root_mn1)).atan(),
+        )
+    };
+    b / PI * ((1.0 - nu * nu) * f1 + (1.0 - nu - 2.0 * nu * nu) * f2)
+}
+
+fn elastic_properties(params: &SoilParams) -> Result<(f64, f64), GroundModelError> {
+    if params.youngs_modulus == 0.0 {
+        return Err(GroundModelError::MissingParameter {
+            reference: params.reference.clone(),
+            name: "youngs_modulus",
+        });
+    }
+    if params.youngs_modulus < 0.0 {
+        return Err(GroundModelError::InvalidParameter {
+            reference: params.reference.clone(),
+            name: "youngs_modulus",
+            message: "must be positive".to_string(),
+        });
+    }
+    if !(-1.0..=0.5).contains(&params.poissons_ratio) {
+        return Err(GroundModelError::InvalidParameter {
+            reference: params.reference.clone(),
+            name: "poissons_ratio",
+            message: "must be between -1 and 0.5".to_string(),
+        });
+    }
+    Ok((params.youngs_modulus, params.poissons_ratio))
+}
+
+/// Whether water drains from a layer of `params` into its neighbour.
+fn drains_into(params: &SoilParams, neighbour: &SoilParams) -> bool {
+    let permeability = |p: &SoilParams| p.coefficient_of_consolidation * p.mv;
+    neighbour.behaviour == SoilType::Granular
+        || (permeability(neighbour) > 0.0
+            && permeability(neighbour) >= DRAINING_PERMEABILITY_RATIO * permeability(params))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::clay;
+    use crate::SoilLayer;
+
+    #[test]
+    fn terzaghi_degree_of_consolidation() {
+        assert_eq!(average_degree_of_consolidation(0.0), 0.0);
+        // Tabulated values: U = 50% at Tv = 0.197 and U = 90% at Tv = 0.848
+        assert!((average_degree_of_consolidation(0.197) - 0.5).abs() < 1e-3);
+        assert!((average_degree_of_consolidation(0.848) - 0.9).abs() < 1e-3);
+        // Both branches agree at the switch-over
+        let below = average_degree_of_consolidation(SMALL_TIME_FACTOR * (1.0 - 1e-9));
+        let above = average_degree_of_consolidation(SMALL_TIME_FACTOR);
+        assert!((below - above).abs() < 1e-6);
+    }
+
+    #[test]
+    fn clay_between_sands_drains_both_ways() {
+        let mut sand = clay("Sand", 0.0, 0.0);
+        sand.behaviour = SoilType::Granular;
+        let mut model = GroundModel::new(
+            vec![
+                SoilLayer::with_unit_reference(10.0, 8.0, String::new(), "Sand".to_string()),
+                SoilLayer::with_unit_reference(8.0, 4.0, String::new(), "Clay".to_string()),
+                SoilLayer::with_unit_reference(4.0, 0.0, String::new(), "Sand".to_string()),
+            ],
+            vec![sand, clay("Clay", 0.0002, 2.0)],
+        );
+        model.groundwater = 10.0;
+
+        let result = model
+            .consolidation_settlement(&ConsolidationOptions {
+                stress_change: StressChange::Load {
+                    load: Load::uniform(100.0),
+                    x: 0.0,
+                    y: 0.0,
+                },
+                ..Default::default()
+            })
+            .unwrap();
+
+        let clay_layer = &result.layers[1];
+        assert_eq!(clay_layer.drainage, Drainage::Double);
+        assert!((clay_layer.drainage_path - 2.0).abs() < 1e-12);
+        // mv·Δσ′·H = 0.0002 × 100 × 4
+        assert!((result.total() - 0.08).abs() < 1e-12);
+        // Tv = 2 × 0.394 / 2² = 0.197 gives half the settlement
+        assert!((result.settlement_at(0.394) - 0.04).abs() < 1e-4);
+        assert!((result.degree_at(1e6) - 1.0).abs() < 1e-12);
+
+        // Overconsolidated by more than the load, the clay only recompresses
+        model.soil_params[1].pop = Some(150.0);
+        model.soil_params[1].mv_recompression = Some(0.00005);
+        let result = model
+            .consolidation_settlement(&ConsolidationOptions {
+                stress_change: StressChange::Profile(vec![[10.0, 100.0]]),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!((result.total() - 0.02).abs() < 1e-12);
+    }
+
+    #[test]
+    fn clay_on_rigid_base_drains_upwards() {
+        let mut model = GroundModel::quick_init(clay("Clay", 0.0001, 1.0), 0.0, 0.0);
+        model.rigid_boundary = Some(-10.0);
+
+        let result = model
+            .consolidation_settlement(&ConsolidationOptions {
+                stress_change: StressChange::Profile(vec![[0.0, 100.0], [-10.0, 0.0]]),
+                sublayers: 20,
+            })
+            .unwrap();
+
+        assert_eq!(result.layers.len(), 1);
+        assert_eq!(result.layers[0].drainage, Drainage::Single);
+        assert!((result.layers[0].drainage_path - 10.0).abs() < 1e-12);
+        assert!((result.layers[0].mean_stress_change - 50.0).abs() < 1e-9);
+        assert!((result.total() - 0.05).abs() < 1e-12);
+
+        let curve = result.time_settlement_curve(&[0.0, 19.7]);
+        assert_eq!(curve[0], [0.0, 0.0]);
+        assert!((curve[1][1] - 0.025).abs() < 1e-4);
+    }
+
+    #[test]
+    fn elastic_settlement_on_half_space() {
+        let mut soil = clay("Clay", 0.0, 0.0);
+        soil.youngs_modulus = 20000.0;
+        soil.poissons_ratio = 0.3;
+        let mut model = GroundModel::quick_init(soil, 0.0, 0.0);
+        model.soil_layers[0].base_level = None;
+
+        // Flexible circle: 2 q a (1 - ν²) / E at the centre, π/4 of that when rigid
+        let circle = Foundation::circular(2.0, 100.0);
+        let expected = 2.0 * 100.0 * 1.0 * (1.0 - 0.09) / 20000.0;
+        let flexible = model.elastic_settlement(&circle).unwrap().total();
+        assert!((flexible - expected).abs() < 1e-12);
+        let rigid = model.elastic_settlement(&circle.rigid()).unwrap().total();
+        assert!((rigid - expected * PI / 4.0).abs() < 1e-12);
+
+        // Centre of a flexible square: influence factor 1.122
+        let square = model
+            .elastic_settlement(&Foundation::rectangular(2.0, 2.0, 100.0))
+            .unwrap()
+            .total();
+        let influence = square * 20000.0 / (100.0 * 2.0 * (1.0 - 0.09));
+        assert!((influence - 1.1222).abs() < 1e-4);
+    }
+
+    #[test]
+    fn elastic_settlement_sums_layers_to_rigid_boundary() {
+        let mut upper = clay("Upper", 0.0, 0.0);
+        upper.youngs_modulus = 10000.0;
+        let mut lower = clay("Lower", 0.0, 0.0);
+        lower.youngs_modulus = 10000.0;
+        let mut model = GroundModel::new(
+            vec![
+                SoilLayer::with_unit_reference(0.0, -3.0, String::new(), "Upper".to_string()),
+                SoilLayer::with_unit_reference(-3.0, -20.0, String::new(), "Lower".to_string()),
+            ],
+            vec![upper.clone(), lower],
+        );
+        model.rigid_boundary = Some(-6.0);
+
+        let footing = Foundation::rectangular(3.0, 6.0, 150.0);
+        let result = model.elastic_settlement(&footing).unwrap();
+        assert_eq!(result.layers.len(), 2);
+        assert_eq!(result.layers[1].base_level, -6.0);
+
+        // Two layers with the same stiffness equal one 6 m layer (Steinbrenner, H/B = 2)
+        let single = 150.0 / 10000.0 * 4.0 * steinbrenner_corner(1.5, 3.0, 6.0, 0.3);
+        assert!((result.total() - single).abs() < 1e-12);
+
+        model.soil_params[1].youngs_modulus = 0.0;
+        assert!(matches!(
+            model.elastic_settlement(&footing),
+            Err(GroundModelError::MissingParameter {
+                name: "youngs_modulus",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn unbounded_layer_needs_rigid_boundary() {
+        let mut model = GroundModel::quick_init(clay("Clay", 0.0001, 1.0), 0.0, 0.0);
+        model.soil_layers[0].base_level = None;
+        let result = model.consolidation_settlement(&ConsolidationOptions::default());
+        assert!(matches!(
+            result,
+            Err(GroundModelError::InvalidGeometry { .. })
+        ));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::units::Stress;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -111,6 +112,14 @@ pub struct StrengthParameters {
     pub units: String,
 }
 
+impl StrengthParameters {
+    /// The typical value as a stress, converted from `units` (UCS ranges are in MPa, `cu`
+    /// in kPa). `None` for SPT N values and angles.
+    pub fn typical_stress(&self) -> Option<Stress> {
+        Stress::from_unit(self.value_range.typical_value, &self.units)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpellingCorrection {
     pub original: String,
@@ -560,8 +569,7 @@ fn match_pattern(input: &str, pos: usize, pattern: &str) -> Option<usize> {
     if input_lower[pos..].starts_with(&pattern_lower) {
         let end = pos + pattern_lower.len();
         let next_char = input.chars().nth(end);
-        if end >= input.len() || next_char.map_or(true, |c| c.is_whitespace() || ",;.)".contains(c))
-        {
+        if end >= input.len() || next_char.is_none_or(|c| c.is_whitespace() || ",;.)".contains(c)) {
             return Some(end);
         }
     }
@@ -1566,17 +1574,13 @@ pub fn validate_soil_description(
         }
     }
 
-    if opts.require_strength_params {
-        if desc.strength_parameters.is_empty() {
-            let can_derive = desc.consistency.is_some()
-                || desc.density.is_some()
-                || desc.rock_strength.is_some();
-            if can_derive {
-                warnings.push(
-                    "Strength parameters could be derived from description but are missing"
-                        .to_string(),
-                );
-            }
+    if opts.require_strength_params && desc.strength_parameters.is_empty() {
+        let can_derive =
+            desc.consistency.is_some() || desc.density.is_some() || desc.rock_strength.is_some();
+        if can_derive {
+            warnings.push(
+                "Strength parameters could be derived from description but are missing".to_string(),
+            );
         }
     }
 
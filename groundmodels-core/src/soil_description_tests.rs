@@ -2,7 +2,8 @@
 mod tests {
     use crate::soil_description::{
         generate_description, parse_soil_description, validate_soil_description, Consistency,
-        Density, MaterialType, SoilType, ValidationOptions,
+        Density, MaterialType, SoilType, StrengthParameterType, StrengthParameters, StrengthRange,
+        ValidationOptions,
     };
 
     #[test]
@@ -32,6 +33,29 @@ mod tests {
         // Strength parameters are derived from rock strength when it matches expected forms
     }
 
+    #[test]
+    fn strength_parameters_convert_to_kpa() {
+        let ucs = StrengthParameters {
+            parameter_type: StrengthParameterType::Ucs,
+            value_range: StrengthRange {
+                lower_bound: 12.5,
+                upper_bound: 50.0,
+                typical_value: 25.0,
+            },
+            confidence: 0.8,
+            units: "MPa".to_string(),
+        };
+        assert_eq!(ucs.typical_stress().unwrap().kpa(), 25000.0);
+
+        let clay = parse_soil_description("Firm brown clay, moist");
+        let cu = &clay.strength_parameters[0];
+        assert_eq!(cu.units, "kPa");
+        assert_eq!(
+            cu.typical_stress().unwrap().kpa(),
+            cu.value_range.typical_value
+        );
+    }
+
     #[test]
     fn validation_catches_invalid_combo() {
         let desc = parse_soil_description("Firm dense clay");
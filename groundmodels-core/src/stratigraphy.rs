@@ -0,0 +1,283 @@
+//! Layer order, boundaries and continuity.
+//!
+//! [`GroundModel::stratigraphy`] sorts the layers from the top down, fills in missing base
+//! levels and clips overlaps so that every level belongs to at most one layer. A level on an
+//! interface belongs to the upper layer: a layer covers `base <= level < top`, and the highest
+//! layer also covers its top.
+
+use crate::{GroundModel, SoilLayer};
+use serde::{Deserialize, Serialize};
+
+/// Default tolerance (m) below which layer boundaries are treated as coincident.
+pub const LEVEL_TOLERANCE: f64 = 1e-6;
+
+/// One layer of a [`Stratigraphy`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayerInterval {
+    /// Index of the layer in `GroundModel::soil_layers`.
+    pub index: usize,
+    pub unit_reference: String,
+    /// Top level, lowered to the base of the layer above where they overlap.
+    pub top: f64,
+    /// `None` when the lowest layer has no base level and extends indefinitely.
+    pub base: Option<f64>,
+    /// The base was taken from the layer below because `base_level` is not set.
+    pub base_inferred: bool,
+}
+
+impl LayerInterval {
+    pub fn thickness(&self) -> Option<f64> {
+        self.base.map(|base| self.top - base)
+    }
+}
+
+/// Ground between layers that no layer covers, or covered by more than one layer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum StratigraphyIssue {
+    Gap {
+        upper: String,
+        lower: String,
+        top: f64,
+        base: f64,
+    },
+    /// `lower` starts above the base of `upper`; the upper layer is kept between `top` and
+    /// `base`.
+    Overlap {
+        upper: String,
+        lower: String,
+        top: f64,
+        base: f64,
+    },
+}
+
+/// Layers ordered from the top down with non-overlapping boundaries.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Stratigraphy {
+    pub layers: Vec<LayerInterval>,
+    pub issues: Vec<StratigraphyIssue>,
+}
+
+impl Stratigraphy {
+    /// Normalises `layers`. A layer without a base level ends at the top of its
+    /// `base_unit_reference` layer when that is below it, otherwise at the top of the next
+    /// layer down. Boundaries within `tolerance` of each other are treated as continuous.
+    pub fn from_layers(layers: &[SoilLayer], tolerance: f64) -> Self {
+        let mut order: Vec<usize> = (0..layers.len()).collect();
+        order.sort_by(|&a, &b| layers[b].top_level.total_cmp(&layers[a].top_level));
+
+        let mut stratigraphy = Stratigraphy::default();
+        for (position, &index) in order.iter().enumerate() {
+            let layer = &layers[index];
+            let below = &order[position + 1..];
+            let inferred_base = || {
+                let named = layer.base_unit_reference.as_ref().and_then(|reference| {
+                    below
+                        .iter()
+                        .find(|&&next| layers[next].unit_reference == *reference)
+                });
+                named.or(below.first()).map(|&next| layers[next].top_level)
+            };
+            let base = layer.base_level.or_else(inferred_base);
+
+            let mut top = layer.top_level;
+            if let Some(upper) = stratigraphy.layers.last() {
+                let upper_base = upper.base.unwrap_or(f64::NEG_INFINITY);
+                if top < upper_base - tolerance {
+                    stratigraphy.issues.push(StratigraphyIssue::Gap {
+                        upper: upper.unit_reference.clone(),
+                        lower: layer.unit_reference.clone(),
+                        top: upper_base,
+                        base: top,
+                    });
+                } else if top > upper_base + tolerance {
+                    stratigraphy.issues.push(StratigraphyIssue::Overlap {
+                        upper: upper.unit_reference.clone(),
+                        lower: layer.unit_reference.clone(),
+                        top,
+                        base: upper_base.max(base.unwrap_or(f64::NEG_INFINITY)),
+                    });
+                }
+                top = top.min(upper_base);
+            }
+            if base.is_some_and(|base| base >= top) {
+                continue;
+            }
+
+            stratigraphy.layers.push(LayerInterval {
+                index,
+                unit_reference: layer.unit_reference.clone(),
+                top,
+                base,
+                base_inferred: layer.base_level.is_none() && base.is_some(),
+            });
+        }
+        stratigraphy
+    }
+
+    pub fn top_level(&self) -> Option<f64> {
+        self.layers.first().map(|layer| layer.top)
+    }
+
+    /// Base of the lowest layer, or `None` when there are no layers or the lowest layer has
+    /// no base.
+    pub fn base_level(&self) -> Option<f64> {
+        self.layers.last().and_then(|layer| layer.base)
+    }
+
+    /// Layer tops from the top down, followed by the base of the lowest layer if it has one.
+    pub fn boundaries(&self) -> Vec<f64> {
+        self.layers
+            .iter()
+            .map(|layer| layer.top)
+            .chain(self.base_level())
+            .collect()
+    }
+
+    /// The layer covering `level`, with interfaces assigned to the upper layer.
+    pub fn layer_at(&self, level: f64) -> Option<&LayerInterval> {
+        self.layers
+            .iter()
+            .enumerate()
+            .find(|(position, layer)| {
+                let below_top = if *position == 0 {
+                    level <= layer.top
+                } else {
+                    level < layer.top
+                };
+                below_top && layer.base.is_none_or(|base| level >= base)
+            })
+            .map(|(_, layer)| layer)
+    }
+
+    /// Depth below the top of the highest layer, or `None` above it.
+    pub fn depth_at(&self, level: f64) -> Option<f64> {
+        let top = self.top_level()?;
+        (level <= top).then_some(top - level)
+    }
+}
+
+impl GroundModel {
+    /// The model's layers sorted, with missing bases inferred and overlaps clipped.
+    pub fn stratigraphy(&self) -> Stratigraphy {
+        Stratigraphy::from_layers(&self.soil_layers, LEVEL_TOLERANCE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer(unit: &str, top: f64, base: Option<f64>) -> SoilLayer {
+        SoilLayer {
+            unit_reference: unit.to_string(),
+            top_level: top,
+            base_level: base,
+            ..SoilLayer::default()
+        }
+    }
+
+    #[test]
+    fn layers_sorted_with_inferred_bases() {
+        let mut clay = layer("Clay", 6.0, None);
+        clay.base_unit_reference = Some("Sand".to_string());
+        let mut made_ground = layer("Made Ground", 10.0, None);
+        made_ground.base_unit_reference = Some("Alluvium".to_string());
+        let model = GroundModel::new(
+            vec![
+                layer("Chalk", -4.0, None),
+                layer("Sand", 2.0, Some(-4.0)),
+                clay,
+                made_ground,
+            ],
+            vec![],
+        );
+
+        let stratigraphy = model.stratigraphy();
+        let units: Vec<&str> = stratigraphy
+            .layers
+            .iter()
+            .map(|layer| layer.unit_reference.as_str())
+            .collect();
+        assert_eq!(units, ["Made Ground", "Clay", "Sand", "Chalk"]);
+        // A base unit that is not in the model falls back to the next layer down
+        assert_eq!(stratigraphy.layers[0].base, Some(6.0));
+        assert!(stratigraphy.layers[0].base_inferred);
+        assert_eq!(stratigraphy.layers[1].base, Some(2.0));
+        assert!(!stratigraphy.layers[2].base_inferred);
+        assert_eq!(stratigraphy.layers[3].base, None);
+        assert_eq!(stratigraphy.layers[3].index, 0);
+        assert!(stratigraphy.issues.is_empty());
+        assert_eq!(stratigraphy.boundaries(), [10.0, 6.0, 2.0, -4.0]);
+        assert_eq!(stratigraphy.base_level(), None);
+    }
+
+    #[test]
+    fn overlaps_clipped_to_upper_layer() {
+        let stratigraphy = Stratigraphy::from_layers(
+            &[
+                layer("Clay", 10.0, Some(4.0)),
+                layer("Sand", 5.0, Some(0.0)),
+                layer("Lens", 8.0, Some(7.0)),
+            ],
+            LEVEL_TOLERANCE,
+        );
+
+        assert_eq!(stratigraphy.layers.len(), 2);
+        assert_eq!(stratigraphy.layers[1].unit_reference, "Sand");
+        assert_eq!(stratigraphy.layers[1].top, 4.0);
+        assert_eq!(stratigraphy.layers[1].thickness(), Some(4.0));
+        assert_eq!(
+            stratigraphy.issues,
+            [
+                StratigraphyIssue::Overlap {
+                    upper: "Clay".to_string(),
+                    lower: "Lens".to_string(),
+                    top: 8.0,
+                    base: 7.0,
+                },
+                StratigraphyIssue::Overlap {
+                    upper: "Clay".to_string(),
+                    lower: "Sand".to_string(),
+                    top: 5.0,
+                    base: 4.0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn interfaces_belong_to_the_upper_layer() {
+        let stratigraphy = Stratigraphy::from_layers(
+            &[
+                layer("Sand", 5.0, Some(0.0)),
+                layer("Clay", 10.0, Some(5.0)),
+                layer("Gravel", -1.0, Some(-3.0)),
+            ],
+            LEVEL_TOLERANCE,
+        );
+
+        assert_eq!(stratigraphy.layer_at(10.0).unwrap().unit_reference, "Clay");
+        assert_eq!(stratigraphy.layer_at(5.0).unwrap().unit_reference, "Clay");
+        assert_eq!(stratigraphy.layer_at(4.9).unwrap().unit_reference, "Sand");
+        assert!(stratigraphy.layer_at(-0.5).is_none());
+        assert_eq!(
+            stratigraphy.layer_at(-3.0).unwrap().unit_reference,
+            "Gravel"
+        );
+        assert!(stratigraphy.layer_at(-3.1).is_none());
+        assert!(stratigraphy.layer_at(10.1).is_none());
+
+        assert_eq!(
+            stratigraphy.issues,
+            [StratigraphyIssue::Gap {
+                upper: "Sand".to_string(),
+                lower: "Gravel".to_string(),
+                top: 0.0,
+                base: -1.0,
+            }]
+        );
+        assert_eq!(stratigraphy.top_level(), Some(10.0));
+        assert_eq!(stratigraphy.base_level(), Some(-3.0));
+        assert_eq!(stratigraphy.depth_at(4.0), Some(6.0));
+    }
+}
@@ -0,0 +1,371 @@
+//! Preconsolidation pressure, overconsolidation ratio and parameters that depend on them.
+//!
+//! A unit's stress history is set by one of `preconsolidation_pressure` (σ′p), `pop`
+//! (σ′p − σ′v0) or `ocr` (σ′p/σ′v0), checked in that order. Each may be constant or vary with
+//! depth or elevation through `SoilParams::profiles`. σ′v0 is the current vertical effective
+//! stress, and σ′p is never taken below it. A unit with none of the three is normally
+//! consolidated.
+//!
+//! Excavation, fill, dewatering and surcharge change σ′v0 but not σ′p, so a POP or OCR is
+//! rewritten against the new effective stress when the model is changed (see
+//! [`GroundModel::excavate`]).
+
+use crate::{GroundModel, GroundModelError, ParameterProfile, ProfileVariable, SoilParams};
+use serde::{Deserialize, Serialize};
+
+/// Spacing (m) of the levels at which σ′p is sampled when it is carried across a change.
+const HISTORY_SPACING: f64 = 0.5;
+/// Depth (m) sampled below the top of a layer that has no base or rigid boundary beneath it.
+const OPEN_BASE_DEPTH: f64 = 50.0;
+
+impl SoilParams {
+    /// σ′p (kPa) at a level with vertical effective stress `effective`, or `None` when the unit
+    /// has no stress history.
+    pub fn preconsolidation_at(&self, effective: f64) -> Option<f64> {
+        self.preconsolidation_pressure
+            .or_else(|| self.pop.map(|pop| effective + pop))
+            .or_else(|| self.ocr.map(|ocr| effective * ocr))
+            .map(|preconsolidation| preconsolidation.max(effective))
+    }
+
+    /// σ′p/σ′v0 at vertical effective stress `effective`: 1.0 for a normally consolidated
+    /// unit, and `None` when `effective` is not positive.
+    pub fn ocr_at(&self, effective: f64) -> Option<f64> {
+        (effective > 0.0).then(|| {
+            self.preconsolidation_at(effective)
+                .map_or(1.0, |preconsolidation| preconsolidation / effective)
+        })
+    }
+
+    /// At-rest coefficient at vertical effective stress `effective`.
+    /// [`SoilParams::k0_overconsolidated`] is used where the unit is overconsolidated, capped
+    /// at the passive coefficient, and [`SoilParams::k0`] otherwise.
+    pub fn k0_at(&self, effective: f64) -> Result<f64, GroundModelError> {
+        let Some(preconsolidation) = self
+            .preconsolidation_at(effective)
+            .filter(|preconsolidation| *preconsolidation > effective)
+        else {
+            return self.k0();
+        };
+        let k_passive = self.get_k_passive(None)?;
+        if effective <= 0.0 {
+            return Ok(k_passive);
+        }
+        Ok(self
+            .k0_overconsolidated(preconsolidation / effective)?
+            .min(k_passive))
+    }
+
+    /// Secant coefficient of volume compressibility (m²/kN) for an effective stress change of
+    /// `change` from `effective`. Recompression up to σ′p and any unloading use
+    /// `mv_recompression`; compression beyond σ′p uses `mv`.
+    pub fn mv_for(&self, effective: f64, change: f64) -> f64 {
+        let mv_recompression = self.mv_recompression.unwrap_or(self.mv);
+        let headroom = self
+            .preconsolidation_at(effective)
+            .map_or(0.0, |preconsolidation| preconsolidation - effective);
+        if change <= 0.0 {
+            return if change < 0.0 || headroom > 0.0 {
+                mv_recompression
+            } else {
+                self.mv
+            };
+        }
+        let recompression = headroom.min(change);
+        (mv_recompression * recompression + self.mv * (change - recompression)) / change
+    }
+}
+
+/// Stress history at one level.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StressHistoryPoint {
+    pub level: f64,
+    pub reference: String,
+    /// Vertical effective stress σ′v0 (kPa).
+    pub effective: f64,
+    /// σ′p (kPa), equal to `effective` where the unit is normally consolidated.
+    pub preconsolidation: f64,
+    /// `None` where `effective` is not positive.
+    pub ocr: Option<f64>,
+}
+
+impl GroundModel {
+    /// σ′p at `level` from the unit there, with any profiles evaluated at that level. `None`
+    /// outside the model or when the unit has no stress history.
+    pub fn preconsolidation_pressure_at_level(&self, level: f64) -> Option<f64> {
+        let params = self.get_params_at_level(level).ok()?;
+        params.preconsolidation_at(self.get_effective_stress_at_level(level))
+    }
+
+    /// OCR at `level` from the current vertical effective stress.
+    pub fn ocr_at_level(&self, level: f64) -> Option<f64> {
+        let params = self.get_params_at_level(level).ok()?;
+        params.ocr_at(self.get_effective_stress_at_level(level))
+    }
+
+    /// Stress history at each of `levels`. Levels outside the model are skipped.
+    pub fn ocr_profile(&self, levels: &[f64]) -> Vec<StressHistoryPoint> {
+        self.stress_profile(levels)
+            .into_iter()
+            .filter_map(|stress| {
+                let params = self.get_params_at_level(stress.level).ok()?;
+                Some(StressHistoryPoint {
+                    level: stress.level,
+                    reference: params.reference.clone(),
+                    effective: stress.effective,
+                    preconsolidation: params
+                        .preconsolidation_at(stress.effective)
+                        .unwrap_or(stress.effective),
+                    ocr: params.ocr_at(stress.effective),
+                })
+            })
+            .collect()
+    }
+
+    /// [`SoilParams::k0_at`] for the unit and vertical effective stress at `level`.
+    pub fn k0_at_level(&self, level: f64) -> Result<f64, GroundModelError> {
+        self.get_params_at_level(level)?
+            .k0_at(self.get_effective_stress_at_level(level))
+    }
+
+    /// Keeps σ′p where it was in `before` for every unit whose stress history is a POP or OCR,
+    /// after the surface, surcharge or water table has changed. σ′p is sampled over the unit's
+    /// layers in `before` and stored as a POP profile over elevation against the new effective
+    /// stress. The POP is held below the sampled range, which keeps σ′p there as long as the
+    /// old and new effective stresses run parallel.
+    pub(crate) fn keep_stress_history(&mut self, before: &GroundModel) {
+        self.soil_params = self
+            .soil_params
+            .iter()
+            .map(|params| self.fixed_stress_history(before, params))
+            .collect();
+
+        let case_ids: Vec<String> = self.cases.keys().cloned().collect();
+        for case_id in case_ids {
+            let (Ok(before_case), Ok(after_case)) =
+                (before.for_case(&case_id), self.for_case(&case_id))
+            else {
+                continue;
+            };
+            if let Some(case_params) = self.cases.get_mut(&case_id) {
+                for params in case_params.iter_mut() {
+                    *params = after_case.fixed_stress_history(&before_case, params);
+                }
+            }
+        }
+    }
+
+    fn fixed_stress_history(&self, before: &GroundModel, params: &SoilParams) -> SoilParams {
+        let is_relative =
+            |code_id: &str| matches!(code_id, "PreOverburdenPressure" | "OverconsolidationRatio");
+        let relative = params.pop.is_some()
+            || params.ocr.is_some()
+            || params.profiles.iter().any(|p| is_relative(&p.code_id));
+        if params.preconsolidation_pressure.is_some() || !relative {
+            return params.clone();
+        }
+
+        let surface = self.get_top_level();
+        let levels: Vec<f64> = before
+            .unit_sample_levels(&params.reference)
+            .into_iter()
+            .filter(|level| *level <= surface)
+            .collect();
+        let Some(top) = levels.iter().copied().reduce(f64::max) else {
+            return params.clone();
+        };
+
+        let datum = before.get_top_level();
+        let points: Vec<[f64; 2]> = before
+            .stress_profile(&levels)
+            .into_iter()
+            .zip(self.stress_profile(&levels))
+            .map(|(old, new)| {
+                let preconsolidation = params
+                    .resolve_profiles(datum - old.level, old.level)
+                    .preconsolidation_at(old.effective)
+                    .unwrap_or(old.effective);
+                [old.level, (preconsolidation - new.effective).max(0.0)]
+            })
+            .collect();
+
+        let mut fixed = params.clone();
+        fixed.ocr = None;
+        fixed.profiles.retain(|p| !is_relative(&p.code_id));
+        let profile = ParameterProfile::new(
+            "PreOverburdenPressure".to_string(),
+            ProfileVariable::Elevation,
+            points,
+        );
+        fixed.pop = profile.value_at(top);
+        fixed.profiles.push(profile);
+        fixed
+    }
+
+    /// Levels at [`HISTORY_SPACING`] through each layer of the unit `reference`.
+    fn unit_sample_levels(&self, reference: &str) -> Vec<f64> {
+        let mut levels = Vec::new();
+        for layer in self
+            .stratigraphy()
+            .layers
+            .into_iter()
+            .filter(|layer| layer.unit_reference == reference)
+        {
+            let base = layer
+                .base
+                .or(self.rigid_boundary.filter(|rigid| *rigid < layer.top))
+                .unwrap_or(layer.top - OPEN_BASE_DEPTH);
+            let steps = ((layer.top - base) / HISTORY_SPACING).ceil().max(1.0) as usize;
+            levels.extend(
+                (0..=steps).map(|i| layer.top - (layer.top - base) * i as f64 / steps as f64),
+            );
+        }
+        levels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::{ParameterProfile, ProfileVariable, SoilLayer};
+
+    fn clay() -> SoilParams {
+        SoilParams {
+            phi_prime: Some(25.0_f64.to_radians()),
+            mv_recompression: Some(0.00005),
+            ..fixtures::clay("Clay", 0.0002, 1.0)
+        }
+    }
+
+    #[test]
+    fn preconsolidation_from_ocr_pop_or_pressure() {
+        let mut params = clay();
+        assert_eq!(params.preconsolidation_at(100.0), None);
+        assert_eq!(params.ocr_at(100.0), Some(1.0));
+
+        params.ocr = Some(2.0);
+        assert_eq!(params.preconsolidation_at(100.0), Some(200.0));
+        params.pop = Some(50.0);
+        assert_eq!(params.preconsolidation_at(100.0), Some(150.0));
+        params.preconsolidation_pressure = Some(300.0);
+        assert_eq!(params.ocr_at(100.0), Some(3.0));
+        // σ′p is never below the current stress
+        assert_eq!(params.ocr_at(400.0), Some(1.0));
+        assert_eq!(params.ocr_at(0.0), None);
+
+        let k0_nc = 1.0 - 25.0_f64.to_radians().sin();
+        assert!((params.k0_at(400.0).unwrap() - k0_nc).abs() < 1e-12);
+        let k0_oc = k0_nc * 3.0_f64.powf(25.0_f64.to_radians().sin());
+        assert!((params.k0_at(100.0).unwrap() - k0_oc).abs() < 1e-12);
+        assert_eq!(
+            params.k0_at(0.0).unwrap(),
+            params.get_k_passive(None).unwrap()
+        );
+    }
+
+    #[test]
+    fn mv_splits_recompression_and_virgin_compression() {
+        let mut params = clay();
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-15;
+        assert!(close(params.mv_for(100.0, 50.0), 0.0002));
+        assert_eq!(params.mv_for(100.0, 0.0), 0.0002);
+
+        params.pop = Some(50.0);
+        assert!(close(params.mv_for(100.0, 50.0), 0.00005));
+        assert!(close(params.mv_for(100.0, 100.0), 0.000125));
+        assert_eq!(params.mv_for(100.0, -20.0), 0.00005);
+        assert_eq!(params.mv_for(100.0, 0.0), 0.00005);
+    }
+
+    #[test]
+    fn ocr_profile_follows_pop_profile() {
+        let mut params = clay();
+        params.pop = Some(100.0);
+        params.profiles.push(ParameterProfile::new(
+            "PreOverburdenPressure".to_string(),
+            ProfileVariable::Depth,
+            vec![[0.0, 100.0], [10.0, 0.0]],
+        ));
+        let mut model = GroundModel::new(
+            vec![SoilLayer::with_unit_reference(
+                10.0,
+                -10.0,
+                String::new(),
+                "Clay".to_string(),
+            )],
+            vec![params],
+        );
+        model.groundwater = -20.0;
+
+        let profile = model.ocr_profile(&[10.0, 5.0, 0.0, -5.0, -20.0]);
+        assert_eq!(profile.len(), 4);
+        assert_eq!(profile[0].ocr, None);
+        assert_eq!(profile[0].preconsolidation, 100.0);
+        assert_eq!(profile[1].preconsolidation, 150.0);
+        assert_eq!(profile[1].ocr, Some(1.5));
+        assert_eq!(profile[2].ocr, Some(1.0));
+        assert_eq!(profile[3].preconsolidation, 300.0);
+
+        assert_eq!(model.ocr_at_level(5.0), Some(1.5));
+        assert_eq!(model.preconsolidation_pressure_at_level(5.0), Some(150.0));
+        assert!(model.k0_at_level(5.0).unwrap() > model.k0_at_level(-5.0).unwrap());
+        assert!(model.k0_at_level(-20.0).is_err());
+    }
+
+    #[test]
+    fn preconsolidation_survives_excavation_fill_and_dewatering() {
+        let mut params = clay();
+        params.pop = Some(50.0);
+        let mut model = GroundModel::new(
+            vec![SoilLayer::with_unit_reference(
+                10.0,
+                -10.0,
+                String::new(),
+                "Clay".to_string(),
+            )],
+            vec![params],
+        );
+        model.groundwater = -20.0;
+        let close = |a: Option<f64>, b: f64| (a.unwrap() - b).abs() < 1e-9;
+        assert!(close(model.preconsolidation_pressure_at_level(0.0), 250.0));
+
+        let excavated = model.excavate(5.0);
+        assert!(close(
+            excavated.preconsolidation_pressure_at_level(0.0),
+            250.0
+        ));
+        assert!(close(
+            excavated.preconsolidation_pressure_at_level(-10.0),
+            450.0
+        ));
+        assert!(close(excavated.ocr_at_level(0.0), 2.5));
+        // A second stage keeps the same σ′p
+        let deeper = excavated.excavate(2.0);
+        assert!(close(deeper.preconsolidation_pressure_at_level(0.0), 250.0));
+
+        let fill = SoilParams {
+            reference: "Fill".to_string(),
+            unit_weight: 20.0,
+            ..SoilParams::default()
+        };
+        let filled = model.place_fill(12.0, &fill).unwrap();
+        assert!(close(filled.preconsolidation_pressure_at_level(0.0), 250.0));
+        // Loaded past σ′p, the clay is normally consolidated
+        let filled = model.place_fill(14.0, &fill).unwrap();
+        assert!(close(filled.preconsolidation_pressure_at_level(0.0), 280.0));
+        assert!(close(filled.ocr_at_level(0.0), 1.0));
+
+        model.groundwater = 5.0;
+        assert!(close(model.preconsolidation_pressure_at_level(0.0), 200.0));
+        let dewatered = model.dewater(-20.0);
+        assert!(close(
+            dewatered.preconsolidation_pressure_at_level(0.0),
+            200.0
+        ));
+        assert!(close(
+            dewatered.preconsolidation_pressure_at_level(8.0),
+            90.0
+        ));
+    }
+}
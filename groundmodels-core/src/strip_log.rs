@@ -1,4 +1,4 @@
-use crate::{GroundModel, SoilLayer, SoilType};
+use crate::{GroundModel, GroundModelError, SoilLayer, SoilType};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,19 +20,9 @@ pub struct StripLogEntry {
     pub sigma_v_prime_mid: Option<f64>,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
 pub struct BuildStripLogOptions {
     pub include_stresses: bool,
-    pub stress_dz: f64,
-}
-
-impl Default for BuildStripLogOptions {
-    fn default() -> Self {
-        BuildStripLogOptions {
-            include_stresses: false,
-            stress_dz: 0.05,
-        }
-    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -90,25 +80,35 @@ impl Default for StripLogColors {
 }
 
 impl GroundModel {
-    pub fn to_strip_log(&self, opts: BuildStripLogOptions) -> Vec<StripLogEntry> {
+    /// Builds one strip log row per layer, top-down. Fails if a layer has a non-finite level
+    /// or a base above its top.
+    pub fn to_strip_log(
+        &self,
+        opts: BuildStripLogOptions,
+    ) -> Result<Vec<StripLogEntry>, GroundModelError> {
+        for layer in &self.soil_layers {
+            check_layer_geometry(layer)?;
+        }
+
         let mut layers = self.soil_layers.clone();
         layers.sort_by(|a, b| b.top_level.partial_cmp(&a.top_level).unwrap());
 
         let mut entries = Vec::new();
         for layer in layers {
             let reference = layer_reference(&layer);
-            let params = self.get_soil_params(&reference).cloned();
             let mid = match layer.base_level {
                 Some(base) => (layer.top_level + base) / 2.0,
                 None => layer.top_level,
             };
+            let params = self
+                .get_soil_params(&reference)
+                .map(|p| p.resolve_profiles(self.get_top_level() - mid, mid));
             let gw = self.groundwater;
             let base = layer.base_level.unwrap_or(layer.top_level);
-            let gw_within =
-                (gw >= base && gw <= layer.top_level) || (gw <= layer.top_level && gw >= base);
+            let gw_within = (base..=layer.top_level).contains(&gw);
             let gw_above_top = gw > layer.top_level;
 
-            let mut entry = StripLogEntry {
+            let entry = StripLogEntry {
                 top_level: layer.top_level,
                 bottom_level: base,
                 thickness: (layer.top_level - base).abs(),
@@ -136,22 +136,32 @@ impl GroundModel {
                 sigma_v_prime_mid: None,
             };
 
-            if opts.include_stresses {
-                let sigma_v = integrate_sigma_v_total_at(self, mid, opts.stress_dz);
-                let u = self.get_pwp_at_level(mid);
-                entry.sigma_v_total_mid = Some(sigma_v);
-                entry.u_mid = Some(u);
-                entry.sigma_v_prime_mid = Some(sigma_v - u);
-            }
-
             entries.push(entry);
         }
 
-        entries
+        if opts.include_stresses {
+            let mids: Vec<f64> = entries
+                .iter()
+                .map(|entry| (entry.top_level + entry.bottom_level) / 2.0)
+                .collect();
+            for (entry, stress) in entries.iter_mut().zip(self.stress_profile(&mids)) {
+                entry.sigma_v_total_mid = Some(stress.total);
+                entry.u_mid = Some(stress.pore_pressure);
+                entry.sigma_v_prime_mid = Some(stress.effective);
+            }
+        }
+
+        Ok(entries)
     }
 
-    pub fn to_strip_log_csv(&self, rows: Option<Vec<StripLogEntry>>) -> String {
-        let rows = rows.unwrap_or_else(|| self.to_strip_log(BuildStripLogOptions::default()));
+    pub fn to_strip_log_csv(
+        &self,
+        rows: Option<Vec<StripLogEntry>>,
+    ) -> Result<String, GroundModelError> {
+        let rows = match rows {
+            Some(rows) => rows,
+            None => self.to_strip_log(BuildStripLogOptions::default())?,
+        };
         let headers = [
             "TOP_LEVEL(m)",
             "BOTTOM_LEVEL(m)",
@@ -197,11 +207,18 @@ impl GroundModel {
             );
         }
 
-        lines.join("\n")
+        Ok(lines.join("\n"))
     }
 
-    pub fn to_ags_geol_csv(&self, hole_id: &str, rows: Option<Vec<StripLogEntry>>) -> String {
-        let rows = rows.unwrap_or_else(|| self.to_strip_log(BuildStripLogOptions::default()));
+    pub fn to_ags_geol_csv(
+        &self,
+        hole_id: &str,
+        rows: Option<Vec<StripLogEntry>>,
+    ) -> Result<String, GroundModelError> {
+        let rows = match rows {
+            Some(rows) => rows,
+            None => self.to_strip_log(BuildStripLogOptions::default())?,
+        };
         let headers = ["HOLE_ID", "GEOL_TOP(m)", "GEOL_BASE(m)", "GEOL_DESC"];
         let mut lines = vec![headers.join(",")];
         for r in rows {
@@ -218,10 +235,13 @@ impl GroundModel {
                 .join(","),
             );
         }
-        lines.join("\n")
+        Ok(lines.join("\n"))
     }
 
-    pub fn render_strip_log_svg(&self, opts: StripLogRenderOptions) -> String {
+    pub fn render_strip_log_svg(
+        &self,
+        opts: StripLogRenderOptions,
+    ) -> Result<String, GroundModelError> {
         let colors = opts.colors.unwrap_or_default();
         let top = self.get_top_level();
         let bottom = self.get_base_level();
@@ -238,7 +258,7 @@ impl GroundModel {
         let y_for_level = |level: f64| col_y + (top - level) * scale;
         let depth_at = |level: f64| top - level;
 
-        let rows = self.to_strip_log(BuildStripLogOptions::default());
+        let rows = self.to_strip_log(BuildStripLogOptions::default())?;
 
         let mut svg = Vec::new();
         svg.push(format!(
@@ -400,7 +420,7 @@ impl GroundModel {
         }
 
         svg.push("</svg>".to_string());
-        svg.join("")
+        Ok(svg.join(""))
     }
 }
 
@@ -412,28 +432,26 @@ fn layer_reference(layer: &SoilLayer) -> String {
     }
 }
 
-fn integrate_sigma_v_total_at(model: &GroundModel, level: f64, dz: f64) -> f64 {
-    let top = model.get_top_level();
-    if level >= top {
-        return 0.0;
-    }
-    let mut s = 0.0;
-    let mut z = top - dz / 2.0;
-    while z > level {
-        let sp = model.get_params_at_level(z).ok();
-        if let Some(params) = sp {
-            s += params.unit_weight * dz;
-        }
-        z -= dz;
-    }
-    s
+fn check_layer_geometry(layer: &SoilLayer) -> Result<(), GroundModelError> {
+    let base = layer.base_level.unwrap_or(layer.top_level);
+    let message = if !layer.top_level.is_finite() || !base.is_finite() {
+        "layer levels must be finite"
+    } else if base > layer.top_level {
+        "base level is above top level"
+    } else {
+        return Ok(());
+    };
+    Err(GroundModelError::InvalidGeometry {
+        reference: layer_reference(layer),
+        message: message.to_string(),
+    })
 }
 
-fn csv_quote(s: &str) -> String {
+pub(crate) fn csv_quote(s: &str) -> String {
     format!("\"{}\"", s.replace('"', "\"\""))
 }
 
-fn escape_xml(s: &str) -> String {
+pub(crate) fn escape_xml(s: &str) -> String {
     s.replace('&', "&amp;")
         .replace('<', "&lt;")
         .replace('>', "&gt;")
@@ -465,7 +483,7 @@ fn wrap_text(text: &str, max_width_px: f64, font_size_px: f64) -> Vec<String> {
     lines
 }
 
-fn format_number(n: f64) -> String {
+pub(crate) fn format_number(n: f64) -> String {
     if n.abs() < 1e-6 {
         "0".to_string()
     } else {
@@ -479,20 +497,24 @@ fn format_number(n: f64) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::SoilParams;
+    use crate::{ParameterProfile, ProfileVariable, SoilParams};
 
     fn sample_model() -> GroundModel {
-        let mut params1 = SoilParams::default();
-        params1.reference = "CL".to_string();
-        params1.unit_weight = 19.0;
-        params1.behaviour = SoilType::Cohesive;
-        params1.phi_prime = Some(22.0_f64.to_radians());
-
-        let mut params2 = SoilParams::default();
-        params2.reference = "SA".to_string();
-        params2.unit_weight = 18.0;
-        params2.behaviour = SoilType::Granular;
-        params2.phi_prime = Some(30.0_f64.to_radians());
+        let params1 = SoilParams {
+            reference: "CL".to_string(),
+            unit_weight: 19.0,
+            behaviour: SoilType::Cohesive,
+            phi_prime: Some(22.0_f64.to_radians()),
+            ..Default::default()
+        };
+
+        let params2 = SoilParams {
+            reference: "SA".to_string(),
+            unit_weight: 18.0,
+            behaviour: SoilType::Granular,
+            phi_prime: Some(30.0_f64.to_radians()),
+            ..Default::default()
+        };
 
         let layer1 = SoilLayer::with_all_fields(
             "CL".to_string(),
@@ -519,22 +541,61 @@ mod tests {
     #[test]
     fn strip_log_csv_has_headers() {
         let model = sample_model();
-        let csv = model.to_strip_log_csv(None);
+        let csv = model.to_strip_log_csv(None).unwrap();
         assert!(csv.starts_with("TOP_LEVEL(m),BOTTOM_LEVEL(m),THICKNESS(m),REFERENCE"));
     }
 
     #[test]
     fn strip_log_svg_contains_elements() {
         let model = sample_model();
-        let svg = model.render_strip_log_svg(StripLogRenderOptions::default());
+        let svg = model
+            .render_strip_log_svg(StripLogRenderOptions::default())
+            .unwrap();
         assert!(svg.starts_with("<svg"));
         assert!(svg.contains("stroke=\"#1E90FF\""));
     }
 
+    #[test]
+    fn strip_log_uses_profile_at_layer_mid() {
+        let mut model = sample_model();
+        model.soil_params[0].profiles.push(ParameterProfile::new(
+            "UndrainedShearStrength".to_string(),
+            ProfileVariable::Depth,
+            vec![[0.0, 30.0], [2.0, 50.0]],
+        ));
+
+        let rows = model.to_strip_log(BuildStripLogOptions::default()).unwrap();
+        assert_eq!(rows[0].cu, Some(40.0));
+    }
+
+    #[test]
+    fn strip_log_stresses_at_layer_mid() {
+        let model = sample_model();
+        let rows = model
+            .to_strip_log(BuildStripLogOptions {
+                include_stresses: true,
+            })
+            .unwrap();
+        // 19 * 2 + 18 * 1 at the middle of the sand
+        assert!((rows[1].sigma_v_total_mid.unwrap() - 56.0).abs() < 1e-9);
+        assert!((rows[1].u_mid.unwrap() - 15.0).abs() < 1e-9);
+    }
+
     #[test]
     fn ags_geol_csv_has_hole_id() {
         let model = sample_model();
-        let csv = model.to_ags_geol_csv("BH101", None);
+        let csv = model.to_ags_geol_csv("BH101", None).unwrap();
         assert!(csv.contains("\"BH101\""));
     }
+
+    #[test]
+    fn strip_log_rejects_inverted_layer() {
+        let mut model = sample_model();
+        model.soil_layers[1].base_level = Some(0.0);
+
+        match model.to_strip_log(BuildStripLogOptions::default()) {
+            Err(GroundModelError::InvalidGeometry { reference, .. }) => assert_eq!(reference, "SA"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
 }
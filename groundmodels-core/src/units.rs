@@ -0,0 +1,360 @@
+//! Units of `SoilParams` fields and conversion at the AGSi boundary.
+//!
+//! `SoilParams` holds angles in radians, stresses, strengths and moduli in kPa, unit weights
+//! in kN/m³, `mv` in m²/kN and `cv` in m²/year; groundwater heads are in m and pore pressures
+//! in kPa. AGSi values are in the units declared for their `codeID` in the
+//! `agsProjectCodeSet` for `agsiDataParameterValue`, or the AGSi code list defaults (degrees
+//! for friction angles).
+
+use crate::agsi::AgSiV101;
+use crate::agsi_io::AgsiError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A stress, strength or modulus, stored in kPa.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default, Serialize, Deserialize)]
+pub struct Stress(f64);
+
+impl Stress {
+    pub fn from_kpa(kpa: f64) -> Self {
+        Stress(kpa)
+    }
+
+    pub fn from_mpa(mpa: f64) -> Self {
+        Stress(mpa * 1000.0)
+    }
+
+    /// A value in a named unit such as `"MPa"`, or `None` if the unit is not a stress unit.
+    pub fn from_unit(value: f64, unit: &str) -> Option<Self> {
+        scale(Quantity::Stress, unit).map(|factor| Stress(value * factor))
+    }
+
+    pub fn kpa(self) -> f64 {
+        self.0
+    }
+
+    pub fn mpa(self) -> f64 {
+        self.0 / 1000.0
+    }
+}
+
+/// What an AGSi parameter code measures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quantity {
+    Angle,
+    Stress,
+    UnitWeight,
+    Compressibility,
+    Consolidation,
+    /// Piezometric heads.
+    Length,
+    /// Ratios and indices read into `SoilParams`, which have no physical unit.
+    Ratio,
+    /// Codes this library does not model. Their values are kept as they are.
+    Other,
+}
+
+impl Quantity {
+    pub fn of_code(code_id: &str) -> Self {
+        match code_id {
+            "AngleFriction" | "EffectiveFrictionAngle" => Quantity::Angle,
+            "Cohesion"
+            | "EffectiveCohesion"
+            | "UndrainedShearStrength"
+            | "YoungsModulus"
+            | "UnconfinedCompressiveStrength"
+            | "PreOverburdenPressure"
+            | "PreconsolidationPressure"
+            | "PorePressure" => Quantity::Stress,
+            "UnitWeight" | "UnitWeightSaturated" | "UnitWeightWater" => Quantity::UnitWeight,
+            "ModulusOfVolumeCompressibility" | "ModulusOfVolumeCompressibilityRecompression" => {
+                Quantity::Compressibility
+            }
+            "CoefficientOfConsolidation" => Quantity::Consolidation,
+            "PiezometricHead" => Quantity::Length,
+            "PoissonsRatio"
+            | "OverconsolidationRatio"
+            | "GeologicalStrengthIndex"
+            | "HoekBrownParamMi"
+            | "Disturbance" => Quantity::Ratio,
+            _ => Quantity::Other,
+        }
+    }
+
+    /// Unit assumed for AGSi values when the file does not declare one.
+    pub fn agsi_default_unit(self) -> Option<&'static str> {
+        match self {
+            Quantity::Angle => Some("deg"),
+            Quantity::Stress => Some("kPa"),
+            Quantity::UnitWeight => Some("kN/m3"),
+            Quantity::Compressibility => Some("m2/kN"),
+            Quantity::Consolidation => Some("m2/yr"),
+            Quantity::Length => Some("m"),
+            Quantity::Ratio => Some("-"),
+            Quantity::Other => None,
+        }
+    }
+}
+
+const DAYS_PER_YEAR: f64 = 365.25;
+
+/// Multiplier from `unit` to the internal unit of `quantity`.
+fn scale(quantity: Quantity, unit: &str) -> Option<f64> {
+    let unit: String = unit
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .replace('³', "3")
+        .replace('²', "2")
+        .to_lowercase();
+    let factor = match (quantity, unit.as_str()) {
+        (Quantity::Angle, "deg" | "degree" | "degrees" | "°") => 1.0_f64.to_radians(),
+        (Quantity::Angle, "rad" | "radian" | "radians") => 1.0,
+        (Quantity::Stress, "kpa" | "kn/m2") => 1.0,
+        (Quantity::Stress, "mpa" | "mn/m2" | "n/mm2") => 1000.0,
+        (Quantity::Stress, "gpa") => 1.0e6,
+        (Quantity::Stress, "pa" | "n/m2") => 0.001,
+        (Quantity::UnitWeight, "kn/m3") => 1.0,
+        (Quantity::UnitWeight, "n/m3") => 0.001,
+        (Quantity::UnitWeight, "mn/m3") => 1000.0,
+        (Quantity::Compressibility, "m2/kn" | "1/kpa") => 1.0,
+        (Quantity::Compressibility, "m2/mn" | "1/mpa") => 0.001,
+        (Quantity::Consolidation, "m2/yr" | "m2/year" | "m2/a") => 1.0,
+        (Quantity::Consolidation, "m2/day" | "m2/d") => DAYS_PER_YEAR,
+        (Quantity::Consolidation, "m2/s") => DAYS_PER_YEAR * 86400.0,
+        (Quantity::Length, "m") => 1.0,
+        (Quantity::Length, "mm") => 0.001,
+        (Quantity::Ratio, "-" | "ratio" | "dimensionless" | "none") => 1.0,
+        (Quantity::Ratio, "%") => 0.01,
+        _ => return None,
+    };
+    Some(factor)
+}
+
+/// Units of AGSi parameter codes, from a file's `agsProjectCode` entries with the AGSi
+/// defaults for codes it does not list.
+#[derive(Debug, Clone, Default)]
+pub struct UnitTable {
+    factors: BTreeMap<String, f64>,
+    /// Units declared for codes this library does not model.
+    declared: BTreeMap<String, String>,
+}
+
+impl UnitTable {
+    /// Reads the units declared in the `agsProjectCodeSet` used by the `codeID` of
+    /// `agsiDataParameterValue`; code sets for other objects and attributes are ignored. A
+    /// unit that cannot be converted for a code this library reads is an error rather than a
+    /// silent mismatch. Codes it does not model keep their values whatever their unit.
+    pub fn from_agsi(agsi: &AgSiV101) -> Result<Self, AgsiError> {
+        let mut table = UnitTable::default();
+        let code_sets = agsi
+            .ags_project
+            .iter()
+            .flat_map(|project| project.ags_project_code_set.iter());
+        for (set_index, code_set) in code_sets.enumerate() {
+            if !code_set
+                .used_by_object
+                .eq_ignore_ascii_case("agsiDataParameterValue")
+                || !code_set.used_by_attribute.eq_ignore_ascii_case("codeID")
+            {
+                continue;
+            }
+            for (code_index, code) in code_set.ags_project_code.iter().enumerate() {
+                let Some(unit) = code.units.as_deref().filter(|unit| !unit.trim().is_empty())
+                else {
+                    continue;
+                };
+                let quantity = Quantity::of_code(code.code_id.as_str());
+                if quantity == Quantity::Other {
+                    table
+                        .declared
+                        .insert(code.code_id.to_string(), unit.to_string());
+                    continue;
+                }
+                let factor = scale(quantity, unit).ok_or_else(|| AgsiError::Parse {
+                    path: format!(
+                        "agsProject.agsProjectCodeSet[{}].agsProjectCode[{}].units",
+                        set_index, code_index
+                    ),
+                    message: format!("unsupported unit '{}' for {}", unit, *code.code_id),
+                })?;
+                table.factors.insert(code.code_id.to_string(), factor);
+            }
+        }
+        Ok(table)
+    }
+
+    /// The unit declared for `code_id` when it is a code this library does not model, whose
+    /// values are kept in that unit.
+    pub fn declared_unit(&self, code_id: &str) -> Option<&str> {
+        self.declared.get(code_id).map(String::as_str)
+    }
+
+    fn factor(&self, code_id: &str) -> f64 {
+        self.factors.get(code_id).copied().unwrap_or_else(|| {
+            let quantity = Quantity::of_code(code_id);
+            quantity
+                .agsi_default_unit()
+                .and_then(|unit| scale(quantity, unit))
+                .unwrap_or(1.0)
+        })
+    }
+
+    /// Converts an AGSi value for `code_id` to the unit `SoilParams` uses.
+    pub fn to_internal(&self, code_id: &str, value: f64) -> f64 {
+        value * self.factor(code_id)
+    }
+
+    /// Converts a `SoilParams` value back to the AGSi unit for `code_id`.
+    pub fn to_agsi(&self, code_id: &str, value: f64) -> f64 {
+        value / self.factor(code_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agsi_io::parse_agsi;
+
+    #[test]
+    fn stress_conversions() {
+        assert_eq!(Stress::from_mpa(25.0).kpa(), 25000.0);
+        assert_eq!(Stress::from_unit(2.0, "MN/m²").unwrap().kpa(), 2000.0);
+        assert!(Stress::from_unit(2.0, "blows").is_none());
+    }
+
+    #[test]
+    fn unit_table_reads_declared_units() {
+        let agsi = parse_agsi(
+            r#"{
+                "agsSchema": {"name": "AGSi", "version": "1.0.1"},
+                "agsFile": {"title": "Units", "producedBy": "test"},
+                "agsProject": {
+                    "projectName": "Units",
+                    "agsProjectCodeSet": [{
+                        "usedByObject": "agsiDataParameterValue",
+                        "usedByAttribute": "codeID",
+                        "agsProjectCode": [
+                            {"codeID": "UnconfinedCompressiveStrength", "description": "UCS", "units": "MPa"},
+                            {"codeID": "ModulusOfVolumeCompressibility", "description": "mv", "units": "m2/MN"}
+                        ]
+                    }]
+                },
+                "agsiModel": []
+            }"#,
+        )
+        .unwrap();
+        let table = UnitTable::from_agsi(&agsi).unwrap();
+
+        assert_eq!(
+            table.to_internal("UnconfinedCompressiveStrength", 25.0),
+            25000.0
+        );
+        assert!((table.to_internal("ModulusOfVolumeCompressibility", 0.2) - 0.0002).abs() < 1e-15);
+        assert!((table.to_internal("AngleFriction", 30.0) - 30.0_f64.to_radians()).abs() < 1e-12);
+        assert_eq!(table.to_internal("UndrainedShearStrength", 75.0), 75.0);
+        assert_eq!(table.to_internal("PoissonsRatio", 0.3), 0.3);
+        assert_eq!(table.to_internal("CoefficientOfConsolidation", 2.0), 2.0);
+        assert!((table.to_agsi("AngleFriction", 30.0_f64.to_radians()) - 30.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn unit_table_reads_only_parameter_code_sets() {
+        let agsi = parse_agsi(
+            r#"{
+                "agsSchema": {"name": "AGSi", "version": "1.0.1"},
+                "agsFile": {"title": "Units", "producedBy": "test"},
+                "agsProject": {
+                    "projectName": "Units",
+                    "agsProjectCodeSet": [
+                        {
+                            "usedByObject": "agsiDataParameterValue",
+                            "usedByAttribute": "codeID",
+                            "agsProjectCode": [
+                                {"codeID": "CoefficientOfConsolidation", "description": "cv", "units": "m2/s"},
+                                {"codeID": "PorePressure", "description": "u", "units": "MPa"},
+                                {"codeID": "PiezometricHead", "description": "Head", "units": "mm"},
+                                {"codeID": "OverconsolidationRatio", "description": "OCR", "units": "-"},
+                                {"codeID": "PoissonsRatio", "description": "nu", "units": "%"},
+                                {"codeID": "MoistureContent", "description": "w", "units": "%"}
+                            ]
+                        },
+                        {
+                            "usedByObject": "agsiDataPropertyValue",
+                            "usedByAttribute": "codeID",
+                            "agsProjectCode": [
+                                {"codeID": "UndrainedShearStrength", "description": "Hand vane", "units": "MPa"},
+                                {"codeID": "Depth", "description": "Depth", "units": "ft"}
+                            ]
+                        }
+                    ]
+                },
+                "agsiModel": []
+            }"#,
+        )
+        .unwrap();
+        let table = UnitTable::from_agsi(&agsi).unwrap();
+
+        let per_year = table.to_internal("CoefficientOfConsolidation", 1.0e-7);
+        assert!((per_year - 1.0e-7 * 365.25 * 86400.0).abs() < 1e-9);
+        assert_eq!(table.to_internal("PorePressure", 0.1), 100.0);
+        assert_eq!(table.to_internal("PiezometricHead", 2500.0), 2.5);
+        assert_eq!(table.to_internal("OverconsolidationRatio", 2.0), 2.0);
+        assert_eq!(table.to_internal("PoissonsRatio", 30.0), 0.3);
+        assert_eq!(table.to_internal("MoistureContent", 25.0), 25.0);
+        // The property code set does not redefine parameter units
+        assert_eq!(table.to_internal("UndrainedShearStrength", 75.0), 75.0);
+    }
+
+    #[test]
+    fn unit_table_rejects_unknown_units_for_modelled_codes() {
+        let agsi = parse_agsi(
+            r#"{
+                "agsSchema": {"name": "AGSi", "version": "1.0.1"},
+                "agsFile": {"title": "Units", "producedBy": "test"},
+                "agsProject": {
+                    "projectName": "Units",
+                    "agsProjectCodeSet": [{
+                        "usedByObject": "agsiDataParameterValue",
+                        "usedByAttribute": "codeID",
+                        "agsProjectCode": [
+                            {"codeID": "AngleFriction", "description": "phi", "units": "gradians"}
+                        ]
+                    }]
+                },
+                "agsiModel": []
+            }"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            UnitTable::from_agsi(&agsi),
+            Err(AgsiError::Parse { ref path, .. }) if path.ends_with("agsProjectCode[0].units")
+        ));
+
+        let agsi = parse_agsi(
+            r#"{
+                "agsSchema": {"name": "AGSi", "version": "1.0.1"},
+                "agsFile": {"title": "Units", "producedBy": "test"},
+                "agsProject": {
+                    "projectName": "Units",
+                    "agsProjectCodeSet": [{
+                        "usedByObject": "agsiDataParameterValue",
+                        "usedByAttribute": "codeID",
+                        "agsProjectCode": [
+                            {"codeID": "PoissonsRatio", "description": "nu", "units": ""},
+                            {"codeID": "Permeability", "description": "k", "units": "m/s"}
+                        ]
+                    }]
+                },
+                "agsiModel": []
+            }"#,
+        )
+        .unwrap();
+        // Codes this library does not model load unchanged
+        let table = UnitTable::from_agsi(&agsi).unwrap();
+        assert_eq!(table.to_internal("Permeability", 1.0e-9), 1.0e-9);
+        assert_eq!(table.declared_unit("Permeability"), Some("m/s"));
+        assert_eq!(table.declared_unit("PoissonsRatio"), None);
+        assert_eq!(table.to_internal("PoissonsRatio", 0.3), 0.3);
+    }
+}
@@ -0,0 +1,588 @@
+//! Sanity checks on parameter values and layer geometry.
+//!
+//! Each check is a rule with an ID such as `PHI_PRIME_RANGE` and a default severity.
+//! [`ValidationRules`] can disable rules, change their severity and adjust the limits, and
+//! can be read from JSON or TOML so a QA pipeline can keep its rule set with the project.
+
+use crate::config;
+use crate::stratigraphy::{Stratigraphy, StratigraphyIssue};
+use crate::{GroundModel, GroundModelError, SoilParams, SoilType};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+const KIND: &str = "validation rules";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// Rule IDs with their default severity.
+pub const VALIDATION_RULES: [(&str, Severity); 17] = [
+    ("UNIT_WEIGHT_NOT_POSITIVE", Severity::Error),
+    ("UNIT_WEIGHT_RANGE", Severity::Warning),
+    ("SATURATED_BELOW_BULK", Severity::Warning),
+    ("PHI_PRIME_RANGE", Severity::Error),
+    ("POISSONS_RATIO_RANGE", Severity::Error),
+    ("NEGATIVE_PARAMETER", Severity::Error),
+    ("STRESS_HISTORY_RANGE", Severity::Error),
+    ("MISSING_STRENGTH", Severity::Warning),
+    ("ROCK_MISSING_HOEK_BROWN", Severity::Error),
+    ("HOEK_BROWN_RANGE", Severity::Error),
+    ("NO_LAYERS", Severity::Error),
+    ("LAYER_INVERTED", Severity::Error),
+    ("LAYER_OVERLAP", Severity::Error),
+    ("LAYER_GAP", Severity::Error),
+    ("LAYER_BASE_MISSING", Severity::Warning),
+    ("MISSING_SOIL_PARAMS", Severity::Error),
+    ("UNUSED_SOIL_PARAMS", Severity::Warning),
+];
+
+/// One problem found by [`GroundModel::validate`] or [`SoilParams::validate`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelIssue {
+    pub rule: String,
+    pub severity: Severity,
+    /// Unit reference of the layer or parameter set, or `None` for the whole model.
+    pub reference: Option<String>,
+    /// Design case of the parameter set, if any.
+    pub case_id: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for ModelIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]", self.severity, self.rule)?;
+        if let Some(reference) = &self.reference {
+            write!(f, " {}", reference)?;
+        }
+        if let Some(case_id) = &self.case_id {
+            write!(f, " ({})", case_id)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Which rules run, at what severity, and the limits they check against.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ValidationRules {
+    /// Rule IDs that are not checked.
+    pub disabled: Vec<String>,
+    /// Severity overrides keyed by rule ID.
+    pub severity: BTreeMap<String, Severity>,
+    /// Largest credible φ′ in degrees.
+    pub max_phi_prime_deg: f64,
+    /// Credible bulk unit weights in kN/m³.
+    pub unit_weight_range: [f64; 2],
+    /// Gaps or overlaps between layers up to this size (m) are ignored.
+    pub level_tolerance: f64,
+}
+
+impl Default for ValidationRules {
+    fn default() -> Self {
+        ValidationRules {
+            disabled: Vec::new(),
+            severity: BTreeMap::new(),
+            max_phi_prime_deg: 50.0,
+            unit_weight_range: [10.0, 30.0],
+            level_tolerance: 0.001,
+        }
+    }
+}
+
+impl ValidationRules {
+    pub fn from_json(text: &str) -> Result<Self, GroundModelError> {
+        config::from_json::<Self>(KIND, text)?.checked("JSON")
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, GroundModelError> {
+        config::from_toml::<Self>(KIND, text)?.checked("TOML")
+    }
+
+    /// Reads a rule set from a `.toml` file, or JSON otherwise.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, GroundModelError> {
+        let path = path.as_ref();
+        config::load::<Self>(KIND, path)?.checked(&path.display().to_string())
+    }
+
+    /// Rejects unknown rule IDs so a misspelt rule is not silently left enabled.
+    fn checked(self, name: &str) -> Result<Self, GroundModelError> {
+        let unknown = self
+            .disabled
+            .iter()
+            .chain(self.severity.keys())
+            .find(|rule| default_severity(rule).is_none());
+        match unknown {
+            Some(rule) => Err(config::invalid(
+                KIND,
+                name,
+                format!("unknown rule '{}'", rule),
+            )),
+            None => Ok(self),
+        }
+    }
+
+    pub fn is_enabled(&self, rule: &str) -> bool {
+        !self.disabled.iter().any(|disabled| disabled == rule)
+    }
+
+    pub fn severity_of(&self, rule: &str) -> Severity {
+        self.severity
+            .get(rule)
+            .copied()
+            .or_else(|| default_severity(rule))
+            .unwrap_or(Severity::Error)
+    }
+}
+
+fn default_severity(rule: &str) -> Option<Severity> {
+    VALIDATION_RULES
+        .iter()
+        .find(|(id, _)| *id == rule)
+        .map(|(_, severity)| *severity)
+}
+
+struct Checker<'a> {
+    rules: &'a ValidationRules,
+    issues: Vec<ModelIssue>,
+}
+
+impl Checker<'_> {
+    fn report(
+        &mut self,
+        rule: &'static str,
+        reference: Option<&str>,
+        case_id: Option<&str>,
+        message: String,
+    ) {
+        if self.rules.is_enabled(rule) {
+            self.issues.push(ModelIssue {
+                rule: rule.to_string(),
+                severity: self.rules.severity_of(rule),
+                reference: reference.map(str::to_string),
+                case_id: case_id.map(str::to_string),
+                message,
+            });
+        }
+    }
+
+    fn params(&mut self, params: &SoilParams) {
+        let rules = self.rules;
+        let mut report = |rule, message| {
+            self.report(
+                rule,
+                Some(&params.reference),
+                params.case_id.as_deref(),
+                message,
+            )
+        };
+
+        if params.unit_weight <= 0.0 {
+            report(
+                "UNIT_WEIGHT_NOT_POSITIVE",
+                format!("unit weight {} kN/m³ is not positive", params.unit_weight),
+            );
+        } else {
+            let [low, high] = rules.unit_weight_range;
+            if params.unit_weight < low || params.unit_weight > high {
+                report(
+                    "UNIT_WEIGHT_RANGE",
+                    format!(
+                        "unit weight {} kN/m³ is outside {}–{} kN/m³",
+                        params.unit_weight, low, high
+                    ),
+                );
+            }
+        }
+        if let Some(saturated) = params.saturated_unit_weight {
+            if saturated <= 0.0 {
+                report(
+                    "UNIT_WEIGHT_NOT_POSITIVE",
+                    format!("saturated unit weight {} kN/m³ is not positive", saturated),
+                );
+            } else if saturated < params.unit_weight {
+                report(
+                    "SATURATED_BELOW_BULK",
+                    format!(
+                        "saturated unit weight {} kN/m³ is below the bulk unit weight {} kN/m³",
+                        saturated, params.unit_weight
+                    ),
+                );
+            }
+        }
+
+        // φ′ = 0 is a valid total-stress (undrained) parameter set
+        if let Some(phi) = params.phi_prime {
+            if phi < 0.0 || phi.to_degrees() > rules.max_phi_prime_deg {
+                report(
+                    "PHI_PRIME_RANGE",
+                    format!(
+                        "φ′ = {:.1}° is outside 0–{}°",
+                        phi.to_degrees(),
+                        rules.max_phi_prime_deg
+                    ),
+                );
+            }
+        }
+
+        if !(0.0..0.5).contains(&params.poissons_ratio) {
+            report(
+                "POISSONS_RATIO_RANGE",
+                format!(
+                    "Poisson's ratio {} is outside 0 ≤ ν < 0.5",
+                    params.poissons_ratio
+                ),
+            );
+        }
+
+        let signed = [
+            ("c′", params.c_prime),
+            ("cu", params.cu),
+            ("E", Some(params.youngs_modulus)),
+            ("mv", Some(params.mv)),
+            ("recompression mv", params.mv_recompression),
+            ("cv", Some(params.coefficient_of_consolidation)),
+            ("UCS", params.ucs),
+        ];
+        for (name, value) in signed {
+            if let Some(value) = value.filter(|value| *value < 0.0) {
+                report(
+                    "NEGATIVE_PARAMETER",
+                    format!("{} = {} is negative", name, value),
+                );
+            }
+        }
+
+        if let Some(ocr) = params.ocr.filter(|ocr| *ocr < 1.0) {
+            report("STRESS_HISTORY_RANGE", format!("OCR {} is below 1", ocr));
+        }
+        let pressures = [
+            ("POP", params.pop),
+            ("σ′p", params.preconsolidation_pressure),
+        ];
+        for (name, value) in pressures {
+            if let Some(value) = value.filter(|value| *value < 0.0) {
+                report(
+                    "STRESS_HISTORY_RANGE",
+                    format!("{} = {} kPa is negative", name, value),
+                );
+            }
+        }
+
+        match params.behaviour {
+            SoilType::Rock => {
+                let missing: Vec<&str> = [
+                    ("gsi", params.gsi),
+                    ("mi", params.mi),
+                    ("ucs", params.ucs.filter(|ucs| *ucs > 0.0)),
+                ]
+                .into_iter()
+                .filter(|(_, value)| value.is_none())
+                .map(|(name, _)| name)
+                .collect();
+                if !missing.is_empty() {
+                    report(
+                        "ROCK_MISSING_HOEK_BROWN",
+                        format!("rock unit has no {}", missing.join(", ")),
+                    );
+                }
+                if params.gsi.is_some_and(|gsi| !(0.0..=100.0).contains(&gsi)) {
+                    report(
+                        "HOEK_BROWN_RANGE",
+                        format!("GSI {} is outside 0–100", params.gsi.unwrap_or_default()),
+                    );
+                }
+                if params.mi.is_some_and(|mi| mi <= 0.0) {
+                    report(
+                        "HOEK_BROWN_RANGE",
+                        format!("mi {} is not positive", params.mi.unwrap_or_default()),
+                    );
+                }
+                if !(0.0..=1.0).contains(&params.disturbance) {
+                    report(
+                        "HOEK_BROWN_RANGE",
+                        format!("disturbance D {} is outside 0–1", params.disturbance),
+                    );
+                }
+            }
+            SoilType::Cohesive if params.cu.is_none() && params.phi_prime.is_none() => {
+                report(
+                    "MISSING_STRENGTH",
+                    "cohesive unit has neither cu nor φ′".to_string(),
+                );
+            }
+            SoilType::Granular if params.phi_prime.is_none() => {
+                report("MISSING_STRENGTH", "granular unit has no φ′".to_string());
+            }
+            _ => {}
+        }
+    }
+
+    fn layers(&mut self, model: &GroundModel) {
+        if model.soil_layers.is_empty() {
+            self.report("NO_LAYERS", None, None, "model has no layers".to_string());
+            return;
+        }
+
+        let stratigraphy =
+            Stratigraphy::from_layers(&model.soil_layers, self.rules.level_tolerance);
+        for issue in &stratigraphy.issues {
+            match issue {
+                StratigraphyIssue::Gap {
+                    upper,
+                    lower,
+                    top,
+                    base,
+                } => self.report(
+                    "LAYER_GAP",
+                    Some(upper),
+                    None,
+                    format!("gap between base {} and top {} of {}", top, base, lower),
+                ),
+                StratigraphyIssue::Overlap {
+                    upper,
+                    lower,
+                    top,
+                    base,
+                } => self.report(
+                    "LAYER_OVERLAP",
+                    Some(upper),
+                    None,
+                    format!("base {} is below top {} of {}", base, top, lower),
+                ),
+            }
+        }
+        for layer in stratigraphy
+            .layers
+            .iter()
+            .filter(|layer| layer.base_inferred)
+        {
+            self.report(
+                "LAYER_BASE_MISSING",
+                Some(&layer.unit_reference),
+                None,
+                format!(
+                    "no base level, taken as {} from the layer below",
+                    layer.base.unwrap_or_default()
+                ),
+            );
+        }
+
+        for layer in &model.soil_layers {
+            let reference = Some(layer.unit_reference.as_str());
+            if let Some(base) = layer.base_level.filter(|base| *base >= layer.top_level) {
+                self.report(
+                    "LAYER_INVERTED",
+                    reference,
+                    None,
+                    format!("base {} is not below top {}", base, layer.top_level),
+                );
+            }
+            if model.get_soil_params(&layer.unit_reference).is_none() {
+                self.report(
+                    "MISSING_SOIL_PARAMS",
+                    reference,
+                    None,
+                    "layer has no matching soil parameters".to_string(),
+                );
+            }
+        }
+
+        for params in &model.soil_params {
+            let used = model
+                .soil_layers
+                .iter()
+                .any(|layer| layer.unit_reference == params.reference);
+            if !used {
+                self.report(
+                    "UNUSED_SOIL_PARAMS",
+                    Some(&params.reference),
+                    params.case_id.as_deref(),
+                    "parameters are not used by any layer".to_string(),
+                );
+            }
+        }
+    }
+}
+
+impl SoilParams {
+    /// Checks parameter values against the default rules.
+    pub fn validate(&self) -> Vec<ModelIssue> {
+        self.validate_with(&ValidationRules::default())
+    }
+
+    pub fn validate_with(&self, rules: &ValidationRules) -> Vec<ModelIssue> {
+        let mut checker = Checker {
+            rules,
+            issues: Vec::new(),
+        };
+        checker.params(self);
+        checker.issues
+    }
+}
+
+impl GroundModel {
+    /// Checks layer geometry, the layer-to-parameter links and every parameter set,
+    /// including design cases, against the default rules. Errors are listed before
+    /// warnings.
+    pub fn validate(&self) -> Vec<ModelIssue> {
+        self.validate_with(&ValidationRules::default())
+    }
+
+    pub fn validate_with(&self, rules: &ValidationRules) -> Vec<ModelIssue> {
+        let mut checker = Checker {
+            rules,
+            issues: Vec::new(),
+        };
+        checker.layers(self);
+        for params in self.soil_params.iter().chain(self.cases.values().flatten()) {
+            checker.params(params);
+        }
+        let mut issues = checker.issues;
+        issues.sort_by_key(|issue| issue.severity);
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::SoilLayer;
+
+    fn clay() -> SoilParams {
+        SoilParams {
+            youngs_modulus: 40000.0,
+            cu: Some(75.0),
+            ..fixtures::clay("Clay", 0.0001, 2.5)
+        }
+    }
+
+    fn rules(issues: &[ModelIssue]) -> Vec<&str> {
+        issues.iter().map(|issue| issue.rule.as_str()).collect()
+    }
+
+    #[test]
+    fn soil_params_checks() {
+        assert!(clay().validate().is_empty());
+        let mut undrained = clay();
+        undrained.phi_prime = Some(0.0);
+        assert!(undrained.validate().is_empty());
+
+        let mut sand = clay();
+        sand.behaviour = SoilType::Granular;
+        sand.cu = None;
+        sand.unit_weight = -18.0;
+        sand.phi_prime = Some(55.0_f64.to_radians());
+        sand.poissons_ratio = 0.5;
+        sand.c_prime = Some(-2.0);
+        assert_eq!(
+            rules(&sand.validate()),
+            [
+                "UNIT_WEIGHT_NOT_POSITIVE",
+                "PHI_PRIME_RANGE",
+                "POISSONS_RATIO_RANGE",
+                "NEGATIVE_PARAMETER"
+            ]
+        );
+
+        let mut rock = clay();
+        rock.behaviour = SoilType::Rock;
+        rock.gsi = Some(120.0);
+        let issues = rock.validate();
+        assert_eq!(
+            rules(&issues),
+            ["ROCK_MISSING_HOEK_BROWN", "HOEK_BROWN_RANGE"]
+        );
+        assert_eq!(issues[0].message, "rock unit has no mi, ucs");
+
+        let mut overconsolidated = clay();
+        overconsolidated.ocr = Some(0.8);
+        overconsolidated.pop = Some(-10.0);
+        let issues = overconsolidated.validate();
+        assert_eq!(
+            rules(&issues),
+            ["STRESS_HISTORY_RANGE", "STRESS_HISTORY_RANGE"]
+        );
+        assert_eq!(issues[1].message, "POP = -10 kPa is negative");
+    }
+
+    #[test]
+    fn ground_model_checks_layers_and_links() {
+        let mut sand = clay();
+        sand.reference = "Sand".to_string();
+        sand.behaviour = SoilType::Granular;
+        sand.phi_prime = None;
+        let model = GroundModel::new(
+            vec![
+                SoilLayer::with_unit_reference(
+                    10.0,
+                    6.0,
+                    "Made Ground".to_string(),
+                    "MG".to_string(),
+                ),
+                SoilLayer::with_unit_reference(5.0, -2.0, "Clay".to_string(), "Clay".to_string()),
+                SoilLayer::with_unit_reference(0.0, -10.0, "Sand".to_string(), "Sand".to_string()),
+            ],
+            vec![clay(), sand],
+        );
+
+        let issues = model.validate();
+        assert_eq!(
+            rules(&issues),
+            [
+                "LAYER_GAP",
+                "LAYER_OVERLAP",
+                "MISSING_SOIL_PARAMS",
+                "MISSING_STRENGTH"
+            ]
+        );
+        assert_eq!(issues[0].reference.as_deref(), Some("MG"));
+        assert_eq!(issues[3].severity, Severity::Warning);
+        assert_eq!(
+            issues[1].to_string(),
+            "error[LAYER_OVERLAP] Clay: base -2 is below top 0 of Sand"
+        );
+
+        let rules = ValidationRules::from_toml(
+            r#"
+            disabled = ["LAYER_GAP"]
+            level_tolerance = 2.5
+
+            [severity]
+            MISSING_SOIL_PARAMS = "warning"
+            "#,
+        )
+        .unwrap();
+        let relaxed = model.validate_with(&rules);
+        assert_eq!(
+            relaxed
+                .iter()
+                .map(|issue| (issue.rule.as_str(), issue.severity))
+                .collect::<Vec<_>>(),
+            [
+                ("MISSING_SOIL_PARAMS", Severity::Warning),
+                ("MISSING_STRENGTH", Severity::Warning)
+            ]
+        );
+
+        assert!(matches!(
+            ValidationRules::from_json(r#"{"disabled": ["LAYER_GAPS"]}"#),
+            Err(GroundModelError::InvalidConfig { .. })
+        ));
+    }
+}
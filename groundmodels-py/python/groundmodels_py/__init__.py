@@ -7,7 +7,37 @@ ground modeling and soil parameter analysis capabilities.
 
 from .groundmodels_py import PySoilParams as SoilParams
 from .groundmodels_py import PyGroundModel as GroundModel
+from .groundmodels_py import PyLoad as Load
+from .groundmodels_py import PyConsolidationSettlement as ConsolidationSettlement
 from .groundmodels_py import convert_agsi_to_json
+from .groundmodels_py import (
+    GroundModelError,
+    MissingParameterError,
+    InvalidParameterError,
+    CalculationError,
+    LevelOutsideModelError,
+    InvalidGeometryError,
+    UnknownCaseError,
+    NotFactoredError,
+    AgsiParseError,
+    AgsiError,
+)
 
-__all__ = ["SoilParams", "GroundModel", "convert_agsi_to_json"]
+__all__ = [
+    "SoilParams",
+    "GroundModel",
+    "Load",
+    "ConsolidationSettlement",
+    "convert_agsi_to_json",
+    "GroundModelError",
+    "MissingParameterError",
+    "InvalidParameterError",
+    "CalculationError",
+    "LevelOutsideModelError",
+    "InvalidGeometryError",
+    "UnknownCaseError",
+    "NotFactoredError",
+    "AgsiParseError",
+    "AgsiError",
+]
 __version__ = "0.1.0"
\ No newline at end of file
@@ -1,7 +1,50 @@
 use pyo3::prelude::*;
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
 use pyo3::types::PyDict;
 use groundmodels_core::{SoilParams, GroundModel};
-use serde_json;
+use groundmodels_core::GroundModelError as CoreError;
+use groundmodels_core::construction::ConstructionSequence;
+use groundmodels_core::design_approach::DesignApproach;
+use groundmodels_core::hoek_brown::{HoekBrownApplication, RockConfinement};
+use groundmodels_core::earth_pressure::{EarthPressureState, WallGeometry};
+use groundmodels_core::agsi_io::{parse_agsi, AgsiError as CoreAgsiError, AgsiExportOptions};
+use groundmodels_core::loads::Load;
+use groundmodels_core::settlement::{ConsolidationOptions, ConsolidationSettlement, StressChange};
+use groundmodels_core::validation::ValidationRules;
+
+create_exception!(groundmodels_py, GroundModelError, PyValueError, "Base class for ground model errors.");
+create_exception!(groundmodels_py, MissingParameterError, GroundModelError, "A calculation needs a parameter that is not set.");
+create_exception!(groundmodels_py, InvalidParameterError, GroundModelError, "A parameter is set but cannot be used.");
+create_exception!(groundmodels_py, CalculationError, GroundModelError, "A calculation has no real result.");
+create_exception!(groundmodels_py, LevelOutsideModelError, GroundModelError, "No layer covers the requested level.");
+create_exception!(groundmodels_py, InvalidGeometryError, GroundModelError, "The layer geometry cannot be used.");
+create_exception!(groundmodels_py, UnknownCaseError, GroundModelError, "The model has no parameters for the case.");
+create_exception!(groundmodels_py, NotFactoredError, GroundModelError, "The parameters have no partial factors applied.");
+create_exception!(groundmodels_py, AgsiParseError, GroundModelError, "The AGSi document does not match the schema.");
+create_exception!(groundmodels_py, AgsiError, GroundModelError, "The AGSi document could not be read or has no matching model.");
+
+fn to_py_err(err: CoreError) -> PyErr {
+    let message = err.to_string();
+    match err {
+        CoreError::MissingParameter { .. } => MissingParameterError::new_err(message),
+        CoreError::InvalidParameter { .. } => InvalidParameterError::new_err(message),
+        CoreError::Calculation { .. } => CalculationError::new_err(message),
+        CoreError::LevelOutsideModel { .. } => LevelOutsideModelError::new_err(message),
+        CoreError::InvalidGeometry { .. } => InvalidGeometryError::new_err(message),
+        CoreError::UnknownCase { .. } => UnknownCaseError::new_err(message),
+        CoreError::NotFactored { .. } => NotFactoredError::new_err(message),
+        CoreError::Agsi(CoreAgsiError::Parse { .. }) => AgsiParseError::new_err(message),
+        CoreError::Agsi(_) => AgsiError::new_err(message),
+        _ => GroundModelError::new_err(message),
+    }
+}
+
+fn load_ground_model(agsi_json: &str, model: Option<&str>) -> PyResult<GroundModel> {
+    parse_agsi(agsi_json)
+        .and_then(|agsi| GroundModel::from_agsi_model(&agsi, model))
+        .map_err(|e| to_py_err(e.into()))
+}
 
 #[pyclass]
 #[derive(Clone)]
@@ -38,6 +81,7 @@ impl PySoilParams {
         self.inner.youngs_modulus = value;
     }
 
+    /// Effective angle of friction in radians.
     #[getter]
     fn phi_prime(&self) -> Option<f64> {
         self.inner.phi_prime
@@ -68,11 +112,108 @@ impl PySoilParams {
         self.inner.cu = value;
     }
 
+    #[getter]
+    fn ocr(&self) -> Option<f64> {
+        self.inner.ocr
+    }
+
+    #[setter]
+    fn set_ocr(&mut self, value: Option<f64>) {
+        self.inner.ocr = value;
+    }
+
+    /// Pre-overburden pressure in kPa.
+    #[getter]
+    fn pop(&self) -> Option<f64> {
+        self.inner.pop
+    }
+
+    #[setter]
+    fn set_pop(&mut self, value: Option<f64>) {
+        self.inner.pop = value;
+    }
+
+    /// Preconsolidation pressure in kPa.
+    #[getter]
+    fn preconsolidation_pressure(&self) -> Option<f64> {
+        self.inner.preconsolidation_pressure
+    }
+
+    #[setter]
+    fn set_preconsolidation_pressure(&mut self, value: Option<f64>) {
+        self.inner.preconsolidation_pressure = value;
+    }
+
+    /// Recompression mv in m²/kN.
+    #[getter]
+    fn mv_recompression(&self) -> Option<f64> {
+        self.inner.mv_recompression
+    }
+
+    #[setter]
+    fn set_mv_recompression(&mut self, value: Option<f64>) {
+        self.inner.mv_recompression = value;
+    }
+
+    #[getter]
+    fn case_id(&self) -> Option<String> {
+        self.inner.case_id.clone()
+    }
+
     #[getter]
     fn behaviour(&self) -> String {
         format!("{:?}", self.inner.behaviour)
     }
 
+    #[pyo3(signature = (slope=None))]
+    fn k_active(&self, slope: Option<f64>) -> PyResult<f64> {
+        self.inner.get_k_active(slope).map_err(to_py_err)
+    }
+
+    #[pyo3(signature = (slope=None))]
+    fn k_passive(&self, slope: Option<f64>) -> PyResult<f64> {
+        self.inner.get_k_passive(slope).map_err(to_py_err)
+    }
+
+    fn k0(&self) -> PyResult<f64> {
+        self.inner.k0().map_err(to_py_err)
+    }
+
+    fn k0_overconsolidated(&self, ocr: f64) -> PyResult<f64> {
+        self.inner.k0_overconsolidated(ocr).map_err(to_py_err)
+    }
+
+    /// K0 at vertical effective stress `effective`, allowing for the stress history.
+    fn k0_at(&self, effective: f64) -> PyResult<f64> {
+        self.inner.k0_at(effective).map_err(to_py_err)
+    }
+
+    #[pyo3(signature = (backfill_slope=0.0, wall_inclination=0.0, friction_ratio=0.0))]
+    fn coulomb_k_active(&self, backfill_slope: f64, wall_inclination: f64, friction_ratio: f64) -> PyResult<f64> {
+        let geometry = WallGeometry::new(backfill_slope, wall_inclination, friction_ratio);
+        self.inner.coulomb_k_active(&geometry).map_err(to_py_err)
+    }
+
+    #[pyo3(signature = (backfill_slope=0.0, wall_inclination=0.0, friction_ratio=0.0))]
+    fn coulomb_k_passive(&self, backfill_slope: f64, wall_inclination: f64, friction_ratio: f64) -> PyResult<f64> {
+        let geometry = WallGeometry::new(backfill_slope, wall_inclination, friction_ratio);
+        self.inner.coulomb_k_passive(&geometry).map_err(to_py_err)
+    }
+
+    /// EN 1997-1 Annex C coefficients as a dict of k_n, k_gamma, k_q and k_c.
+    #[pyo3(signature = (passive=false, backfill_slope=0.0, wall_inclination=0.0, friction_ratio=0.0))]
+    fn annex_c_coefficients(&self, py: Python<'_>, passive: bool, backfill_slope: f64, wall_inclination: f64, friction_ratio: f64) -> PyResult<PyObject> {
+        let state = if passive { EarthPressureState::Passive } else { EarthPressureState::Active };
+        let geometry = WallGeometry::new(backfill_slope, wall_inclination, friction_ratio);
+        let coefficients = self.inner.annex_c_coefficients(state, &geometry).map_err(to_py_err)?;
+        let dict = PyDict::new_bound(py);
+        dict.set_item("k_n", coefficients.k_n)?;
+        dict.set_item("k_gamma", coefficients.k_gamma)?;
+        dict.set_item("k_q", coefficients.k_q)?;
+        dict.set_item("k_c", coefficients.k_c)?;
+        Ok(dict.into())
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "SoilParams(unit_weight={}, youngs_modulus={}, behaviour={})",
@@ -82,11 +223,8 @@ impl PySoilParams {
 
     #[staticmethod]
     fn from_agsi_json(agsi_json: &str) -> PyResult<Self> {
-        let agsi_data: serde_json::Value = serde_json::from_str(agsi_json)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
-        
         // Extract first soil params from ground model
-        let ground_model = GroundModel::from_agsi_file(&agsi_data);
+        let ground_model = load_ground_model(agsi_json, None)?;
         let soil_params = if let Some(first_params) = ground_model.soil_params.first() {
             first_params.clone()
         } else {
@@ -104,11 +242,117 @@ impl PySoilParams {
             dict.set_item("c_prime", self.inner.c_prime)?;
             dict.set_item("cu", self.inner.cu)?;
             dict.set_item("behaviour", format!("{:?}", self.inner.behaviour))?;
+            dict.set_item("case_id", self.inner.case_id.clone())?;
             Ok(dict.to_object(py))
         })
     }
 }
 
+#[pyclass]
+#[derive(Clone)]
+pub struct PyLoad {
+    inner: Load,
+}
+
+#[pymethods]
+impl PyLoad {
+    #[staticmethod]
+    fn uniform(pressure: f64) -> Self {
+        Self { inner: Load::uniform(pressure) }
+    }
+
+    #[staticmethod]
+    fn strip(x: f64, width: f64, pressure: f64) -> Self {
+        Self { inner: Load::strip(x, width, pressure) }
+    }
+
+    #[staticmethod]
+    fn rectangular(x: f64, y: f64, width: f64, length: f64, pressure: f64) -> Self {
+        Self { inner: Load::rectangular(x, y, width, length, pressure) }
+    }
+
+    #[staticmethod]
+    fn circular(x: f64, y: f64, radius: f64, pressure: f64) -> Self {
+        Self { inner: Load::circular(x, y, radius, pressure) }
+    }
+
+    #[staticmethod]
+    fn embankment(x: f64, crest_width: f64, side_width: f64, height: f64, unit_weight: f64) -> Self {
+        Self { inner: Load::embankment(x, crest_width, side_width, height, unit_weight) }
+    }
+
+    fn at_level(&self, level: f64) -> Self {
+        Self { inner: self.inner.clone().at_level(level) }
+    }
+
+    #[getter]
+    fn pressure(&self) -> f64 {
+        self.inner.pressure
+    }
+
+    #[getter]
+    fn level(&self) -> Option<f64> {
+        self.inner.level
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Load({:?}, pressure={})", self.inner.shape, self.inner.pressure)
+    }
+}
+
+#[pyclass]
+pub struct PyConsolidationSettlement {
+    inner: ConsolidationSettlement,
+}
+
+#[pymethods]
+impl PyConsolidationSettlement {
+    /// Final primary consolidation settlement (m).
+    #[getter]
+    fn total(&self) -> f64 {
+        self.inner.total()
+    }
+
+    fn settlement_at(&self, time: f64) -> f64 {
+        self.inner.settlement_at(time)
+    }
+
+    fn degree_at(&self, time: f64) -> f64 {
+        self.inner.degree_at(time)
+    }
+
+    fn time_settlement_curve(&self, times: Vec<f64>) -> Vec<(f64, f64)> {
+        self.inner.time_settlement_curve(&times)
+            .into_iter()
+            .map(|[time, settlement]| (time, settlement))
+            .collect()
+    }
+
+    #[getter]
+    fn layers(&self) -> PyResult<Vec<PyObject>> {
+        Python::with_gil(|py| {
+            self.inner.layers.iter()
+                .map(|layer| {
+                    let dict = PyDict::new_bound(py);
+                    dict.set_item("reference", layer.reference.clone())?;
+                    dict.set_item("top_level", layer.top_level)?;
+                    dict.set_item("base_level", layer.base_level)?;
+                    dict.set_item("mean_stress_change", layer.mean_stress_change)?;
+                    dict.set_item("settlement", layer.settlement)?;
+                    dict.set_item("coefficient_of_consolidation", layer.coefficient_of_consolidation)?;
+                    dict.set_item("drainage", format!("{:?}", layer.drainage))?;
+                    dict.set_item("drainage_path", layer.drainage_path)?;
+                    Ok(dict.to_object(py))
+                })
+                .collect()
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!("ConsolidationSettlement(total={}, layers={})", self.inner.total(), self.inner.layers.len())
+    }
+}
+
 #[pyclass]
 pub struct PyGroundModel {
     inner: GroundModel,
@@ -130,6 +374,166 @@ impl PyGroundModel {
             .collect()
     }
 
+    #[getter]
+    fn case_ids(&self) -> Vec<String> {
+        self.inner.cases.keys().cloned().collect()
+    }
+
+    fn for_case(&self, case_id: &str) -> PyResult<Self> {
+        let ground_model = self.inner.for_case(case_id).map_err(to_py_err)?;
+        Ok(Self { inner: ground_model })
+    }
+
+    /// Copy of the model with a built-in design approach, or one read from a JSON/TOML file, applied.
+    fn apply_design_approach(&self, name_or_path: &str) -> PyResult<Self> {
+        let approach = DesignApproach::resolve(name_or_path).map_err(to_py_err)?;
+        let ground_model = self.inner.apply_design_approach(&approach).map_err(to_py_err)?;
+        Ok(Self { inner: ground_model })
+    }
+
+    /// Copy of the model with rock layers converted to equivalent Mohr-Coulomb parameters.
+    /// σ3 is the in-situ stress unless a tunnel depth or slope height selects the Hoek σ3max.
+    #[pyo3(signature = (tunnel_depth=None, slope_height=None))]
+    fn to_equivalent_mohr_coulomb(&self, tunnel_depth: Option<f64>, slope_height: Option<f64>) -> PyResult<Self> {
+        let confinement = match (tunnel_depth, slope_height) {
+            (Some(depth), _) => RockConfinement::Sig3Max(HoekBrownApplication::Tunnel { depth }),
+            (None, Some(height)) => RockConfinement::Sig3Max(HoekBrownApplication::Slope { height }),
+            (None, None) => RockConfinement::InSitu,
+        };
+        let ground_model = self.inner.to_equivalent_mohr_coulomb(confinement).map_err(to_py_err)?;
+        Ok(Self { inner: ground_model })
+    }
+
+    /// Sanity checks on the layers and parameters, as dicts of rule, severity, reference,
+    /// case_id and message. `rules_path` is a JSON or TOML rule set.
+    #[pyo3(signature = (rules_path=None))]
+    fn validate(&self, rules_path: Option<&str>) -> PyResult<Vec<PyObject>> {
+        let rules = match rules_path {
+            Some(path) => ValidationRules::load(path).map_err(to_py_err)?,
+            None => ValidationRules::default(),
+        };
+        Python::with_gil(|py| {
+            self.inner.validate_with(&rules).iter()
+                .map(|issue| {
+                    let dict = PyDict::new_bound(py);
+                    dict.set_item("rule", issue.rule.clone())?;
+                    dict.set_item("severity", issue.severity.to_string())?;
+                    dict.set_item("reference", issue.reference.clone())?;
+                    dict.set_item("case_id", issue.case_id.clone())?;
+                    dict.set_item("message", issue.message.clone())?;
+                    Ok(dict.to_object(py))
+                })
+                .collect()
+        })
+    }
+
+    /// Stress history at each level inside the model, as dicts of level, reference,
+    /// effective, preconsolidation and ocr.
+    fn ocr_profile(&self, levels: Vec<f64>) -> PyResult<Vec<PyObject>> {
+        Python::with_gil(|py| {
+            self.inner.ocr_profile(&levels).iter()
+                .map(|point| {
+                    let dict = PyDict::new_bound(py);
+                    dict.set_item("level", point.level)?;
+                    dict.set_item("reference", point.reference.clone())?;
+                    dict.set_item("effective", point.effective)?;
+                    dict.set_item("preconsolidation", point.preconsolidation)?;
+                    dict.set_item("ocr", point.ocr)?;
+                    Ok(dict.to_object(py))
+                })
+                .collect()
+        })
+    }
+
+    fn k0_at_level(&self, level: f64) -> PyResult<f64> {
+        self.inner.k0_at_level(level).map_err(to_py_err)
+    }
+
+    /// Copy of the model with the ground above `level` removed.
+    fn excavate(&self, level: f64) -> Self {
+        Self { inner: self.inner.excavate(level) }
+    }
+
+    /// Copy of the model with fill of `params` placed from the ground surface up to `level`.
+    fn place_fill(&self, level: f64, params: &PySoilParams) -> PyResult<Self> {
+        let ground_model = self.inner.place_fill(level, &params.inner).map_err(to_py_err)?;
+        Ok(Self { inner: ground_model })
+    }
+
+    /// Copy of the model with a hydrostatic water table at `level`.
+    fn dewater(&self, level: f64) -> Self {
+        Self { inner: self.inner.dewater(level) }
+    }
+
+    /// Runs a JSON or TOML construction sequence and returns one dict per stage and level in
+    /// the ground, with stage, level, total, pore_pressure, effective, max_past_effective and
+    /// path.
+    fn construction_sequence(&self, sequence_path: &str, levels: Vec<f64>) -> PyResult<Vec<PyObject>> {
+        let history = ConstructionSequence::load(sequence_path)
+            .and_then(|sequence| sequence.run(&self.inner, &levels))
+            .map_err(to_py_err)?;
+        Python::with_gil(|py| {
+            let mut rows = Vec::new();
+            for stage in &history.stages {
+                for stress in stage.stresses.iter().flatten() {
+                    let dict = PyDict::new_bound(py);
+                    dict.set_item("stage", stage.name.clone())?;
+                    dict.set_item("level", stress.stress.level)?;
+                    dict.set_item("total", stress.stress.total)?;
+                    dict.set_item("pore_pressure", stress.stress.pore_pressure)?;
+                    dict.set_item("effective", stress.stress.effective)?;
+                    dict.set_item("max_past_effective", stress.max_past_effective)?;
+                    dict.set_item("path", format!("{:?}", stress.path).to_lowercase())?;
+                    rows.push(dict.to_object(py));
+                }
+            }
+            Ok(rows)
+        })
+    }
+
+    fn params_at_level(&self, level: f64) -> PyResult<PySoilParams> {
+        let params = self.inner.get_params_at_level(level).map_err(to_py_err)?;
+        Ok(PySoilParams { inner: params })
+    }
+
+    #[getter]
+    fn rigid_boundary(&self) -> Option<f64> {
+        self.inner.rigid_boundary
+    }
+
+    #[setter]
+    fn set_rigid_boundary(&mut self, value: Option<f64>) {
+        self.inner.rigid_boundary = value;
+    }
+
+    fn add_load(&mut self, load: &PyLoad) {
+        self.inner.loads.push(load.inner.clone());
+    }
+
+    /// Primary consolidation settlement below `(x, y)`. Uses `load` when given, otherwise
+    /// `stress_change` as `(level, increment)` pairs, otherwise the model's own loads.
+    #[pyo3(signature = (x=0.0, y=0.0, load=None, stress_change=None, sublayers=10))]
+    fn consolidation_settlement(
+        &self,
+        x: f64,
+        y: f64,
+        load: Option<PyLoad>,
+        stress_change: Option<Vec<(f64, f64)>>,
+        sublayers: usize,
+    ) -> PyResult<PyConsolidationSettlement> {
+        let stress_change = match (load, stress_change) {
+            (Some(load), _) => StressChange::Load { load: load.inner, x, y },
+            (None, Some(points)) => StressChange::Profile(
+                points.into_iter().map(|(level, increment)| [level, increment]).collect(),
+            ),
+            (None, None) => StressChange::ModelLoads { x, y },
+        };
+        let result = self.inner
+            .consolidation_settlement(&ConsolidationOptions { stress_change, sublayers })
+            .map_err(to_py_err)?;
+        Ok(PyConsolidationSettlement { inner: result })
+    }
+
     fn __len__(&self) -> usize {
         self.inner.soil_params.len()
     }
@@ -139,14 +543,30 @@ impl PyGroundModel {
     }
 
     #[staticmethod]
-    fn from_agsi_json(agsi_json: &str) -> PyResult<Self> {
-        let agsi_data: serde_json::Value = serde_json::from_str(agsi_json)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
-        
-        let ground_model = GroundModel::from_agsi_file(&agsi_data);
+    #[pyo3(signature = (agsi_json, model=None))]
+    fn from_agsi_json(agsi_json: &str, model: Option<&str>) -> PyResult<Self> {
+        let ground_model = load_ground_model(agsi_json, model)?;
         Ok(Self { inner: ground_model })
     }
 
+    #[pyo3(signature = (title=None, produced_by=None, model_id=None))]
+    fn to_agsi_json(
+        &self,
+        title: Option<String>,
+        produced_by: Option<String>,
+        model_id: Option<String>,
+    ) -> PyResult<String> {
+        let defaults = AgsiExportOptions::default();
+        let agsi = self.inner.to_agsi(AgsiExportOptions {
+            title: title.unwrap_or(defaults.title),
+            produced_by: produced_by.unwrap_or(defaults.produced_by),
+            model_id,
+            ..defaults
+        });
+        serde_json::to_string_pretty(&agsi)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+
     fn to_dict(&self) -> PyResult<PyObject> {
         Python::with_gil(|py| {
             let dict = PyDict::new_bound(py);
@@ -163,13 +583,11 @@ impl PyGroundModel {
 }
 
 #[pyfunction]
-fn convert_agsi_to_json(agsi_json: &str, convert_type: &str) -> PyResult<String> {
-    let agsi_data: serde_json::Value = serde_json::from_str(agsi_json)
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
-
+#[pyo3(signature = (agsi_json, convert_type, model=None))]
+fn convert_agsi_to_json(agsi_json: &str, convert_type: &str, model: Option<&str>) -> PyResult<String> {
     let result = match convert_type.to_lowercase().as_str() {
         "soil_params" | "soilparams" => {
-            let ground_model = GroundModel::from_agsi_file(&agsi_data);
+            let ground_model = load_ground_model(agsi_json, model)?;
             let soil_params = if let Some(first_params) = ground_model.soil_params.first() {
                 first_params.clone()
             } else {
@@ -178,7 +596,7 @@ fn convert_agsi_to_json(agsi_json: &str, convert_type: &str) -> PyResult<String>
             serde_json::to_string_pretty(&soil_params)
         }
         "ground_model" | "groundmodel" => {
-            let ground_model = GroundModel::from_agsi_file(&agsi_data);
+            let ground_model = load_ground_model(agsi_json, model)?;
             serde_json::to_string_pretty(&ground_model)
         }
         _ => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
@@ -193,6 +611,18 @@ fn convert_agsi_to_json(agsi_json: &str, convert_type: &str) -> PyResult<String>
 fn groundmodels_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PySoilParams>()?;
     m.add_class::<PyGroundModel>()?;
+    m.add_class::<PyLoad>()?;
+    m.add_class::<PyConsolidationSettlement>()?;
     m.add_function(wrap_pyfunction!(convert_agsi_to_json, m)?)?;
+    m.add("GroundModelError", m.py().get_type_bound::<GroundModelError>())?;
+    m.add("MissingParameterError", m.py().get_type_bound::<MissingParameterError>())?;
+    m.add("InvalidParameterError", m.py().get_type_bound::<InvalidParameterError>())?;
+    m.add("CalculationError", m.py().get_type_bound::<CalculationError>())?;
+    m.add("LevelOutsideModelError", m.py().get_type_bound::<LevelOutsideModelError>())?;
+    m.add("InvalidGeometryError", m.py().get_type_bound::<InvalidGeometryError>())?;
+    m.add("UnknownCaseError", m.py().get_type_bound::<UnknownCaseError>())?;
+    m.add("NotFactoredError", m.py().get_type_bound::<NotFactoredError>())?;
+    m.add("AgsiParseError", m.py().get_type_bound::<AgsiParseError>())?;
+    m.add("AgsiError", m.py().get_type_bound::<AgsiError>())?;
     Ok(())
 }
\ No newline at end of file